@@ -45,7 +45,11 @@ pub trait ChainReader {
     fn chain_state_reader(&self) -> &dyn ChainStateReader;
     fn get_block_info(&self, block_id: Option<HashValue>) -> Result<Option<BlockInfo>>;
     fn get_total_difficulty(&self) -> Result<U256>;
+    /// Check the block is on this chain, a saved block of another branch is not counted.
     fn exist_block(&self, block_id: HashValue) -> Result<bool>;
+    /// Check the block is saved in the storage, on this chain or any other branch,
+    /// without reading the block.
+    fn has_block_in_storage(&self, block_id: HashValue) -> Result<bool>;
     fn epoch_info(&self) -> Result<EpochInfo>;
     fn epoch(&self) -> &Epoch;
     fn get_epoch_info_by_number(&self, number: Option<BlockNumber>) -> Result<EpochInfo>;
@@ -2,19 +2,230 @@
 // SPDX-License-Identifier: Apache-2
 #![deny(clippy::integer_arithmetic)]
 
-use starcoin_vm_types::transaction::SignedUserTransaction;
+use starcoin_accumulator::AccumulatorMultiProof;
+use starcoin_crypto::HashValue;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::BlockHeader;
+use starcoin_types::startup_info::ChainStats;
+use starcoin_vm_types::genesis_config::ChainId;
+use starcoin_vm_types::on_chain_config::ConsensusConfig;
+use starcoin_vm_types::on_chain_resource::dao::ProposalState;
+use starcoin_vm_types::transaction::{SignedUserTransaction, TransactionOutput};
 
 mod chain;
 mod errors;
 pub mod message;
 mod service;
 
+/// The max number of blocks can be fetched by one batch request.
+pub const MAX_BLOCK_BATCH_SIZE: u64 = 1000;
+
 #[derive(Clone, Debug)]
 pub struct ExcludedTxns {
     pub discarded_txns: Vec<SignedUserTransaction>,
     pub untouched_txns: Vec<SignedUserTransaction>,
 }
 
+/// The result of repairing an account's state subtree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StateRepairResult {
+    /// The stored subtree is consistent with the replayed one, nothing is rewritten.
+    Consistent,
+    /// The stored subtree is rewritten with the replayed one.
+    Repaired,
+    /// The replayed state diverges from the chain at `block`, so the subtree can not be repaired.
+    Diverged {
+        block: BlockHeader,
+        replayed_state_root: HashValue,
+    },
+}
+
+/// The result of simulating a write set on the chain head state.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SimulateResult {
+    /// The state root after the write set is applied.
+    pub state_root: HashValue,
+    /// The access paths whose value is changed by the write set.
+    pub changed_paths: Vec<AccessPath>,
+}
+
+/// The consensus the chain is running.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsensusInfo {
+    /// The consensus strategy name, such as `dummy`, `argon`.
+    pub name: String,
+    /// The target block time of the current epoch, in milliseconds.
+    pub target_block_time: u64,
+}
+
+/// The parameters of the difficulty adjustment.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DifficultyParams {
+    /// The count of the recent blocks used to calculate the next difficulty.
+    pub window: u64,
+    /// The max ratio the difficulty can increase or reduce between two adjacent blocks,
+    /// it is compiled into the consensus rather than read from the on-chain config.
+    pub max_adjust_ratio: u64,
+}
+
+/// The result of submitting a transaction and mining it into a new head block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubmitAndMineResult {
+    /// The output of executing the transaction in the new block.
+    pub output: TransactionOutput,
+    /// The id of the mined block, which is connected as the main head.
+    pub head: HashValue,
+}
+
+/// The multi proof of the transactions included by one block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlockTxnMultiProof {
+    pub block_id: HashValue,
+    /// The transaction info hashes with their leaf indexes in the txn accumulator, sorted by index.
+    pub txn_infos: Vec<(u64, HashValue)>,
+    /// The proof of the txn infos against the block's txn accumulator root.
+    pub proof: AccumulatorMultiProof,
+}
+
+/// The multi proof of several transactions, grouped by the blocks which include them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TxnMultiProof {
+    pub blocks: Vec<BlockTxnMultiProof>,
+}
+
+/// The parameters the chain is built with at genesis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenesisConfigInfo {
+    pub chain_id: ChainId,
+    /// How many blocks to delay before rewarding miners.
+    pub reward_delay: u64,
+    pub consensus_config: ConsensusConfig,
+    /// The sha3 hash of the bcs encoded genesis gas schedule.
+    pub gas_schedule_hash: HashValue,
+}
+
+/// The mining reward schedule at the main head. The reward is not halved, but the reward per block
+/// is adjusted from the base reward every epoch by the block time target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RewardSchedule {
+    /// The base reward of a block, set by the on chain consensus config.
+    pub base_reward_per_block: u128,
+    /// The reward of a block in the current epoch.
+    pub reward_per_block: u128,
+    /// The reward of an uncle, in percent of the block reward.
+    pub reward_per_uncle_percent: u64,
+    /// How many blocks to delay before rewarding miners.
+    pub reward_delay: u64,
+    /// How many blocks in an epoch, the reward per block is adjusted at the epoch boundary.
+    pub epoch_block_count: u64,
+}
+
+/// The state root the next block on the main head would produce from the pending txns.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StateRootPreview {
+    pub state_root: HashValue,
+    /// How many pending txns would be included in the block.
+    pub included_txns: u64,
+}
+
+/// The roots recomputed by re-executing a block template on its parent.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TemplateValidation {
+    pub state_root: HashValue,
+    pub txn_accumulator_root: HashValue,
+    /// The block accumulator root of the parent.
+    pub block_accumulator_root: HashValue,
+    pub gas_used: u64,
+}
+
+/// The count of the user txns in a block by the payload type.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TxnTypeBreakdown {
+    pub scripts: u64,
+    /// The txns publishing or updating modules by a package.
+    pub modules: u64,
+    pub script_functions: u64,
+}
+
+/// The bcs serialized size of a block and its parts.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BlockSizeBreakdown {
+    pub header: u64,
+    pub body: u64,
+    pub total: u64,
+}
+
+/// A governance proposal of the STC dao at the main head.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalInfo {
+    pub id: u64,
+    pub proposer: AccountAddress,
+    pub state: ProposalState,
+    /// The time the voting ends, in milliseconds.
+    pub voting_deadline: u64,
+}
+
+/// The usage of a sender's sequence number.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SequenceUsage {
+    Free,
+    /// A txn with the sequence number is pending in the txpool.
+    PendingInPool,
+    /// The sequence number is below the sequence number of the account at the main head.
+    AlreadyConfirmed,
+}
+
+/// The STC balance of an account, the locked part is staked in a dao vote.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AccountBalances {
+    pub liquid: u128,
+    pub locked: u128,
+    pub total: u128,
+}
+
+/// The hit and miss counts and the current entry count of a cache.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CacheStat {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: u64,
+}
+
+/// A switch of the main chain to a branch which does not extend the old head.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReorgRecord {
+    /// The count of the blocks of the old main chain above the common ancestor.
+    pub depth: u64,
+    /// The latest block shared by the old and the new main chain.
+    pub common_ancestor: HashValue,
+    /// The local time in milliseconds the reorg happened at.
+    pub timestamp: u64,
+}
+
+/// The cache statistics of the chain reader service.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CacheStats {
+    /// The storage cache, which caches the blocks among the other chain data.
+    pub block_cache: CacheStat,
+    /// The on chain config cache at the main head.
+    pub config_cache: CacheStat,
+    /// The account state cache of the main head statedb.
+    pub state_cache: CacheStat,
+}
+
+/// A transaction newly admitted to the txpool.
+#[derive(Clone, Debug)]
+pub struct PendingTxnEvent {
+    pub txn: SignedUserTransaction,
+}
+
+/// A snapshot of the main chain statistics pushed to a subscriber.
+#[derive(Clone, Debug)]
+pub struct ChainStatsEvent {
+    pub stats: ChainStats,
+}
+
 pub use chain::{Chain, ChainReader, ChainWriter, ExecutedBlock, MintedUncleNumber, VerifiedBlock};
 pub use errors::*;
 pub use service::{ChainAsyncService, ReadableChainService, WriteableChainService};
@@ -1,21 +1,34 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
+use crate::{
+    AccountBalances, BlockSizeBreakdown, CacheStats, ChainStatsEvent, ConsensusInfo,
+    DifficultyParams, GenesisConfigInfo, PendingTxnEvent, ProposalInfo, ReorgRecord,
+    RewardSchedule, SequenceUsage, SimulateResult, StateRootPreview, SubmitAndMineResult,
+    TemplateValidation, TxnMultiProof, TxnTypeBreakdown,
+};
 use anyhow::Result;
+use futures::channel::oneshot;
+use starcoin_accumulator::AccumulatorProof;
 use starcoin_crypto::HashValue;
-use starcoin_service_registry::ServiceRequest;
+use starcoin_service_registry::{EventNotifier, ServiceRequest};
 use starcoin_types::block::BlockSummary;
 use starcoin_types::block::EpochUncleSummary;
 use starcoin_types::stress_test::TPS;
 use starcoin_types::transaction::BlockTransactionInfo;
 use starcoin_types::{
-    block::{Block, BlockHeader, BlockInfo, BlockNumber},
+    account_address::AccountAddress,
+    block::{Block, BlockHeader, BlockInfo, BlockNumber, BlockTemplate},
     contract_event::ContractEventInfo,
     filter::Filter,
-    startup_info::{ChainStatus, StartupInfo},
-    transaction::Transaction,
+    startup_info::{ChainInfo, ChainStats, ChainStatus, StartupInfo},
+    transaction::{SignedUserTransaction, Transaction},
+    write_set::WriteSet,
+    U256,
 };
+use starcoin_vm_types::language_storage::ModuleId;
 use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
+use starcoin_vm_types::token::token_code::TokenCode;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug)]
@@ -56,6 +69,187 @@ pub enum ChainRequest {
     GetEpochUnclesByNumber(Option<BlockNumber>),
     UnclePath(HashValue, HashValue),
     EpochUncleSummaryByNumber(Option<BlockNumber>),
+    GetBranchInfo(HashValue),
+    ReplayChain {
+        from: BlockNumber,
+        to: BlockNumber,
+    },
+    CreateBlockTemplateOn {
+        parent: HashValue,
+        max_txns: u64,
+    },
+    GetChainStats(),
+    GetMinGasPrice(),
+    SubscribePendingTxns(EventNotifier<PendingTxnEvent>),
+    GetBlockTxnRoot(HashValue),
+    SimulateWriteSet(WriteSet),
+    GetBlockFromHead(u64),
+    GetBlockTxnHashes(HashValue),
+    IsBestHead(),
+    GetConsensusInfo(),
+    GetSignedTxnBytes(HashValue),
+    GetDifficultyParams(),
+    SubmitAndMine(Box<SignedUserTransaction>),
+    GetTxnMultiProof(Vec<HashValue>),
+    GetFrameworkVersion(),
+    GetOldestAvailableBlock(),
+    GetFeeRevenue {
+        start: BlockNumber,
+        end: BlockNumber,
+    },
+    GetGenesisConfig(),
+    RebroadcastTxn(HashValue),
+    GetGasUsedRange {
+        start: BlockNumber,
+        end: BlockNumber,
+    },
+    EstimateTimeToBlock(BlockNumber),
+    GetAccumulatorSiblings(BlockNumber),
+    GetBlockTxnTypeBreakdown(HashValue),
+    VerifyTxnSignature(Box<SignedUserTransaction>),
+    SnapshotBalances(BlockNumber),
+    GetRewardSchedule(),
+    FindConflictingTxns(HashValue),
+    PreviewNextStateRoot(u64),
+    BalanceDeltas {
+        from: BlockNumber,
+        to: BlockNumber,
+    },
+    ValidateBlockTemplate(Box<BlockTemplate>),
+    GetTransactionByHash(HashValue),
+    GetBlockTouchedAddresses(HashValue),
+    GetNextEpochBoundary(),
+    GetBlocksByNumberRange {
+        start: BlockNumber,
+        count: u64,
+    },
+    GetBlocksByAuthor {
+        author: AccountAddress,
+        limit: u64,
+    },
+    GetBlockSizeBreakdown(HashValue),
+    GetBlocksByHashBatch(Vec<HashValue>),
+    SubscribeChainStats {
+        interval_secs: u64,
+        notifier: EventNotifier<ChainStatsEvent>,
+    },
+    VerifyHeaderProof {
+        header: Box<BlockHeader>,
+        proof: AccumulatorProof,
+    },
+    ListGovernanceProposals(),
+    CheckSequenceUsage {
+        sender: AccountAddress,
+        sequence_number: u64,
+    },
+    GetBranches(),
+    CreateBlockTemplateWithTxns(Vec<SignedUserTransaction>),
+    GetAccountBalances(AccountAddress),
+    ExistsBlock(HashValue),
+    GetFrozenSubtreeRoots(),
+    GetTxnIndexInBlock(HashValue),
+    GetCumulativeDifficulty(BlockNumber),
+    BatchVerifyTxnSignatures(Vec<SignedUserTransaction>),
+    GetCacheStats(),
+    GetTxnDependencies(SignedUserTransaction),
+    GetBlockPropagationDelay(HashValue),
+    GetAccountTokens(AccountAddress),
+    WillFitNextBlock(SignedUserTransaction),
+    GetRecentReorgs(u64),
+}
+
+impl ChainRequest {
+    /// The variant name, used as the metric label of the request.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChainRequest::CurrentHeader(..) => "CurrentHeader",
+            ChainRequest::GetHeaderByHash(..) => "GetHeaderByHash",
+            ChainRequest::HeadBlock(..) => "HeadBlock",
+            ChainRequest::GetBlockByNumber(..) => "GetBlockByNumber",
+            ChainRequest::GetBlockHeaderByNumber(..) => "GetBlockHeaderByNumber",
+            ChainRequest::GetBlockByHash(..) => "GetBlockByHash",
+            ChainRequest::GetBlockInfoByHash(..) => "GetBlockInfoByHash",
+            ChainRequest::GetBlockInfoByNumber(..) => "GetBlockInfoByNumber",
+            ChainRequest::GetStartupInfo(..) => "GetStartupInfo",
+            ChainRequest::GetHeadChainStatus(..) => "GetHeadChainStatus",
+            ChainRequest::GetEpochInfo(..) => "GetEpochInfo",
+            ChainRequest::GetEpochInfoByNumber(..) => "GetEpochInfoByNumber",
+            ChainRequest::GetGlobalTimeByNumber(..) => "GetGlobalTimeByNumber",
+            ChainRequest::GetTransactionBlock(..) => "GetTransactionBlock",
+            ChainRequest::GetTransaction(..) => "GetTransaction",
+            ChainRequest::GetTransactionInfo(..) => "GetTransactionInfo",
+            ChainRequest::GetBlockTransactionInfos(..) => "GetBlockTransactionInfos",
+            ChainRequest::GetTransactionInfoByBlockAndIndex { .. } => {
+                "GetTransactionInfoByBlockAndIndex"
+            }
+            ChainRequest::GetEventsByTxnHash { .. } => "GetEventsByTxnHash",
+            ChainRequest::GetBlocksByNumber(..) => "GetBlocksByNumber",
+            ChainRequest::MainEvents(..) => "MainEvents",
+            ChainRequest::GetBlockIds { .. } => "GetBlockIds",
+            ChainRequest::GetBlocks(..) => "GetBlocks",
+            ChainRequest::GetHeaders(..) => "GetHeaders",
+            ChainRequest::GetEpochUnclesByNumber(..) => "GetEpochUnclesByNumber",
+            ChainRequest::UnclePath(..) => "UnclePath",
+            ChainRequest::EpochUncleSummaryByNumber(..) => "EpochUncleSummaryByNumber",
+            ChainRequest::GetBranchInfo(..) => "GetBranchInfo",
+            ChainRequest::ReplayChain { .. } => "ReplayChain",
+            ChainRequest::CreateBlockTemplateOn { .. } => "CreateBlockTemplateOn",
+            ChainRequest::GetChainStats(..) => "GetChainStats",
+            ChainRequest::GetMinGasPrice(..) => "GetMinGasPrice",
+            ChainRequest::SubscribePendingTxns(..) => "SubscribePendingTxns",
+            ChainRequest::GetBlockTxnRoot(..) => "GetBlockTxnRoot",
+            ChainRequest::SimulateWriteSet(..) => "SimulateWriteSet",
+            ChainRequest::GetBlockFromHead(..) => "GetBlockFromHead",
+            ChainRequest::GetBlockTxnHashes(..) => "GetBlockTxnHashes",
+            ChainRequest::IsBestHead(..) => "IsBestHead",
+            ChainRequest::GetConsensusInfo(..) => "GetConsensusInfo",
+            ChainRequest::GetSignedTxnBytes(..) => "GetSignedTxnBytes",
+            ChainRequest::GetDifficultyParams(..) => "GetDifficultyParams",
+            ChainRequest::SubmitAndMine(..) => "SubmitAndMine",
+            ChainRequest::GetTxnMultiProof(..) => "GetTxnMultiProof",
+            ChainRequest::GetFrameworkVersion(..) => "GetFrameworkVersion",
+            ChainRequest::GetOldestAvailableBlock(..) => "GetOldestAvailableBlock",
+            ChainRequest::GetFeeRevenue { .. } => "GetFeeRevenue",
+            ChainRequest::GetGenesisConfig(..) => "GetGenesisConfig",
+            ChainRequest::RebroadcastTxn(..) => "RebroadcastTxn",
+            ChainRequest::GetGasUsedRange { .. } => "GetGasUsedRange",
+            ChainRequest::EstimateTimeToBlock(..) => "EstimateTimeToBlock",
+            ChainRequest::GetAccumulatorSiblings(..) => "GetAccumulatorSiblings",
+            ChainRequest::GetBlockTxnTypeBreakdown(..) => "GetBlockTxnTypeBreakdown",
+            ChainRequest::VerifyTxnSignature(..) => "VerifyTxnSignature",
+            ChainRequest::SnapshotBalances(..) => "SnapshotBalances",
+            ChainRequest::GetRewardSchedule(..) => "GetRewardSchedule",
+            ChainRequest::FindConflictingTxns(..) => "FindConflictingTxns",
+            ChainRequest::PreviewNextStateRoot(..) => "PreviewNextStateRoot",
+            ChainRequest::BalanceDeltas { .. } => "BalanceDeltas",
+            ChainRequest::ValidateBlockTemplate(..) => "ValidateBlockTemplate",
+            ChainRequest::GetTransactionByHash(..) => "GetTransactionByHash",
+            ChainRequest::GetBlockTouchedAddresses(..) => "GetBlockTouchedAddresses",
+            ChainRequest::GetNextEpochBoundary(..) => "GetNextEpochBoundary",
+            ChainRequest::GetBlocksByNumberRange { .. } => "GetBlocksByNumberRange",
+            ChainRequest::GetBlocksByAuthor { .. } => "GetBlocksByAuthor",
+            ChainRequest::GetBlockSizeBreakdown(..) => "GetBlockSizeBreakdown",
+            ChainRequest::GetBlocksByHashBatch(..) => "GetBlocksByHashBatch",
+            ChainRequest::SubscribeChainStats { .. } => "SubscribeChainStats",
+            ChainRequest::VerifyHeaderProof { .. } => "VerifyHeaderProof",
+            ChainRequest::ListGovernanceProposals(..) => "ListGovernanceProposals",
+            ChainRequest::CheckSequenceUsage { .. } => "CheckSequenceUsage",
+            ChainRequest::GetBranches(..) => "GetBranches",
+            ChainRequest::CreateBlockTemplateWithTxns(..) => "CreateBlockTemplateWithTxns",
+            ChainRequest::GetAccountBalances(..) => "GetAccountBalances",
+            ChainRequest::ExistsBlock(..) => "ExistsBlock",
+            ChainRequest::GetFrozenSubtreeRoots(..) => "GetFrozenSubtreeRoots",
+            ChainRequest::GetTxnIndexInBlock(..) => "GetTxnIndexInBlock",
+            ChainRequest::GetCumulativeDifficulty(..) => "GetCumulativeDifficulty",
+            ChainRequest::BatchVerifyTxnSignatures(..) => "BatchVerifyTxnSignatures",
+            ChainRequest::GetCacheStats(..) => "GetCacheStats",
+            ChainRequest::GetTxnDependencies(..) => "GetTxnDependencies",
+            ChainRequest::GetBlockPropagationDelay(..) => "GetBlockPropagationDelay",
+            ChainRequest::GetAccountTokens(..) => "GetAccountTokens",
+            ChainRequest::WillFitNextBlock(..) => "WillFitNextBlock",
+            ChainRequest::GetRecentReorgs(..) => "GetRecentReorgs",
+        }
+    }
 }
 
 impl ServiceRequest for ChainRequest {
@@ -89,4 +283,40 @@ pub enum ChainResponse {
     TPS(TPS),
     BlockSummaries(Vec<BlockSummary>),
     UncleSummary(EpochUncleSummary),
+    ChainInfoOption(Box<Option<ChainInfo>>),
+    BlockTemplate(Box<BlockTemplate>),
+    ChainStats(Box<ChainStats>),
+    U64(u64),
+    HashValueOption(Option<HashValue>),
+    SimulateResult(Box<SimulateResult>),
+    HashVecOption(Option<Vec<HashValue>>),
+    Bool(bool),
+    ConsensusInfo(Box<ConsensusInfo>),
+    BytesOption(Option<Vec<u8>>),
+    DifficultyParams(Box<DifficultyParams>),
+    SubmitAndMineReceiver(oneshot::Receiver<Result<SubmitAndMineResult>>),
+    TxnMultiProof(Box<TxnMultiProof>),
+    BlockNumber(BlockNumber),
+    U128(u128),
+    GenesisConfig(Box<GenesisConfigInfo>),
+    TxnTypeBreakdown(Box<TxnTypeBreakdown>),
+    BalanceSnapshot(Vec<(AccountAddress, u128)>),
+    RewardSchedule(Box<RewardSchedule>),
+    StateRootPreview(StateRootPreview),
+    BalanceDeltas(Vec<(AccountAddress, i128)>),
+    TemplateValidation(TemplateValidation),
+    SignedUserTransactionOption(Option<Box<SignedUserTransaction>>),
+    AddressVec(Vec<AccountAddress>),
+    SizeBreakdown(BlockSizeBreakdown),
+    Proposals(Vec<ProposalInfo>),
+    SequenceUsage(SequenceUsage),
+    Branches(Vec<(HashValue, U256)>),
+    AccountBalances(AccountBalances),
+    U64Option(Option<u64>),
+    DifficultyOption(Option<U256>),
+    BoolVec(Vec<bool>),
+    CacheStats(Box<CacheStats>),
+    ModuleIdVec(Vec<ModuleId>),
+    TokenBalances(Vec<(TokenCode, u128)>),
+    Reorgs(Vec<ReorgRecord>),
 }
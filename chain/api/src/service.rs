@@ -2,19 +2,31 @@
 // SPDX-License-Identifier: Apache-2
 
 use crate::message::{ChainRequest, ChainResponse};
+use crate::{
+    AccountBalances, BlockSizeBreakdown, CacheStats, ChainStatsEvent, ConsensusInfo,
+    DifficultyParams, GenesisConfigInfo, PendingTxnEvent, ProposalInfo, ReorgRecord,
+    RewardSchedule, SequenceUsage, SimulateResult, StateRootPreview, SubmitAndMineResult,
+    TemplateValidation, TxnMultiProof, TxnTypeBreakdown,
+};
 use anyhow::{bail, Result};
+use starcoin_accumulator::AccumulatorProof;
 use starcoin_crypto::HashValue;
-use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
+use starcoin_service_registry::{ActorService, EventNotifier, ServiceHandler, ServiceRef};
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::block::{BlockSummary, EpochUncleSummary};
 use starcoin_types::contract_event::{ContractEvent, ContractEventInfo};
 use starcoin_types::filter::Filter;
-use starcoin_types::startup_info::ChainStatus;
-use starcoin_types::transaction::{BlockTransactionInfo, Transaction};
+use starcoin_types::startup_info::{ChainInfo, ChainStats, ChainStatus};
+use starcoin_types::transaction::{BlockTransactionInfo, SignedUserTransaction, Transaction};
+use starcoin_types::write_set::WriteSet;
+use starcoin_types::U256;
 use starcoin_types::{
-    block::{Block, BlockHeader, BlockInfo, BlockNumber},
+    block::{Block, BlockHeader, BlockInfo, BlockNumber, BlockTemplate},
     startup_info::StartupInfo,
 };
+use starcoin_vm_types::language_storage::ModuleId;
 use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
+use starcoin_vm_types::token::token_code::TokenCode;
 
 /// Readable block chain service trait
 pub trait ReadableChainService {
@@ -59,6 +71,150 @@ pub trait ReadableChainService {
         &self,
         number: Option<BlockNumber>,
     ) -> Result<EpochUncleSummary>;
+    /// Get the chain info of the branch whose head is `tip`.
+    fn get_branch_info(&self, tip: HashValue) -> Result<Option<ChainInfo>>;
+    /// Re-execute main blocks in [from, to], return the first block whose state root diverges.
+    fn replay_chain(&self, from: BlockNumber, to: BlockNumber) -> Result<Option<BlockHeader>>;
+    /// Create a block template on the `parent` block, with at most `max_txns` pending txns.
+    fn create_block_template_on(&self, parent: HashValue, max_txns: u64) -> Result<BlockTemplate>;
+    /// Get a consistent snapshot of the main chain statistics.
+    fn get_chain_stats(&self) -> Result<ChainStats>;
+    /// Get the min gas price for txpool admission, the larger of the on-chain and node's floor.
+    fn get_min_gas_price(&self) -> Result<u64>;
+    /// Get the txn accumulator root stored when the block is connected.
+    fn get_block_txn_root(&self, block_id: HashValue) -> Result<Option<HashValue>>;
+    /// Apply the write set to a scratch copy of the head state, nothing is persisted.
+    fn simulate_write_set(&self, write_set: WriteSet) -> Result<SimulateResult>;
+    /// Get the main block at `offset` blocks below the head, offset 0 is the head block.
+    fn get_block_from_head(&self, offset: u64) -> Result<Option<Block>>;
+    /// Get the hashes of the user transactions in the block, in the block order.
+    fn get_block_txn_hashes(&self, block_id: HashValue) -> Result<Option<Vec<HashValue>>>;
+    /// Check whether the main head's total difficulty is not less than the best known peer head.
+    /// A peer's head is the one it reported when the connection was opened, later blocks of the
+    /// peer do not update it, so the result is as of connection time.
+    /// Always true if no peer head is known.
+    fn is_best_head(&self) -> Result<bool>;
+    /// Get the consensus strategy and the target block time of the main chain.
+    fn get_consensus_info(&self) -> Result<ConsensusInfo>;
+    /// Get the bcs bytes of the signed user transaction saved in storage, None if the transaction
+    /// is unknown or still pending.
+    fn get_signed_txn_bytes(&self, txn_hash: HashValue) -> Result<Option<Vec<u8>>>;
+    /// Get the difficulty adjustment parameters, the window is read from the on chain consensus config.
+    fn get_difficulty_params(&self) -> Result<DifficultyParams>;
+    /// Get one multi proof for each block including some of the main chain transactions.
+    fn get_txn_multiproof(&self, txn_hashes: Vec<HashValue>) -> Result<TxnMultiProof>;
+    /// Get the major version of the on chain framework at the main head.
+    fn get_framework_version(&self) -> Result<u64>;
+    /// Get the lowest number of the main chain blocks whose body is still in storage.
+    fn get_oldest_available_block(&self) -> Result<BlockNumber>;
+    /// Get the total gas fee paid by the user txns of the main blocks in [start, end].
+    /// Fails if the range is larger than the configured block range max count.
+    fn get_fee_revenue(&self, start: BlockNumber, end: BlockNumber) -> Result<u128>;
+    /// Get the chain id, reward delay, consensus config and gas schedule hash set at genesis.
+    fn get_genesis_config(&self) -> Result<GenesisConfigInfo>;
+    /// Get the total gas used by the main blocks in [start, end].
+    /// Fails if the range is larger than the configured block range max count.
+    fn get_gas_used_range(&self, start: BlockNumber, end: BlockNumber) -> Result<u128>;
+    /// Estimate the seconds until the main chain reaches the target block number,
+    /// by the median block time of the recent blocks. Return 0 if the target is reached.
+    fn estimate_time_to_block(&self, target: BlockNumber) -> Result<u64>;
+    /// Get the sibling hashes from the leaf of the main block to the root of the block accumulator
+    /// at the main head.
+    fn get_accumulator_siblings(&self, number: BlockNumber) -> Result<Vec<HashValue>>;
+    /// Count the user txns of the block by the payload type.
+    fn get_block_txn_type_breakdown(&self, block_id: HashValue) -> Result<TxnTypeBreakdown>;
+    /// Verify the signature of the txn, and the signing key against the sender's authentication
+    /// key at the main head, without executing the txn.
+    fn verify_txn_signature(&self, txn: SignedUserTransaction) -> Result<bool>;
+    /// The STC balances of all accounts at the main block of the number. The whole state tree at
+    /// the block is iterated, so the cost is O(accounts).
+    fn snapshot_balances(&self, number: BlockNumber) -> Result<Vec<(AccountAddress, u128)>>;
+    /// The mining reward schedule read from the on chain configs at the main head.
+    fn get_reward_schedule(&self) -> Result<RewardSchedule>;
+    /// The pending txns in the txpool with the same sender and sequence number as the txn,
+    /// but a different hash. The txn is looked up in the txpool, then in the storage.
+    /// Only conflicts between an executed txn and the pending txns are reported: the txpool
+    /// keeps at most one pending txn for a sender and sequence number, so two pending txns never
+    /// conflict, and the result is empty for a txn which is still pending.
+    fn find_conflicting_txns(&self, txn_hash: HashValue) -> Result<Vec<HashValue>>;
+    /// Execute up to `max_txns` pending txns on the main head as the next block would,
+    /// without connecting the block, and return the resulting state root.
+    fn preview_next_state_root(&self, max_txns: u64) -> Result<StateRootPreview>;
+    /// The STC balance changes of the accounts between the main blocks `from` and `to`,
+    /// an account created in between reports its whole balance.
+    fn get_balance_deltas(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<(AccountAddress, i128)>>;
+    /// Re-execute the template on its parent without saving, and return the recomputed roots,
+    /// or an error describing why the template is invalid.
+    fn validate_block_template(&self, template: BlockTemplate) -> Result<TemplateValidation>;
+    /// Get the signed user transaction saved in storage, None if the transaction is unknown,
+    /// still pending or not a user transaction.
+    fn get_transaction_by_hash(&self, txn_hash: HashValue)
+        -> Result<Option<SignedUserTransaction>>;
+    /// Get the sorted addresses written by the block's txns, the block is re-executed on its
+    /// parent state to collect the write set.
+    fn get_block_touched_addresses(&self, block_id: HashValue) -> Result<Vec<AccountAddress>>;
+    /// Get the number of the first block of the next epoch, it is the end block number of the
+    /// current epoch.
+    fn get_next_epoch_boundary(&self) -> Result<BlockNumber>;
+    /// Get at most `count` main blocks in ascending number order from `start`, the count is
+    /// clamped to the configured max and the range stops at the head.
+    fn main_blocks_by_range(&self, start: BlockNumber, count: u64) -> Result<Vec<Block>>;
+    /// Get at most `limit` main blocks produced by `author`, newest first.
+    fn get_blocks_by_author(&self, author: AccountAddress, limit: u64) -> Result<Vec<Block>>;
+    /// Get the bcs serialized size of the block header, the block body and the whole block.
+    fn get_block_size_breakdown(&self, block_id: HashValue) -> Result<BlockSizeBreakdown>;
+    /// Get the blocks by hash in the input order, None for an unknown block, the batch size is
+    /// limited to MAX_BLOCK_BATCH_SIZE.
+    fn get_blocks_by_hash_batch(&self, ids: Vec<HashValue>) -> Result<Vec<Option<Block>>>;
+    /// Verify the header is at its number of the main chain, by the proof against the block
+    /// accumulator root of the main head.
+    fn verify_header_proof(&self, header: BlockHeader, proof: AccumulatorProof) -> Result<bool>;
+    /// Get the proposals of the STC dao stored at the main head, sorted by the proposal id.
+    fn list_governance_proposals(&self) -> Result<Vec<ProposalInfo>>;
+    /// Check whether the sequence number of the sender is confirmed at the main head, or used by
+    /// a txn in the txpool.
+    fn check_sequence_usage(
+        &self,
+        sender: AccountAddress,
+        sequence_number: u64,
+    ) -> Result<SequenceUsage>;
+    /// Get the head of every known branch with its total difficulty, the heaviest first.
+    fn get_branches(&self) -> Result<Vec<(HashValue, U256)>>;
+    /// Create a block template on the main head with exactly the txns, the txns of a sender should
+    /// follow its sequence number at the head without gaps.
+    fn create_block_template_with_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Result<BlockTemplate>;
+    /// Get the liquid and locked STC balance of the account at the main head.
+    fn get_account_balances(&self, address: AccountAddress) -> Result<AccountBalances>;
+    /// Check the block is already saved, on the main chain or not.
+    fn exists_block(&self, block_id: HashValue) -> Result<bool>;
+    /// Get the frozen subtree roots of the main chain's block accumulator.
+    fn get_frozen_subtree_roots(&self) -> Result<Vec<HashValue>>;
+    /// Get the index of the transaction in the transaction infos of its block on the main chain,
+    /// the block metadata transaction is at index 0.
+    fn get_txn_index_in_block(&self, txn_hash: HashValue) -> Result<Option<u64>>;
+    /// Get the total difficulty from genesis through the main chain block of the number.
+    fn get_cumulative_difficulty(&self, number: BlockNumber) -> Result<Option<U256>>;
+    /// Verify the signatures of the txns against the authentication keys in the head state.
+    fn batch_verify_txn_signatures(&self, txns: Vec<SignedUserTransaction>) -> Result<Vec<bool>>;
+    /// Get the hit and miss counts and the sizes of the storage, config and state caches.
+    fn get_cache_stats(&self) -> Result<CacheStats>;
+    /// Get the transitive closure of the modules the txn payload depends on, resolved at the head state.
+    fn get_txn_dependencies(&self, txn: SignedUserTransaction) -> Result<Vec<ModuleId>>;
+    /// Get the seconds between the timestamp of a recent head block and the local time it is connected at.
+    fn get_block_propagation_delay(&self, block_id: HashValue) -> Result<u64>;
+    /// Get the balances of all the token types the account holds at the head state.
+    fn get_account_tokens(&self, address: AccountAddress) -> Result<Vec<(TokenCode, u128)>>;
+    /// Estimate whether the txn ranks within the gas budget of the next block given the pending txns.
+    fn will_fit_next_block(&self, txn: SignedUserTransaction) -> Result<bool>;
+    /// Get the latest reorgs of the main chain, the latest first.
+    fn get_recent_reorgs(&self, limit: u64) -> Result<Vec<ReorgRecord>>;
 }
 
 /// Writeable block chain service trait
@@ -126,6 +282,109 @@ pub trait ChainAsyncService:
         &self,
         number: Option<BlockNumber>,
     ) -> Result<EpochUncleSummary>;
+    async fn get_branch_info(&self, tip: HashValue) -> Result<Option<ChainInfo>>;
+    async fn replay_chain(&self, from: BlockNumber, to: BlockNumber)
+        -> Result<Option<BlockHeader>>;
+    async fn create_block_template_on(
+        &self,
+        parent: HashValue,
+        max_txns: u64,
+    ) -> Result<BlockTemplate>;
+    async fn get_chain_stats(&self) -> Result<ChainStats>;
+    async fn get_min_gas_price(&self) -> Result<u64>;
+    /// Subscribe the transactions newly admitted to the txpool,
+    /// the subscription is dropped once the notifier is closed.
+    async fn subscribe_pending_txns(&self, notifier: EventNotifier<PendingTxnEvent>) -> Result<()>;
+    async fn get_block_txn_root(&self, block_id: HashValue) -> Result<Option<HashValue>>;
+    async fn simulate_write_set(&self, write_set: WriteSet) -> Result<SimulateResult>;
+    async fn get_block_from_head(&self, offset: u64) -> Result<Option<Block>>;
+    async fn get_block_txn_hashes(&self, block_id: HashValue) -> Result<Option<Vec<HashValue>>>;
+    /// Compare the main head with the peer heads as of connection time,
+    /// see `ReadableChainService::is_best_head`.
+    async fn is_best_head(&self) -> Result<bool>;
+    async fn get_consensus_info(&self) -> Result<ConsensusInfo>;
+    async fn get_signed_txn_bytes(&self, txn_hash: HashValue) -> Result<Option<Vec<u8>>>;
+    async fn get_difficulty_params(&self) -> Result<DifficultyParams>;
+    /// Admit the transaction, mine it into a new block and return its output once the block
+    /// connector connects the block as the new main head. Only supported if the miner config
+    /// enable_submit_and_mine is set.
+    async fn submit_and_mine(&self, txn: SignedUserTransaction) -> Result<SubmitAndMineResult>;
+    async fn get_txn_multiproof(&self, txn_hashes: Vec<HashValue>) -> Result<TxnMultiProof>;
+    async fn get_framework_version(&self) -> Result<u64>;
+    async fn get_oldest_available_block(&self) -> Result<BlockNumber>;
+    async fn get_fee_revenue(&self, start: BlockNumber, end: BlockNumber) -> Result<u128>;
+    async fn get_genesis_config(&self) -> Result<GenesisConfigInfo>;
+    /// Re-broadcast the user txn in the txpool or storage to the peers,
+    /// return false if the txn is unknown. Fails if the network service is absent.
+    async fn rebroadcast_txn(&self, txn_hash: HashValue) -> Result<bool>;
+    async fn get_gas_used_range(&self, start: BlockNumber, end: BlockNumber) -> Result<u128>;
+    async fn estimate_time_to_block(&self, target: BlockNumber) -> Result<u64>;
+    async fn get_accumulator_siblings(&self, number: BlockNumber) -> Result<Vec<HashValue>>;
+    async fn get_block_txn_type_breakdown(&self, block_id: HashValue) -> Result<TxnTypeBreakdown>;
+    async fn verify_txn_signature(&self, txn: SignedUserTransaction) -> Result<bool>;
+    /// Snapshot the STC balances of all accounts at the main block of the number, the whole state
+    /// tree is iterated, so the cost is O(accounts).
+    async fn snapshot_balances(&self, number: BlockNumber) -> Result<Vec<(AccountAddress, u128)>>;
+    async fn get_reward_schedule(&self) -> Result<RewardSchedule>;
+    /// The pending txns conflicting with the executed txn, see
+    /// `ReadableChainService::find_conflicting_txns`.
+    async fn find_conflicting_txns(&self, txn_hash: HashValue) -> Result<Vec<HashValue>>;
+    async fn preview_next_state_root(&self, max_txns: u64) -> Result<StateRootPreview>;
+    async fn get_balance_deltas(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<(AccountAddress, i128)>>;
+    async fn validate_block_template(&self, template: BlockTemplate) -> Result<TemplateValidation>;
+    async fn get_transaction_by_hash(
+        &self,
+        txn_hash: &HashValue,
+    ) -> Result<Option<SignedUserTransaction>>;
+    async fn get_block_touched_addresses(&self, block_id: HashValue)
+        -> Result<Vec<AccountAddress>>;
+    async fn get_next_epoch_boundary(&self) -> Result<BlockNumber>;
+    async fn main_blocks_by_range(&self, start: BlockNumber, count: u64) -> Result<Vec<Block>>;
+    async fn get_blocks_by_author(&self, author: AccountAddress, limit: u64) -> Result<Vec<Block>>;
+    async fn get_block_size_breakdown(&self, block_id: HashValue) -> Result<BlockSizeBreakdown>;
+    async fn get_blocks_by_hash_batch(&self, hashes: Vec<HashValue>) -> Result<Vec<Option<Block>>>;
+    /// Push the main chain stats to the notifier every `interval_secs` seconds,
+    /// the subscription is dropped once the notifier is closed.
+    async fn subscribe_chain_stats(
+        &self,
+        interval_secs: u64,
+        notifier: EventNotifier<ChainStatsEvent>,
+    ) -> Result<()>;
+    async fn verify_header_proof(
+        &self,
+        header: BlockHeader,
+        proof: AccumulatorProof,
+    ) -> Result<bool>;
+    async fn list_governance_proposals(&self) -> Result<Vec<ProposalInfo>>;
+    async fn check_sequence_usage(
+        &self,
+        sender: AccountAddress,
+        sequence_number: u64,
+    ) -> Result<SequenceUsage>;
+    async fn get_branches(&self) -> Result<Vec<(HashValue, U256)>>;
+    async fn create_block_template_with_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Result<BlockTemplate>;
+    async fn get_account_balances(&self, address: AccountAddress) -> Result<AccountBalances>;
+    async fn exists_block(&self, block_id: HashValue) -> Result<bool>;
+    async fn get_frozen_subtree_roots(&self) -> Result<Vec<HashValue>>;
+    async fn get_txn_index_in_block(&self, txn_hash: HashValue) -> Result<Option<u64>>;
+    async fn get_cumulative_difficulty(&self, number: BlockNumber) -> Result<Option<U256>>;
+    async fn batch_verify_txn_signatures(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Result<Vec<bool>>;
+    async fn get_cache_stats(&self) -> Result<CacheStats>;
+    async fn get_txn_dependencies(&self, txn: SignedUserTransaction) -> Result<Vec<ModuleId>>;
+    async fn get_block_propagation_delay(&self, block_id: HashValue) -> Result<u64>;
+    async fn get_account_tokens(&self, address: AccountAddress) -> Result<Vec<(TokenCode, u128)>>;
+    async fn will_fit_next_block(&self, txn: SignedUserTransaction) -> Result<bool>;
+    async fn get_recent_reorgs(&self, limit: u64) -> Result<Vec<ReorgRecord>>;
 }
 
 #[async_trait::async_trait]
@@ -447,4 +706,626 @@ where
             bail!("get uncle path error.")
         }
     }
+
+    async fn get_branch_info(&self, tip: HashValue) -> Result<Option<ChainInfo>> {
+        let response = self.send(ChainRequest::GetBranchInfo(tip)).await??;
+        if let ChainResponse::ChainInfoOption(chain_info) = response {
+            Ok(*chain_info)
+        } else {
+            bail!("get branch info error.")
+        }
+    }
+
+    async fn replay_chain(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Option<BlockHeader>> {
+        let response = self.send(ChainRequest::ReplayChain { from, to }).await??;
+        if let ChainResponse::BlockHeaderOption(header) = response {
+            Ok(*header)
+        } else {
+            bail!("replay chain error.")
+        }
+    }
+
+    async fn create_block_template_on(
+        &self,
+        parent: HashValue,
+        max_txns: u64,
+    ) -> Result<BlockTemplate> {
+        let response = self
+            .send(ChainRequest::CreateBlockTemplateOn { parent, max_txns })
+            .await??;
+        if let ChainResponse::BlockTemplate(template) = response {
+            Ok(*template)
+        } else {
+            bail!("create block template on parent error.")
+        }
+    }
+
+    async fn get_chain_stats(&self) -> Result<ChainStats> {
+        let response = self.send(ChainRequest::GetChainStats()).await??;
+        if let ChainResponse::ChainStats(stats) = response {
+            Ok(*stats)
+        } else {
+            bail!("get chain stats error.")
+        }
+    }
+
+    async fn get_min_gas_price(&self) -> Result<u64> {
+        let response = self.send(ChainRequest::GetMinGasPrice()).await??;
+        if let ChainResponse::U64(min_gas_price) = response {
+            Ok(min_gas_price)
+        } else {
+            bail!("get min gas price error.")
+        }
+    }
+
+    async fn subscribe_pending_txns(&self, notifier: EventNotifier<PendingTxnEvent>) -> Result<()> {
+        let response = self
+            .send(ChainRequest::SubscribePendingTxns(notifier))
+            .await??;
+        if let ChainResponse::None = response {
+            Ok(())
+        } else {
+            bail!("subscribe pending txns error.")
+        }
+    }
+
+    async fn get_block_txn_root(&self, block_id: HashValue) -> Result<Option<HashValue>> {
+        let response = self.send(ChainRequest::GetBlockTxnRoot(block_id)).await??;
+        if let ChainResponse::HashValueOption(txn_root) = response {
+            Ok(txn_root)
+        } else {
+            bail!("get block txn root error.")
+        }
+    }
+
+    async fn simulate_write_set(&self, write_set: WriteSet) -> Result<SimulateResult> {
+        let response = self
+            .send(ChainRequest::SimulateWriteSet(write_set))
+            .await??;
+        if let ChainResponse::SimulateResult(result) = response {
+            Ok(*result)
+        } else {
+            bail!("simulate write set error.")
+        }
+    }
+
+    async fn get_block_from_head(&self, offset: u64) -> Result<Option<Block>> {
+        let response = self.send(ChainRequest::GetBlockFromHead(offset)).await??;
+        if let ChainResponse::BlockOption(block) = response {
+            Ok(block.map(|b| *b))
+        } else {
+            bail!("get block from head error.")
+        }
+    }
+
+    async fn get_block_txn_hashes(&self, block_id: HashValue) -> Result<Option<Vec<HashValue>>> {
+        let response = self
+            .send(ChainRequest::GetBlockTxnHashes(block_id))
+            .await??;
+        if let ChainResponse::HashVecOption(txn_hashes) = response {
+            Ok(txn_hashes)
+        } else {
+            bail!("get block txn hashes error.")
+        }
+    }
+
+    async fn is_best_head(&self) -> Result<bool> {
+        let response = self.send(ChainRequest::IsBestHead()).await??;
+        if let ChainResponse::Bool(is_best) = response {
+            Ok(is_best)
+        } else {
+            bail!("is best head error.")
+        }
+    }
+
+    async fn get_consensus_info(&self) -> Result<ConsensusInfo> {
+        let response = self.send(ChainRequest::GetConsensusInfo()).await??;
+        if let ChainResponse::ConsensusInfo(consensus_info) = response {
+            Ok(*consensus_info)
+        } else {
+            bail!("get consensus info error.")
+        }
+    }
+
+    async fn get_signed_txn_bytes(&self, txn_hash: HashValue) -> Result<Option<Vec<u8>>> {
+        let response = self
+            .send(ChainRequest::GetSignedTxnBytes(txn_hash))
+            .await??;
+        if let ChainResponse::BytesOption(bytes) = response {
+            Ok(bytes)
+        } else {
+            bail!("get signed txn bytes error.")
+        }
+    }
+
+    async fn get_difficulty_params(&self) -> Result<DifficultyParams> {
+        let response = self.send(ChainRequest::GetDifficultyParams()).await??;
+        if let ChainResponse::DifficultyParams(params) = response {
+            Ok(*params)
+        } else {
+            bail!("get difficulty params error.")
+        }
+    }
+
+    async fn submit_and_mine(&self, txn: SignedUserTransaction) -> Result<SubmitAndMineResult> {
+        let response = self
+            .send(ChainRequest::SubmitAndMine(Box::new(txn)))
+            .await??;
+        if let ChainResponse::SubmitAndMineReceiver(receiver) = response {
+            receiver.await?
+        } else {
+            bail!("submit and mine error.")
+        }
+    }
+
+    async fn get_txn_multiproof(&self, txn_hashes: Vec<HashValue>) -> Result<TxnMultiProof> {
+        let response = self
+            .send(ChainRequest::GetTxnMultiProof(txn_hashes))
+            .await??;
+        if let ChainResponse::TxnMultiProof(proof) = response {
+            Ok(*proof)
+        } else {
+            bail!("get txn multiproof error.")
+        }
+    }
+
+    async fn get_framework_version(&self) -> Result<u64> {
+        let response = self.send(ChainRequest::GetFrameworkVersion()).await??;
+        if let ChainResponse::U64(version) = response {
+            Ok(version)
+        } else {
+            bail!("get framework version error.")
+        }
+    }
+
+    async fn get_oldest_available_block(&self) -> Result<BlockNumber> {
+        let response = self.send(ChainRequest::GetOldestAvailableBlock()).await??;
+        if let ChainResponse::BlockNumber(number) = response {
+            Ok(number)
+        } else {
+            bail!("get oldest available block error.")
+        }
+    }
+
+    async fn get_fee_revenue(&self, start: BlockNumber, end: BlockNumber) -> Result<u128> {
+        let response = self
+            .send(ChainRequest::GetFeeRevenue { start, end })
+            .await??;
+        if let ChainResponse::U128(revenue) = response {
+            Ok(revenue)
+        } else {
+            bail!("get fee revenue error.")
+        }
+    }
+
+    async fn get_genesis_config(&self) -> Result<GenesisConfigInfo> {
+        let response = self.send(ChainRequest::GetGenesisConfig()).await??;
+        if let ChainResponse::GenesisConfig(config) = response {
+            Ok(*config)
+        } else {
+            bail!("get genesis config error.")
+        }
+    }
+
+    async fn rebroadcast_txn(&self, txn_hash: HashValue) -> Result<bool> {
+        let response = self.send(ChainRequest::RebroadcastTxn(txn_hash)).await??;
+        if let ChainResponse::Bool(broadcast) = response {
+            Ok(broadcast)
+        } else {
+            bail!("rebroadcast txn error.")
+        }
+    }
+
+    async fn get_gas_used_range(&self, start: BlockNumber, end: BlockNumber) -> Result<u128> {
+        let response = self
+            .send(ChainRequest::GetGasUsedRange { start, end })
+            .await??;
+        if let ChainResponse::U128(gas_used) = response {
+            Ok(gas_used)
+        } else {
+            bail!("get gas used range error.")
+        }
+    }
+
+    async fn estimate_time_to_block(&self, target: BlockNumber) -> Result<u64> {
+        let response = self
+            .send(ChainRequest::EstimateTimeToBlock(target))
+            .await??;
+        if let ChainResponse::U64(seconds) = response {
+            Ok(seconds)
+        } else {
+            bail!("estimate time to block error.")
+        }
+    }
+
+    async fn get_accumulator_siblings(&self, number: BlockNumber) -> Result<Vec<HashValue>> {
+        let response = self
+            .send(ChainRequest::GetAccumulatorSiblings(number))
+            .await??;
+        if let ChainResponse::HashVec(siblings) = response {
+            Ok(siblings)
+        } else {
+            bail!("get accumulator siblings error.")
+        }
+    }
+
+    async fn get_block_txn_type_breakdown(&self, block_id: HashValue) -> Result<TxnTypeBreakdown> {
+        let response = self
+            .send(ChainRequest::GetBlockTxnTypeBreakdown(block_id))
+            .await??;
+        if let ChainResponse::TxnTypeBreakdown(breakdown) = response {
+            Ok(*breakdown)
+        } else {
+            bail!("get block txn type breakdown error.")
+        }
+    }
+
+    async fn verify_txn_signature(&self, txn: SignedUserTransaction) -> Result<bool> {
+        let response = self
+            .send(ChainRequest::VerifyTxnSignature(Box::new(txn)))
+            .await??;
+        if let ChainResponse::Bool(valid) = response {
+            Ok(valid)
+        } else {
+            bail!("verify txn signature error.")
+        }
+    }
+
+    async fn snapshot_balances(&self, number: BlockNumber) -> Result<Vec<(AccountAddress, u128)>> {
+        let response = self.send(ChainRequest::SnapshotBalances(number)).await??;
+        if let ChainResponse::BalanceSnapshot(balances) = response {
+            Ok(balances)
+        } else {
+            bail!("snapshot balances error.")
+        }
+    }
+
+    async fn get_reward_schedule(&self) -> Result<RewardSchedule> {
+        let response = self.send(ChainRequest::GetRewardSchedule()).await??;
+        if let ChainResponse::RewardSchedule(schedule) = response {
+            Ok(*schedule)
+        } else {
+            bail!("get reward schedule error.")
+        }
+    }
+
+    async fn find_conflicting_txns(&self, txn_hash: HashValue) -> Result<Vec<HashValue>> {
+        let response = self
+            .send(ChainRequest::FindConflictingTxns(txn_hash))
+            .await??;
+        if let ChainResponse::HashVec(txn_hashes) = response {
+            Ok(txn_hashes)
+        } else {
+            bail!("find conflicting txns error.")
+        }
+    }
+
+    async fn preview_next_state_root(&self, max_txns: u64) -> Result<StateRootPreview> {
+        let response = self
+            .send(ChainRequest::PreviewNextStateRoot(max_txns))
+            .await??;
+        if let ChainResponse::StateRootPreview(preview) = response {
+            Ok(preview)
+        } else {
+            bail!("preview next state root error.")
+        }
+    }
+
+    async fn get_balance_deltas(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<(AccountAddress, i128)>> {
+        let response = self
+            .send(ChainRequest::BalanceDeltas { from, to })
+            .await??;
+        if let ChainResponse::BalanceDeltas(deltas) = response {
+            Ok(deltas)
+        } else {
+            bail!("get balance deltas error.")
+        }
+    }
+
+    async fn validate_block_template(&self, template: BlockTemplate) -> Result<TemplateValidation> {
+        let response = self
+            .send(ChainRequest::ValidateBlockTemplate(Box::new(template)))
+            .await??;
+        if let ChainResponse::TemplateValidation(validation) = response {
+            Ok(validation)
+        } else {
+            bail!("validate block template error.")
+        }
+    }
+
+    async fn get_transaction_by_hash(
+        &self,
+        txn_hash: &HashValue,
+    ) -> Result<Option<SignedUserTransaction>> {
+        let response = self
+            .send(ChainRequest::GetTransactionByHash(*txn_hash))
+            .await??;
+        if let ChainResponse::SignedUserTransactionOption(txn) = response {
+            Ok(txn.map(|txn| *txn))
+        } else {
+            bail!("get transaction by hash error.")
+        }
+    }
+
+    async fn get_block_touched_addresses(
+        &self,
+        block_id: HashValue,
+    ) -> Result<Vec<AccountAddress>> {
+        let response = self
+            .send(ChainRequest::GetBlockTouchedAddresses(block_id))
+            .await??;
+        if let ChainResponse::AddressVec(addresses) = response {
+            Ok(addresses)
+        } else {
+            bail!("get block touched addresses error.")
+        }
+    }
+
+    async fn get_next_epoch_boundary(&self) -> Result<BlockNumber> {
+        let response = self.send(ChainRequest::GetNextEpochBoundary()).await??;
+        if let ChainResponse::BlockNumber(number) = response {
+            Ok(number)
+        } else {
+            bail!("get next epoch boundary error.")
+        }
+    }
+
+    async fn main_blocks_by_range(&self, start: BlockNumber, count: u64) -> Result<Vec<Block>> {
+        let response = self
+            .send(ChainRequest::GetBlocksByNumberRange { start, count })
+            .await??;
+        if let ChainResponse::BlockVec(blocks) = response {
+            Ok(blocks)
+        } else {
+            bail!("get blocks by range error.")
+        }
+    }
+
+    async fn get_blocks_by_author(&self, author: AccountAddress, limit: u64) -> Result<Vec<Block>> {
+        let response = self
+            .send(ChainRequest::GetBlocksByAuthor { author, limit })
+            .await??;
+        if let ChainResponse::BlockVec(blocks) = response {
+            Ok(blocks)
+        } else {
+            bail!("get blocks by author error.")
+        }
+    }
+
+    async fn get_block_size_breakdown(&self, block_id: HashValue) -> Result<BlockSizeBreakdown> {
+        let response = self
+            .send(ChainRequest::GetBlockSizeBreakdown(block_id))
+            .await??;
+        if let ChainResponse::SizeBreakdown(breakdown) = response {
+            Ok(breakdown)
+        } else {
+            bail!("get block size breakdown error.")
+        }
+    }
+
+    async fn get_blocks_by_hash_batch(&self, hashes: Vec<HashValue>) -> Result<Vec<Option<Block>>> {
+        let response = self
+            .send(ChainRequest::GetBlocksByHashBatch(hashes))
+            .await??;
+        if let ChainResponse::BlockOptionVec(blocks) = response {
+            Ok(blocks)
+        } else {
+            bail!("get blocks by hash batch error.")
+        }
+    }
+
+    async fn subscribe_chain_stats(
+        &self,
+        interval_secs: u64,
+        notifier: EventNotifier<ChainStatsEvent>,
+    ) -> Result<()> {
+        let response = self
+            .send(ChainRequest::SubscribeChainStats {
+                interval_secs,
+                notifier,
+            })
+            .await??;
+        if let ChainResponse::None = response {
+            Ok(())
+        } else {
+            bail!("subscribe chain stats error.")
+        }
+    }
+
+    async fn verify_header_proof(
+        &self,
+        header: BlockHeader,
+        proof: AccumulatorProof,
+    ) -> Result<bool> {
+        let response = self
+            .send(ChainRequest::VerifyHeaderProof {
+                header: Box::new(header),
+                proof,
+            })
+            .await??;
+        if let ChainResponse::Bool(valid) = response {
+            Ok(valid)
+        } else {
+            bail!("verify header proof error.")
+        }
+    }
+
+    async fn list_governance_proposals(&self) -> Result<Vec<ProposalInfo>> {
+        let response = self.send(ChainRequest::ListGovernanceProposals()).await??;
+        if let ChainResponse::Proposals(proposals) = response {
+            Ok(proposals)
+        } else {
+            bail!("list governance proposals error.")
+        }
+    }
+
+    async fn check_sequence_usage(
+        &self,
+        sender: AccountAddress,
+        sequence_number: u64,
+    ) -> Result<SequenceUsage> {
+        let response = self
+            .send(ChainRequest::CheckSequenceUsage {
+                sender,
+                sequence_number,
+            })
+            .await??;
+        if let ChainResponse::SequenceUsage(usage) = response {
+            Ok(usage)
+        } else {
+            bail!("check sequence usage error.")
+        }
+    }
+
+    async fn get_branches(&self) -> Result<Vec<(HashValue, U256)>> {
+        let response = self.send(ChainRequest::GetBranches()).await??;
+        if let ChainResponse::Branches(branches) = response {
+            Ok(branches)
+        } else {
+            bail!("get branches error.")
+        }
+    }
+
+    async fn create_block_template_with_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Result<BlockTemplate> {
+        let response = self
+            .send(ChainRequest::CreateBlockTemplateWithTxns(txns))
+            .await??;
+        if let ChainResponse::BlockTemplate(template) = response {
+            Ok(*template)
+        } else {
+            bail!("create block template with txns error.")
+        }
+    }
+
+    async fn get_account_balances(&self, address: AccountAddress) -> Result<AccountBalances> {
+        let response = self
+            .send(ChainRequest::GetAccountBalances(address))
+            .await??;
+        if let ChainResponse::AccountBalances(balances) = response {
+            Ok(balances)
+        } else {
+            bail!("get account balances error.")
+        }
+    }
+
+    async fn exists_block(&self, block_id: HashValue) -> Result<bool> {
+        let response = self.send(ChainRequest::ExistsBlock(block_id)).await??;
+        if let ChainResponse::Bool(exists) = response {
+            Ok(exists)
+        } else {
+            bail!("exists block error.")
+        }
+    }
+
+    async fn get_frozen_subtree_roots(&self) -> Result<Vec<HashValue>> {
+        let response = self.send(ChainRequest::GetFrozenSubtreeRoots()).await??;
+        if let ChainResponse::HashVec(roots) = response {
+            Ok(roots)
+        } else {
+            bail!("get frozen subtree roots error.")
+        }
+    }
+
+    async fn get_txn_index_in_block(&self, txn_hash: HashValue) -> Result<Option<u64>> {
+        let response = self
+            .send(ChainRequest::GetTxnIndexInBlock(txn_hash))
+            .await??;
+        if let ChainResponse::U64Option(index) = response {
+            Ok(index)
+        } else {
+            bail!("get txn index in block error.")
+        }
+    }
+
+    async fn get_cumulative_difficulty(&self, number: BlockNumber) -> Result<Option<U256>> {
+        let response = self
+            .send(ChainRequest::GetCumulativeDifficulty(number))
+            .await??;
+        if let ChainResponse::DifficultyOption(difficulty) = response {
+            Ok(difficulty)
+        } else {
+            bail!("get cumulative difficulty error.")
+        }
+    }
+
+    async fn batch_verify_txn_signatures(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Result<Vec<bool>> {
+        let response = self
+            .send(ChainRequest::BatchVerifyTxnSignatures(txns))
+            .await??;
+        if let ChainResponse::BoolVec(results) = response {
+            Ok(results)
+        } else {
+            bail!("batch verify txn signatures error.")
+        }
+    }
+
+    async fn get_cache_stats(&self) -> Result<CacheStats> {
+        let response = self.send(ChainRequest::GetCacheStats()).await??;
+        if let ChainResponse::CacheStats(stats) = response {
+            Ok(*stats)
+        } else {
+            bail!("get cache stats error.")
+        }
+    }
+
+    async fn get_txn_dependencies(&self, txn: SignedUserTransaction) -> Result<Vec<ModuleId>> {
+        let response = self.send(ChainRequest::GetTxnDependencies(txn)).await??;
+        if let ChainResponse::ModuleIdVec(module_ids) = response {
+            Ok(module_ids)
+        } else {
+            bail!("get txn dependencies error.")
+        }
+    }
+
+    async fn get_block_propagation_delay(&self, block_id: HashValue) -> Result<u64> {
+        let response = self
+            .send(ChainRequest::GetBlockPropagationDelay(block_id))
+            .await??;
+        if let ChainResponse::U64(seconds) = response {
+            Ok(seconds)
+        } else {
+            bail!("get block propagation delay error.")
+        }
+    }
+
+    async fn get_account_tokens(&self, address: AccountAddress) -> Result<Vec<(TokenCode, u128)>> {
+        let response = self.send(ChainRequest::GetAccountTokens(address)).await??;
+        if let ChainResponse::TokenBalances(balances) = response {
+            Ok(balances)
+        } else {
+            bail!("get account tokens error.")
+        }
+    }
+
+    async fn will_fit_next_block(&self, txn: SignedUserTransaction) -> Result<bool> {
+        let response = self.send(ChainRequest::WillFitNextBlock(txn)).await??;
+        if let ChainResponse::Bool(fit) = response {
+            Ok(fit)
+        } else {
+            bail!("will fit next block error.")
+        }
+    }
+
+    async fn get_recent_reorgs(&self, limit: u64) -> Result<Vec<ReorgRecord>> {
+        let response = self.send(ChainRequest::GetRecentReorgs(limit)).await??;
+        if let ChainResponse::Reorgs(reorgs) = response {
+            Ok(reorgs)
+        } else {
+            bail!("get recent reorgs error.")
+        }
+    }
 }
@@ -6,7 +6,9 @@ use crypto::HashValue;
 use logger::prelude::*;
 use starcoin_accumulator::{node::AccumulatorStoreType, Accumulator, MerkleAccumulator};
 use starcoin_chain_api::ExcludedTxns;
-use starcoin_executor::{execute_block_transactions, execute_transactions};
+use starcoin_executor::{
+    execute_block_transactions, execute_transactions, TransactionCheck, TransactionValidator,
+};
 use starcoin_state_api::{ChainStateReader, ChainStateWriter};
 use starcoin_statedb::ChainStateDB;
 use starcoin_types::genesis_config::{ChainId, ConsensusStrategy};
@@ -133,11 +135,32 @@ impl OpenedBlock {
     /// as the internal state may be corrupted.
     /// TODO: make the function can be called again even last call returns error.  
     pub fn push_txns(&mut self, user_txns: Vec<SignedUserTransaction>) -> Result<ExcludedTxns> {
-        let mut txns: Vec<_> = user_txns
-            .iter()
-            .cloned()
-            .map(Transaction::UserTransaction)
-            .collect();
+        let mut discard_txns: Vec<SignedUserTransaction> = Vec::new();
+        // the state checks run against the state before the block, and would reject a txn funded
+        // or created by an earlier txn of the block, so leave them to the VM.
+        let validator = TransactionValidator::with_checks(
+            self.chain_id,
+            self.block_meta.timestamp() / 1000,
+            TransactionCheck::stateless(),
+        );
+        let mut txns = Vec::with_capacity(user_txns.len());
+        for txn in user_txns {
+            match validator.validate(&self.state, &txn) {
+                Ok(Some(reason)) => {
+                    debug!("discard txn {} before execute: {}", txn.id(), reason);
+                    discard_txns.push(txn);
+                }
+                Ok(None) => txns.push(Transaction::UserTransaction(txn)),
+                Err(e) => {
+                    debug!(
+                        "discard txn {} before execute, validate error: {:?}",
+                        txn.id(),
+                        e
+                    );
+                    discard_txns.push(txn);
+                }
+            }
+        }
 
         let txn_outputs = {
             let gas_left = self.gas_limit.checked_sub(self.gas_used).ok_or_else(|| {
@@ -158,7 +181,6 @@ impl OpenedBlock {
                 .collect()
         };
 
-        let mut discard_txns: Vec<SignedUserTransaction> = Vec::new();
         debug_assert_eq!(txns.len(), txn_outputs.len());
         for (txn, output) in txns.into_iter().zip(txn_outputs.into_iter()) {
             let txn_hash = txn.id();
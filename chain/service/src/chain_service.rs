@@ -1,34 +1,92 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{format_err, Error, Result};
+use crate::metrics::{CHAIN_REQUEST_HISTOGRAM, SELF_AUDIT_FAILURES};
+use crate::on_chain_config_cache::OnChainConfigCache;
+use anyhow::{bail, ensure, format_err, Error, Result};
+use futures::channel::oneshot;
+use futures::StreamExt;
+use network_api::messages::PeerEvent;
+use network_api::TxnBroadcaster;
+use rayon::prelude::*;
+use starcoin_accumulator::Accumulator;
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::message::{ChainRequest, ChainResponse};
-use starcoin_chain_api::{ChainReader, ChainWriter, ReadableChainService};
+use starcoin_chain_api::{
+    AccountBalances, BlockSizeBreakdown, CacheStat, CacheStats, ChainReader, ChainStatsEvent,
+    ChainWriter, ConsensusInfo, DifficultyParams, GenesisConfigInfo, PendingTxnEvent, ProposalInfo,
+    ReadableChainService, ReorgRecord, RewardSchedule, SequenceUsage, SimulateResult,
+    StateRootPreview, SubmitAndMineResult, TemplateValidation, TxnMultiProof, TxnTypeBreakdown,
+    MAX_BLOCK_BATCH_SIZE,
+};
 use starcoin_config::NodeConfig;
+use starcoin_consensus::difficulty::MAX_TARGET_ADJUST_RATIO;
+use starcoin_consensus::Consensus;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_service_registry::{
-    ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler,
+    ActorService, EventHandler, EventNotifier, ServiceContext, ServiceFactory, ServiceHandler,
+};
+use starcoin_state_api::StateReaderExt;
+use starcoin_storage::storage::InnerStore;
+use starcoin_storage::{BlockStore, ContractEventStore, Storage, Store};
+use starcoin_txpool::TxPoolService;
+use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::block::{
+    BlockSummary, BlockTemplate, EpochUncleSummary, ExecutedBlock, UncleSummary,
 };
-use starcoin_storage::{BlockStore, Storage, Store};
-use starcoin_types::block::{BlockSummary, EpochUncleSummary, ExecutedBlock, UncleSummary};
 use starcoin_types::contract_event::ContractEventInfo;
 use starcoin_types::filter::Filter;
-use starcoin_types::system_events::NewHeadBlock;
-use starcoin_types::transaction::BlockTransactionInfo;
+use starcoin_types::peer_info::PeerId;
+use starcoin_types::system_events::{BlockReceived, ChainReorg, MinedBlock, NewHeadBlock};
+use starcoin_types::transaction::{BlockTransactionInfo, SignedUserTransaction, TxStatus};
 use starcoin_types::{
+    account_address::AccountAddress,
     block::{Block, BlockHeader, BlockInfo, BlockNumber},
     contract_event::ContractEvent,
-    startup_info::StartupInfo,
+    startup_info::{ChainInfo, ChainStats, ChainStatus, StartupInfo},
     transaction::Transaction,
+    write_set::WriteSet,
+    U256,
+};
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_config::{genesis_address, stc_struct_tag, BalanceResource};
+use starcoin_vm_types::errors::Location;
+use starcoin_vm_types::file_format::{CompiledModule, CompiledScript};
+use starcoin_vm_types::gas_schedule::GasAlgebra;
+use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::language_storage::ModuleId;
+use starcoin_vm_types::language_storage::StructTag;
+use starcoin_vm_types::on_chain_config::{
+    ConsensusConfig, OnChainConfig, RewardConfig, VMConfig, Version,
 };
+use starcoin_vm_types::on_chain_resource::dao::{ProposalSummary, Vote};
 use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
+use starcoin_vm_types::time::TimeService;
+use starcoin_vm_types::token::token_code::TokenCode;
+use starcoin_vm_types::transaction::{TransactionPayload, TransactionPayloadType};
+use starcoin_vm_types::views::ModuleView;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::convert::TryFrom;
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// The max count of the recent blocks whose local receipt time is kept.
+const MAX_BLOCK_RECEIPTS: usize = 1024;
+/// The max count of the recent reorgs kept in the history.
+const MAX_RECENT_REORGS: usize = 256;
+/// How long a submit and mine request waits for the block connector to connect the mined block.
+const SUBMIT_AND_MINE_TIMEOUT: Duration = Duration::from_secs(10);
+
+type SubmitAndMineSender = oneshot::Sender<Result<SubmitAndMineResult>>;
 
 /// A Chain reader service to provider Reader API.
 pub struct ChainReaderService {
     inner: ChainReaderServiceInner,
+    /// The blocks mined by the submit and mine requests which are not connected yet.
+    mined_blocks: HashMap<HashValue, (SubmitAndMineResult, SubmitAndMineSender)>,
 }
 
 impl ChainReaderService {
@@ -36,11 +94,84 @@ impl ChainReaderService {
         config: Arc<NodeConfig>,
         startup_info: StartupInfo,
         storage: Arc<dyn Store>,
+        txpool: TxPoolService,
     ) -> Result<Self> {
         Ok(Self {
-            inner: ChainReaderServiceInner::new(config, startup_info, storage)?,
+            inner: ChainReaderServiceInner::new(config, startup_info, storage, txpool)?,
+            mined_blocks: HashMap::new(),
         })
     }
+
+    /// Mine the template on a separate thread, the mined block is sent to the block connector, and
+    /// the result is sent by the receiver once the block is connected as the new main head.
+    fn submit_and_mine(
+        &self,
+        template: BlockTemplate,
+        ctx: &mut ServiceContext<ChainReaderService>,
+    ) -> oneshot::Receiver<Result<SubmitAndMineResult>> {
+        let (sender, receiver) = oneshot::channel();
+        let strategy = self.inner.main.consensus();
+        let time_service = self.inner.config.net().time_service();
+        let storage = self.inner.storage.clone();
+        let notifier = ctx.self_ref().event_notifier::<SubmitAndMineBlockEvent>();
+        thread::spawn(
+            move || match mine_block(strategy, time_service, storage, template) {
+                Ok((block, result)) => {
+                    let event = SubmitAndMineBlockEvent {
+                        block,
+                        result,
+                        sender,
+                    };
+                    if let Err(e) = notifier.notify(event) {
+                        error!("Notify the mined block to ChainReaderService err: {:?}", e);
+                    }
+                }
+                Err(e) => {
+                    let _ = sender.send(Err(e));
+                }
+            },
+        );
+        receiver
+    }
+
+    /// Forward the transactions newly admitted to the txpool to the notifier,
+    /// until the notifier is closed.
+    fn subscribe_pending_txns(
+        &self,
+        notifier: EventNotifier<PendingTxnEvent>,
+        ctx: &mut ServiceContext<ChainReaderService>,
+    ) {
+        let txpool = self.inner.txpool.clone();
+        let mut receiver = txpool.subscribe_txns();
+        ctx.spawn(async move {
+            while let Some(txn_statuses) = receiver.next().await {
+                if notifier.is_closed() {
+                    break;
+                }
+                for (txn_hash, status) in txn_statuses.iter() {
+                    if *status != TxStatus::Added {
+                        continue;
+                    }
+                    // the txn may be already removed from the pool.
+                    if let Some(txn) = txpool.find_txn(txn_hash) {
+                        if let Err(e) = notifier.notify(PendingTxnEvent { txn }) {
+                            warn!(
+                                "Notify pending txn {} to {} err: {:?}",
+                                txn_hash,
+                                notifier.target_service(),
+                                e
+                            );
+                        }
+                    }
+                }
+            }
+            // drop the receiver, the txpool will remove the listener at the next notify.
+            debug!(
+                "Pending txns subscription of {} is closed.",
+                notifier.target_service()
+            );
+        });
+    }
 }
 
 impl ServiceFactory<Self> for ChainReaderService {
@@ -50,22 +181,156 @@ impl ServiceFactory<Self> for ChainReaderService {
         let startup_info = storage
             .get_startup_info()?
             .ok_or_else(|| format_err!("StartupInfo should exist at service init."))?;
-        Self::new(config, startup_info, storage)
+        let startup_info = recover_startup_info(storage.as_ref(), startup_info)?;
+        let txpool = ctx.get_shared_or_put(|| {
+            let head_header = storage
+                .get_block_header_by_hash(startup_info.main)?
+                .ok_or_else(|| {
+                    format_err!("Can not find head block header {}", startup_info.main)
+                })?;
+            Ok(TxPoolService::new(
+                config.clone(),
+                storage.clone(),
+                head_header,
+            ))
+        })?;
+        Self::new(config, startup_info, storage, txpool)
+    }
+}
+
+/// Mine the block template of a submit and mine request, and dry run the block on its parent for
+/// the output of the submitted txn.
+fn mine_block(
+    strategy: ConsensusStrategy,
+    time_service: Arc<dyn TimeService>,
+    storage: Arc<dyn Store>,
+    template: BlockTemplate,
+) -> Result<(Block, SubmitAndMineResult)> {
+    let parent_hash = template.parent_hash;
+    let block = strategy.create_block(template, time_service.as_ref())?;
+    // the outputs start with the block metadata txn, the user txn is the last one.
+    let output = BlockChain::new(time_service, parent_hash, storage)?
+        .dry_run_block(&block)?
+        .pop()
+        .ok_or_else(|| format_err!("Can not find the output of the txn of block {}", block.id()))?;
+    let head = block.id();
+    Ok((block, SubmitAndMineResult { output, head }))
+}
+
+/// Check the txn is signed by the authentication key of the sender account, or the key the
+/// sender address is derived from if the account does not exist.
+fn check_txn_signature(txn: &SignedUserTransaction, account_key: Option<&[u8]>) -> bool {
+    let authentication_key = txn.authenticator().authentication_key();
+    if txn.check_signature().is_err() {
+        return false;
+    }
+    match account_key {
+        Some(account_key) => account_key == authentication_key.as_ref(),
+        // the account is created by its first txn, which must be signed by the key the
+        // address is derived from.
+        None => authentication_key.derived_address() == txn.sender(),
     }
 }
 
+fn script_dependencies(script: &CompiledScript) -> Vec<ModuleId> {
+    let inner = script.as_inner();
+    inner
+        .module_handles
+        .iter()
+        .map(|handle| {
+            ModuleId::new(
+                inner.address_identifiers[handle.address.0 as usize],
+                inner.identifiers[handle.name.0 as usize].clone(),
+            )
+        })
+        .collect()
+}
+
+fn module_dependencies(module: &CompiledModule) -> Vec<ModuleId> {
+    let self_id = module.self_id();
+    ModuleView::new(module)
+        .module_handles()
+        .map(|handle_view| handle_view.module_id())
+        .filter(|module_id| *module_id != self_id)
+        .collect()
+}
+
+/// Convert the balance of the address to i128, so balances can be diffed.
+fn balance_to_i128(address: AccountAddress, balance: u128) -> Result<i128> {
+    i128::try_from(balance)
+        .map_err(|_| format_err!("The balance {} of {} overflows i128.", balance, address))
+}
+
+/// Walk back from the startup head to the nearest block whose block and block info are both
+/// saved, and save it as the new startup head, so a node can start after a partially written
+/// shutdown. The parent of a missing header is unknown, so the genesis is used in that case.
+fn recover_startup_info(storage: &dyn Store, startup_info: StartupInfo) -> Result<StartupInfo> {
+    let genesis = storage
+        .get_genesis()?
+        .ok_or_else(|| format_err!("Can not find genesis hash in storage."))?;
+    let mut head_id = startup_info.main;
+    while head_id != genesis {
+        let header = match storage.get_block_header_by_hash(head_id)? {
+            Some(header) => header,
+            None => {
+                head_id = genesis;
+                break;
+            }
+        };
+        if storage.get_block_by_hash(head_id)?.is_some()
+            && storage.get_block_info(head_id)?.is_some()
+        {
+            break;
+        }
+        head_id = header.parent_hash();
+    }
+    if head_id == startup_info.main {
+        return Ok(startup_info);
+    }
+    warn!(
+        "Startup head {} is missing in storage, recover the startup head to {}.",
+        startup_info.main, head_id
+    );
+    let startup_info = StartupInfo::new(head_id);
+    storage.save_startup_info(startup_info.clone())?;
+    Ok(startup_info)
+}
+
 impl ActorService for ChainReaderService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.subscribe::<NewHeadBlock>();
+        ctx.subscribe::<PeerEvent>();
+        ctx.subscribe::<BlockReceived>();
+        ctx.subscribe::<ChainReorg>();
+        let self_audit_interval = self.inner.config.storage.self_audit_interval();
+        if self_audit_interval > 0 {
+            ctx.run_interval(Duration::from_secs(self_audit_interval), |ctx| {
+                ctx.notify(SelfAuditEvent)
+            });
+        }
         Ok(())
     }
 
     fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.unsubscribe::<NewHeadBlock>();
+        ctx.unsubscribe::<PeerEvent>();
+        ctx.unsubscribe::<BlockReceived>();
+        ctx.unsubscribe::<ChainReorg>();
         Ok(())
     }
 }
 
+impl EventHandler<Self, BlockReceived> for ChainReaderService {
+    fn handle_event(
+        &mut self,
+        event: BlockReceived,
+        _ctx: &mut ServiceContext<ChainReaderService>,
+    ) {
+        self.inner
+            .record_block_receipt(event.block_id, event.received_at);
+    }
+}
+
 impl EventHandler<Self, NewHeadBlock> for ChainReaderService {
     fn handle_event(&mut self, event: NewHeadBlock, _ctx: &mut ServiceContext<ChainReaderService>) {
         let new_head = event.0.block().header();
@@ -75,16 +340,139 @@ impl EventHandler<Self, NewHeadBlock> for ChainReaderService {
             self.inner.switch_main(new_head.id())
         } {
             warn!("ChainReaderService handle NewHeadBlock err: {:?}", e);
+        } else if let Some((result, sender)) = self.mined_blocks.remove(&new_head.id()) {
+            if sender.send(Ok(result)).is_err() {
+                debug!(
+                    "The submit and mine request of block {} is dropped.",
+                    new_head.id()
+                );
+            }
         }
     }
 }
 
+/// The block of a submit and mine request is mined.
+#[derive(Debug)]
+struct SubmitAndMineBlockEvent {
+    block: Block,
+    result: SubmitAndMineResult,
+    sender: SubmitAndMineSender,
+}
+
+impl EventHandler<Self, SubmitAndMineBlockEvent> for ChainReaderService {
+    fn handle_event(
+        &mut self,
+        event: SubmitAndMineBlockEvent,
+        ctx: &mut ServiceContext<ChainReaderService>,
+    ) {
+        let block_id = event.block.id();
+        self.mined_blocks
+            .insert(block_id, (event.result, event.sender));
+        // the block connector is the only writer, the new head is notified by NewHeadBlock.
+        ctx.broadcast(MinedBlock(Arc::new(event.block)));
+        ctx.run_later(SUBMIT_AND_MINE_TIMEOUT, move |ctx| {
+            ctx.notify(SubmitAndMineTimeoutEvent(block_id))
+        });
+    }
+}
+
+/// The block of a submit and mine request is not connected in time.
+#[derive(Clone, Debug)]
+struct SubmitAndMineTimeoutEvent(HashValue);
+
+impl EventHandler<Self, SubmitAndMineTimeoutEvent> for ChainReaderService {
+    fn handle_event(
+        &mut self,
+        event: SubmitAndMineTimeoutEvent,
+        _ctx: &mut ServiceContext<ChainReaderService>,
+    ) {
+        if let Some((_, sender)) = self.mined_blocks.remove(&event.0) {
+            let _ = sender.send(Err(format_err!(
+                "The mined block {} is not connected as the main head in {:?}.",
+                event.0,
+                SUBMIT_AND_MINE_TIMEOUT
+            )));
+        }
+    }
+}
+
+impl EventHandler<Self, ChainReorg> for ChainReaderService {
+    fn handle_event(&mut self, event: ChainReorg, _ctx: &mut ServiceContext<ChainReaderService>) {
+        if let Err(e) = self.inner.record_reorg(&event) {
+            warn!("ChainReaderService handle ChainReorg err: {:?}", e);
+        }
+    }
+}
+
+impl EventHandler<Self, PeerEvent> for ChainReaderService {
+    fn handle_event(&mut self, event: PeerEvent, _ctx: &mut ServiceContext<ChainReaderService>) {
+        match event {
+            PeerEvent::Open(peer_id, chain_info) => self
+                .inner
+                .update_peer_head(peer_id, chain_info.total_difficulty()),
+            PeerEvent::Close(peer_id) => self.inner.remove_peer_head(&peer_id),
+        }
+    }
+}
+
+/// Trigger a self audit of the main chain head.
+#[derive(Clone, Debug)]
+struct SelfAuditEvent;
+
+impl EventHandler<Self, SelfAuditEvent> for ChainReaderService {
+    fn handle_event(
+        &mut self,
+        _event: SelfAuditEvent,
+        _ctx: &mut ServiceContext<ChainReaderService>,
+    ) {
+        // the failure is logged and counted by the audit.
+        let _ = self.inner.self_audit();
+    }
+}
+
+/// Push the chain stats to a subscriber, the next tick is scheduled only while the subscriber
+/// is alive, so the subscription ends with the subscriber.
+#[derive(Clone, Debug)]
+struct ChainStatsTick {
+    interval: Duration,
+    notifier: EventNotifier<ChainStatsEvent>,
+}
+
+impl EventHandler<Self, ChainStatsTick> for ChainReaderService {
+    fn handle_event(&mut self, tick: ChainStatsTick, ctx: &mut ServiceContext<ChainReaderService>) {
+        if tick.notifier.is_closed() {
+            debug!(
+                "Chain stats subscription of {} is closed.",
+                tick.notifier.target_service()
+            );
+            return;
+        }
+        match self.inner.get_chain_stats() {
+            Ok(stats) => {
+                if let Err(e) = tick.notifier.notify(ChainStatsEvent { stats }) {
+                    warn!(
+                        "Notify chain stats to {} err: {:?}",
+                        tick.notifier.target_service(),
+                        e
+                    );
+                }
+            }
+            Err(e) => warn!("Get chain stats err: {:?}", e),
+        }
+        let interval = tick.interval;
+        ctx.run_later(interval, move |ctx| ctx.notify(tick.clone()));
+    }
+}
+
 impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
     fn handle(
         &mut self,
         msg: ChainRequest,
-        _ctx: &mut ServiceContext<ChainReaderService>,
+        ctx: &mut ServiceContext<ChainReaderService>,
     ) -> Result<ChainResponse> {
+        let _timer = CHAIN_REQUEST_HISTOGRAM
+            .with_label_values(&[msg.name()])
+            .start_timer();
         match msg {
             ChainRequest::CurrentHeader() => Ok(ChainResponse::BlockHeader(Box::new(
                 self.inner.main_head_header(),
@@ -235,6 +623,197 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
             ChainRequest::EpochUncleSummaryByNumber(number) => Ok(ChainResponse::UncleSummary(
                 self.inner.epoch_uncle_summary_by_number(number)?,
             )),
+            ChainRequest::GetBranchInfo(tip) => Ok(ChainResponse::ChainInfoOption(Box::new(
+                self.inner.get_branch_info(tip)?,
+            ))),
+            ChainRequest::ReplayChain { from, to } => Ok(ChainResponse::BlockHeaderOption(
+                Box::new(self.inner.replay_chain(from, to)?),
+            )),
+            ChainRequest::CreateBlockTemplateOn { parent, max_txns } => {
+                Ok(ChainResponse::BlockTemplate(Box::new(
+                    self.inner.create_block_template_on(parent, max_txns)?,
+                )))
+            }
+            ChainRequest::GetChainStats() => Ok(ChainResponse::ChainStats(Box::new(
+                self.inner.get_chain_stats()?,
+            ))),
+            ChainRequest::GetMinGasPrice() => {
+                Ok(ChainResponse::U64(self.inner.get_min_gas_price()?))
+            }
+            ChainRequest::GetBlockTxnRoot(block_id) => Ok(ChainResponse::HashValueOption(
+                self.inner.get_block_txn_root(block_id)?,
+            )),
+            ChainRequest::SimulateWriteSet(write_set) => Ok(ChainResponse::SimulateResult(
+                Box::new(self.inner.simulate_write_set(write_set)?),
+            )),
+            ChainRequest::GetBlockFromHead(offset) => Ok(ChainResponse::BlockOption(
+                self.inner.get_block_from_head(offset)?.map(Box::new),
+            )),
+            ChainRequest::GetBlockTxnHashes(block_id) => Ok(ChainResponse::HashVecOption(
+                self.inner.get_block_txn_hashes(block_id)?,
+            )),
+            ChainRequest::IsBestHead() => Ok(ChainResponse::Bool(self.inner.is_best_head()?)),
+            ChainRequest::GetConsensusInfo() => Ok(ChainResponse::ConsensusInfo(Box::new(
+                self.inner.get_consensus_info()?,
+            ))),
+            ChainRequest::GetSignedTxnBytes(txn_hash) => Ok(ChainResponse::BytesOption(
+                self.inner.get_signed_txn_bytes(txn_hash)?,
+            )),
+            ChainRequest::GetDifficultyParams() => Ok(ChainResponse::DifficultyParams(Box::new(
+                self.inner.get_difficulty_params()?,
+            ))),
+            ChainRequest::GetTxnMultiProof(txn_hashes) => Ok(ChainResponse::TxnMultiProof(
+                Box::new(self.inner.get_txn_multiproof(txn_hashes)?),
+            )),
+            ChainRequest::GetFrameworkVersion() => {
+                Ok(ChainResponse::U64(self.inner.get_framework_version()?))
+            }
+            ChainRequest::GetOldestAvailableBlock() => Ok(ChainResponse::BlockNumber(
+                self.inner.get_oldest_available_block()?,
+            )),
+            ChainRequest::GetFeeRevenue { start, end } => {
+                Ok(ChainResponse::U128(self.inner.get_fee_revenue(start, end)?))
+            }
+            ChainRequest::GetGenesisConfig() => Ok(ChainResponse::GenesisConfig(Box::new(
+                self.inner.get_genesis_config()?,
+            ))),
+            ChainRequest::RebroadcastTxn(txn_hash) => {
+                let network = ctx.get_shared::<Arc<dyn TxnBroadcaster>>().ok();
+                Ok(ChainResponse::Bool(
+                    self.inner.rebroadcast_txn(txn_hash, network.as_deref())?,
+                ))
+            }
+            ChainRequest::GetGasUsedRange { start, end } => Ok(ChainResponse::U128(
+                self.inner.get_gas_used_range(start, end)?,
+            )),
+            ChainRequest::EstimateTimeToBlock(target) => Ok(ChainResponse::U64(
+                self.inner.estimate_time_to_block(target)?,
+            )),
+            ChainRequest::GetAccumulatorSiblings(number) => Ok(ChainResponse::HashVec(
+                self.inner.get_accumulator_siblings(number)?,
+            )),
+            ChainRequest::GetBlockTxnTypeBreakdown(block_id) => {
+                Ok(ChainResponse::TxnTypeBreakdown(Box::new(
+                    self.inner.get_block_txn_type_breakdown(block_id)?,
+                )))
+            }
+            ChainRequest::VerifyTxnSignature(txn) => {
+                Ok(ChainResponse::Bool(self.inner.verify_txn_signature(*txn)?))
+            }
+            ChainRequest::SnapshotBalances(number) => Ok(ChainResponse::BalanceSnapshot(
+                self.inner.snapshot_balances(number)?,
+            )),
+            ChainRequest::GetRewardSchedule() => Ok(ChainResponse::RewardSchedule(Box::new(
+                self.inner.get_reward_schedule()?,
+            ))),
+            ChainRequest::FindConflictingTxns(txn_hash) => Ok(ChainResponse::HashVec(
+                self.inner.find_conflicting_txns(txn_hash)?,
+            )),
+            ChainRequest::PreviewNextStateRoot(max_txns) => Ok(ChainResponse::StateRootPreview(
+                self.inner.preview_next_state_root(max_txns)?,
+            )),
+            ChainRequest::BalanceDeltas { from, to } => Ok(ChainResponse::BalanceDeltas(
+                self.inner.get_balance_deltas(from, to)?,
+            )),
+            ChainRequest::ValidateBlockTemplate(template) => Ok(ChainResponse::TemplateValidation(
+                self.inner.validate_block_template(*template)?,
+            )),
+            ChainRequest::GetTransactionByHash(txn_hash) => {
+                Ok(ChainResponse::SignedUserTransactionOption(
+                    self.inner.get_transaction_by_hash(txn_hash)?.map(Box::new),
+                ))
+            }
+            ChainRequest::GetBlockTouchedAddresses(block_id) => Ok(ChainResponse::AddressVec(
+                self.inner.get_block_touched_addresses(block_id)?,
+            )),
+            ChainRequest::GetNextEpochBoundary() => Ok(ChainResponse::BlockNumber(
+                self.inner.get_next_epoch_boundary()?,
+            )),
+            ChainRequest::GetBlocksByNumberRange { start, count } => Ok(ChainResponse::BlockVec(
+                self.inner.main_blocks_by_range(start, count)?,
+            )),
+            ChainRequest::GetBlocksByAuthor { author, limit } => Ok(ChainResponse::BlockVec(
+                self.inner.get_blocks_by_author(author, limit)?,
+            )),
+            ChainRequest::GetBlockSizeBreakdown(block_id) => Ok(ChainResponse::SizeBreakdown(
+                self.inner.get_block_size_breakdown(block_id)?,
+            )),
+            ChainRequest::GetBlocksByHashBatch(ids) => Ok(ChainResponse::BlockOptionVec(
+                self.inner.get_blocks_by_hash_batch(ids)?,
+            )),
+            ChainRequest::VerifyHeaderProof { header, proof } => Ok(ChainResponse::Bool(
+                self.inner.verify_header_proof(*header, proof)?,
+            )),
+            ChainRequest::ListGovernanceProposals() => Ok(ChainResponse::Proposals(
+                self.inner.list_governance_proposals()?,
+            )),
+            ChainRequest::CheckSequenceUsage {
+                sender,
+                sequence_number,
+            } => Ok(ChainResponse::SequenceUsage(
+                self.inner.check_sequence_usage(sender, sequence_number)?,
+            )),
+            ChainRequest::GetBranches() => Ok(ChainResponse::Branches(self.inner.get_branches()?)),
+            ChainRequest::CreateBlockTemplateWithTxns(txns) => Ok(ChainResponse::BlockTemplate(
+                Box::new(self.inner.create_block_template_with_txns(txns)?),
+            )),
+            ChainRequest::GetAccountBalances(address) => Ok(ChainResponse::AccountBalances(
+                self.inner.get_account_balances(address)?,
+            )),
+            ChainRequest::ExistsBlock(block_id) => {
+                Ok(ChainResponse::Bool(self.inner.exists_block(block_id)?))
+            }
+            ChainRequest::GetFrozenSubtreeRoots() => Ok(ChainResponse::HashVec(
+                self.inner.get_frozen_subtree_roots()?,
+            )),
+            ChainRequest::GetTxnIndexInBlock(txn_hash) => Ok(ChainResponse::U64Option(
+                self.inner.get_txn_index_in_block(txn_hash)?,
+            )),
+            ChainRequest::GetCumulativeDifficulty(number) => Ok(ChainResponse::DifficultyOption(
+                self.inner.get_cumulative_difficulty(number)?,
+            )),
+            ChainRequest::BatchVerifyTxnSignatures(txns) => Ok(ChainResponse::BoolVec(
+                self.inner.batch_verify_txn_signatures(txns)?,
+            )),
+            ChainRequest::GetCacheStats() => Ok(ChainResponse::CacheStats(Box::new(
+                self.inner.get_cache_stats()?,
+            ))),
+            ChainRequest::GetTxnDependencies(txn) => Ok(ChainResponse::ModuleIdVec(
+                self.inner.get_txn_dependencies(txn)?,
+            )),
+            ChainRequest::GetBlockPropagationDelay(block_id) => Ok(ChainResponse::U64(
+                self.inner.get_block_propagation_delay(block_id)?,
+            )),
+            ChainRequest::GetAccountTokens(address) => Ok(ChainResponse::TokenBalances(
+                self.inner.get_account_tokens(address)?,
+            )),
+            ChainRequest::WillFitNextBlock(txn) => {
+                Ok(ChainResponse::Bool(self.inner.will_fit_next_block(txn)?))
+            }
+            ChainRequest::GetRecentReorgs(limit) => {
+                Ok(ChainResponse::Reorgs(self.inner.get_recent_reorgs(limit)?))
+            }
+            ChainRequest::SubmitAndMine(txn) => {
+                let template = self.inner.create_submit_and_mine_template(*txn)?;
+                Ok(ChainResponse::SubmitAndMineReceiver(
+                    self.submit_and_mine(template, ctx),
+                ))
+            }
+            ChainRequest::SubscribePendingTxns(notifier) => {
+                self.subscribe_pending_txns(notifier, ctx);
+                Ok(ChainResponse::None)
+            }
+            ChainRequest::SubscribeChainStats {
+                interval_secs,
+                notifier,
+            } => {
+                ensure!(interval_secs > 0, "The stats interval should be positive.");
+                ctx.notify(ChainStatsTick {
+                    interval: Duration::from_secs(interval_secs),
+                    notifier,
+                });
+                Ok(ChainResponse::None)
+            }
         }
     }
 }
@@ -244,6 +823,15 @@ pub struct ChainReaderServiceInner {
     startup_info: StartupInfo,
     main: BlockChain,
     storage: Arc<dyn Store>,
+    txpool: TxPoolService,
+    /// The total difficulty of the peers' head, reported by the network when a peer connects.
+    peer_heads: HashMap<PeerId, U256>,
+    /// The on chain configs read at the main head.
+    on_chain_config_cache: RefCell<OnChainConfigCache>,
+    /// The local time in milliseconds the recent blocks are received by the block connector at.
+    block_receipts: VecDeque<(HashValue, u64)>,
+    /// The recent reorgs of the main chain, the latest at the back.
+    recent_reorgs: VecDeque<ReorgRecord>,
 }
 
 impl ChainReaderServiceInner {
@@ -251,6 +839,7 @@ impl ChainReaderServiceInner {
         config: Arc<NodeConfig>,
         startup_info: StartupInfo,
         storage: Arc<dyn Store>,
+        txpool: TxPoolService,
     ) -> Result<Self> {
         let net = config.net();
         let main = BlockChain::new(net.time_service(), startup_info.main, storage.clone())?;
@@ -259,6 +848,11 @@ impl ChainReaderServiceInner {
             startup_info,
             main,
             storage,
+            txpool,
+            peer_heads: HashMap::new(),
+            on_chain_config_cache: RefCell::new(OnChainConfigCache::default()),
+            block_receipts: VecDeque::new(),
+            recent_reorgs: VecDeque::new(),
         })
     }
 
@@ -266,6 +860,37 @@ impl ChainReaderServiceInner {
         &self.main
     }
 
+    /// Record the local time the block is received by the block connector at.
+    pub fn record_block_receipt(&mut self, block_id: HashValue, received_at: u64) {
+        if self.block_receipts.len() >= MAX_BLOCK_RECEIPTS {
+            self.block_receipts.pop_front();
+        }
+        self.block_receipts.push_back((block_id, received_at));
+    }
+
+    /// Record a reorg reported by the block connector, the common ancestor is the parent of the
+    /// first retracted block.
+    pub fn record_reorg(&mut self, reorg: &ChainReorg) -> Result<()> {
+        let first_retracted = match reorg.retracted.first() {
+            Some(block_id) => *block_id,
+            None => return Ok(()),
+        };
+        let common_ancestor = self
+            .storage
+            .get_block_header_by_hash(first_retracted)?
+            .ok_or_else(|| format_err!("Can not find block header by {}.", first_retracted))?
+            .parent_hash();
+        if self.recent_reorgs.len() >= MAX_RECENT_REORGS {
+            self.recent_reorgs.pop_front();
+        }
+        self.recent_reorgs.push_back(ReorgRecord {
+            depth: reorg.retracted.len() as u64,
+            common_ancestor,
+            timestamp: self.config.net().time_service().now_millis(),
+        });
+        Ok(())
+    }
+
     pub fn update_chain_head(&mut self, block: ExecutedBlock) -> Result<()> {
         self.main.connect(block)?;
         Ok(())
@@ -277,6 +902,126 @@ impl ChainReaderServiceInner {
         Ok(())
     }
 
+    /// Get the on chain config at the main head, the config is cached until the head changes.
+    pub fn get_on_chain_config<C>(&self) -> Result<Option<C>>
+    where
+        C: OnChainConfig,
+    {
+        let head_id = self.main.current_header().id();
+        self.on_chain_config_cache
+            .borrow_mut()
+            .get_or_fetch(head_id, C::config_id(), |access_path| {
+                self.main.chain_state_reader().get(access_path)
+            })?
+            .map(|bytes| C::deserialize_into_config(&bytes))
+            .transpose()
+    }
+
+    /// Verify the stored data of the main head, a failure is logged and counted by the
+    /// `self_audit_failures` metric.
+    pub fn self_audit(&self) -> Result<()> {
+        let result = self.main.verify_head_integrity();
+        if let Err(e) = &result {
+            SELF_AUDIT_FAILURES.inc();
+            error!("Chain head self audit failed: {:?}", e);
+        }
+        result
+    }
+
+    /// Admit the transaction to the txpool, then create a block template on the main head which
+    /// packages the transaction. Only supported if the submit and mine is enabled in the config.
+    pub fn create_submit_and_mine_template(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> Result<BlockTemplate> {
+        ensure!(
+            self.config.miner.is_submit_and_mine_enabled(),
+            "Submit and mine is disabled, enable it by the miner config enable_submit_and_mine."
+        );
+        let txn_hash = txn.id();
+        self.txpool
+            .add_txns(vec![txn.clone()])
+            .pop()
+            .ok_or_else(|| {
+                format_err!("Txpool does not return the result of txn {}", txn_hash)
+            })??;
+        let (template, excluded) =
+            self.main
+                .create_block_template(txn.sender(), None, vec![txn], vec![], None)?;
+        ensure!(
+            excluded.discarded_txns.is_empty() && excluded.untouched_txns.is_empty(),
+            "Txn {} can not be packaged into the block: {:?}",
+            txn_hash,
+            excluded
+        );
+        Ok(template)
+    }
+
+    /// Check the block range [start, end] is valid and not larger than the configured max count.
+    fn check_block_range(&self, start: BlockNumber, end: BlockNumber) -> Result<()> {
+        ensure!(start <= end, "Invalid block range [{}, {}]", start, end);
+        let max_count = self.config.storage.block_range_max_count();
+        ensure!(
+            end - start < max_count,
+            "The block range [{}, {}] exceeds the max count {}",
+            start,
+            end,
+            max_count
+        );
+        Ok(())
+    }
+
+    /// The gas fee paid by the user txns of the block, the block metadata txn pays nothing.
+    fn block_fee(&self, block: &Block) -> Result<u128> {
+        let gas_prices: HashMap<HashValue, u64> = block
+            .transactions()
+            .iter()
+            .map(|txn| (txn.id(), txn.gas_unit_price()))
+            .collect();
+        Ok(self
+            .storage
+            .get_block_transaction_infos(block.id())?
+            .iter()
+            .filter_map(|txn_info| {
+                gas_prices
+                    .get(&txn_info.transaction_hash())
+                    .map(|gas_price| u128::from(txn_info.gas_used()) * u128::from(*gas_price))
+            })
+            .fold(0u128, |acc, fee| acc.saturating_add(fee)))
+    }
+
+    /// Re-broadcast the user txn in the txpool or storage to the peers,
+    /// return false if the txn is unknown.
+    pub fn rebroadcast_txn(
+        &self,
+        txn_hash: HashValue,
+        network: Option<&dyn TxnBroadcaster>,
+    ) -> Result<bool> {
+        let network = network.ok_or_else(|| format_err!("Network service is not available."))?;
+        let txn = match self.txpool.find_txn(&txn_hash) {
+            Some(txn) => Some(txn),
+            None => match self.storage.get_transaction(txn_hash)? {
+                Some(Transaction::UserTransaction(txn)) => Some(txn),
+                _ => None,
+            },
+        };
+        match txn {
+            Some(txn) => {
+                network.broadcast_txns(vec![txn]);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    pub fn update_peer_head(&mut self, peer_id: PeerId, total_difficulty: U256) {
+        self.peer_heads.insert(peer_id, total_difficulty);
+    }
+
+    pub fn remove_peer_head(&mut self, peer_id: &PeerId) {
+        self.peer_heads.remove(peer_id);
+    }
+
     fn uncle_summary(
         &self,
         start_number: BlockNumber,
@@ -492,25 +1237,818 @@ impl ReadableChainService for ChainReaderServiceInner {
             epoch_uncle_summary,
         ))
     }
+
+    fn get_branch_info(&self, tip: HashValue) -> Result<Option<ChainInfo>> {
+        let header = match self.storage.get_block_header_by_hash(tip)? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        let block_info = match self.storage.get_block_info(tip)? {
+            Some(block_info) => block_info,
+            None => return Ok(None),
+        };
+        Ok(Some(ChainInfo::new(
+            header.chain_id(),
+            self.main.info().genesis_hash(),
+            ChainStatus::new(header, block_info),
+        )))
+    }
+
+    fn replay_chain(&self, from: BlockNumber, to: BlockNumber) -> Result<Option<BlockHeader>> {
+        self.main.replay(from, to)
+    }
+
+    fn create_block_template_on(&self, parent: HashValue, max_txns: u64) -> Result<BlockTemplate> {
+        // build the template on a chain whose head is the parent, so the epoch and state are right.
+        let chain = BlockChain::new(
+            self.config.net().time_service(),
+            parent,
+            self.storage.clone(),
+        )?;
+        let txns = self.txpool.get_pending_txns(Some(max_txns), None);
+        let (template, _) = chain.create_block_template(
+            genesis_address(),
+            None,
+            txns,
+            vec![],
+            self.config.miner.block_gas_limit,
+        )?;
+        Ok(template)
+    }
+
+    fn get_chain_stats(&self) -> Result<ChainStats> {
+        self.main.chain_stats()
+    }
+
+    fn get_min_gas_price(&self) -> Result<u64> {
+        let on_chain_min_gas_price = self
+            .get_on_chain_config::<VMConfig>()?
+            .map(|vm_config| {
+                vm_config
+                    .gas_schedule
+                    .gas_constants
+                    .min_price_per_gas_unit
+                    .get()
+            })
+            .unwrap_or_default();
+        Ok(on_chain_min_gas_price.max(self.config.tx_pool.min_gas_price()))
+    }
+
+    fn get_block_txn_root(&self, block_id: HashValue) -> Result<Option<HashValue>> {
+        Ok(self
+            .storage
+            .get_block_info(block_id)?
+            .map(|block_info| block_info.txn_accumulator_info.accumulator_root))
+    }
+
+    fn simulate_write_set(&self, write_set: WriteSet) -> Result<SimulateResult> {
+        self.main.simulate_write_set(write_set)
+    }
+
+    fn get_block_from_head(&self, offset: u64) -> Result<Option<Block>> {
+        match self.main.current_header().number().checked_sub(offset) {
+            Some(number) => self.main.get_block_by_number(number),
+            None => Ok(None),
+        }
+    }
+
+    fn get_block_txn_hashes(&self, block_id: HashValue) -> Result<Option<Vec<HashValue>>> {
+        Ok(self
+            .storage
+            .get_block_by_hash(block_id)?
+            .map(|block| block.transactions().iter().map(|txn| txn.id()).collect()))
+    }
+
+    fn is_best_head(&self) -> Result<bool> {
+        match self.peer_heads.values().max() {
+            Some(best_peer_difficulty) => {
+                Ok(self.main.get_total_difficulty()? >= *best_peer_difficulty)
+            }
+            None => Ok(true),
+        }
+    }
+
+    fn get_consensus_info(&self) -> Result<ConsensusInfo> {
+        Ok(ConsensusInfo {
+            name: self.main.consensus().to_string(),
+            target_block_time: self.main.epoch().block_time_target(),
+        })
+    }
+
+    fn get_signed_txn_bytes(&self, txn_hash: HashValue) -> Result<Option<Vec<u8>>> {
+        match self.storage.get_transaction(txn_hash)? {
+            Some(Transaction::UserTransaction(txn)) => Ok(Some(bcs_ext::to_bytes(&txn)?)),
+            _ => Ok(None),
+        }
+    }
+
+    fn get_difficulty_params(&self) -> Result<DifficultyParams> {
+        let consensus_config = self
+            .get_on_chain_config::<ConsensusConfig>()?
+            .ok_or_else(|| format_err!("Can not find on chain consensus config."))?;
+        Ok(DifficultyParams {
+            window: consensus_config.base_block_difficulty_window,
+            max_adjust_ratio: MAX_TARGET_ADJUST_RATIO,
+        })
+    }
+
+    fn get_txn_multiproof(&self, txn_hashes: Vec<HashValue>) -> Result<TxnMultiProof> {
+        self.main.get_txn_multiproof(txn_hashes)
+    }
+
+    fn get_framework_version(&self) -> Result<u64> {
+        // every chain initializes the version at genesis, so a missing one means a malformed chain.
+        let version = self
+            .get_on_chain_config::<Version>()?
+            .ok_or_else(|| format_err!("Can not find on chain version config."))?;
+        Ok(version.major)
+    }
+
+    fn get_oldest_available_block(&self) -> Result<BlockNumber> {
+        // bodies are pruned from genesis upward, so the available blocks are a suffix of the chain.
+        let (mut low, mut high) = (0, self.main.current_header().number());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.main.get_block_by_number(mid)?.is_some() {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        ensure!(
+            self.main.get_block_by_number(low)?.is_some(),
+            "Can not find any block body of the main chain."
+        );
+        Ok(low)
+    }
+
+    fn get_fee_revenue(&self, start: BlockNumber, end: BlockNumber) -> Result<u128> {
+        self.check_block_range(start, end)?;
+        let mut revenue = 0u128;
+        for number in start..=end {
+            let block = self
+                .main
+                .get_block_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+            revenue = revenue.saturating_add(self.block_fee(&block)?);
+        }
+        Ok(revenue)
+    }
+
+    fn get_genesis_config(&self) -> Result<GenesisConfigInfo> {
+        let net = self.config.net();
+        let genesis_config = net.genesis_config();
+        Ok(GenesisConfigInfo {
+            chain_id: net.chain_id(),
+            reward_delay: genesis_config.reward_delay,
+            consensus_config: genesis_config.consensus_config.clone(),
+            gas_schedule_hash: HashValue::sha3_256_of(&bcs_ext::to_bytes(
+                &genesis_config.vm_config.gas_schedule,
+            )?),
+        })
+    }
+
+    fn get_gas_used_range(&self, start: BlockNumber, end: BlockNumber) -> Result<u128> {
+        self.check_block_range(start, end)?;
+        let mut gas_used = 0u128;
+        // the header gas used is checked against the txn infos when the block is connected.
+        for number in start..=end {
+            let header = self
+                .main
+                .get_header_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find block header by number {}", number))?;
+            gas_used = gas_used.saturating_add(u128::from(header.gas_used()));
+        }
+        Ok(gas_used)
+    }
+
+    fn estimate_time_to_block(&self, target: BlockNumber) -> Result<u64> {
+        let head_number = self.main.current_header().number();
+        if target <= head_number {
+            return Ok(0);
+        }
+        let block_time = self
+            .main
+            .median_block_time(self.main.epoch().block_difficulty_window())?;
+        // the block timestamp is in milliseconds.
+        Ok((target - head_number).saturating_mul(block_time) / 1000)
+    }
+
+    fn get_accumulator_siblings(&self, number: BlockNumber) -> Result<Vec<HashValue>> {
+        let proof = self
+            .main
+            .get_block_accumulator()
+            .get_proof(number)?
+            .ok_or_else(|| format_err!("Can not find block accumulator leaf {}", number))?;
+        Ok(proof.siblings().to_vec())
+    }
+
+    fn get_block_txn_type_breakdown(&self, block_id: HashValue) -> Result<TxnTypeBreakdown> {
+        let block = self
+            .storage
+            .get_block_by_hash(block_id)?
+            .ok_or_else(|| format_err!("Can not find block by id {}", block_id))?;
+        let mut breakdown = TxnTypeBreakdown::default();
+        for txn in block.transactions() {
+            match txn.payload().payload_type() {
+                TransactionPayloadType::Script => breakdown.scripts += 1,
+                TransactionPayloadType::Package => breakdown.modules += 1,
+                TransactionPayloadType::ScriptFunction => breakdown.script_functions += 1,
+            }
+        }
+        Ok(breakdown)
+    }
+
+    fn verify_txn_signature(&self, txn: SignedUserTransaction) -> Result<bool> {
+        let account = self
+            .main
+            .chain_state_reader()
+            .get_account_resource(txn.sender())?;
+        Ok(check_txn_signature(
+            &txn,
+            account.as_ref().map(|account| account.authentication_key()),
+        ))
+    }
+
+    fn snapshot_balances(&self, number: BlockNumber) -> Result<Vec<(AccountAddress, u128)>> {
+        let block_id = self.main.get_hash_by_number_ensure(number)?;
+        let chain = BlockChain::new(
+            self.config.net().time_service(),
+            block_id,
+            self.storage.clone(),
+        )?;
+        let state_reader = chain.chain_state_reader();
+        let mut balances = vec![];
+        for (address, _) in state_reader.dump()?.state_sets() {
+            if let Some(balance) = state_reader.get_balance(*address)? {
+                balances.push((*address, balance));
+            }
+        }
+        Ok(balances)
+    }
+
+    fn get_reward_schedule(&self) -> Result<RewardSchedule> {
+        let consensus_config = self
+            .get_on_chain_config::<ConsensusConfig>()?
+            .ok_or_else(|| format_err!("Can not find on chain consensus config."))?;
+        let reward_config = self
+            .get_on_chain_config::<RewardConfig>()?
+            .ok_or_else(|| format_err!("Can not find on chain reward config."))?;
+        let epoch = self.main.epoch();
+        Ok(RewardSchedule {
+            base_reward_per_block: consensus_config.base_reward_per_block,
+            reward_per_block: epoch.reward_per_block(),
+            reward_per_uncle_percent: epoch.reward_per_uncle_percent(),
+            reward_delay: reward_config.reward_delay,
+            epoch_block_count: consensus_config.epoch_block_count,
+        })
+    }
+
+    fn find_conflicting_txns(&self, txn_hash: HashValue) -> Result<Vec<HashValue>> {
+        let txn = match self.txpool.find_txn(&txn_hash) {
+            Some(txn) => txn,
+            None => match self.storage.get_transaction(txn_hash)? {
+                Some(Transaction::UserTransaction(txn)) => txn,
+                _ => bail!("Can not find user txn {}", txn_hash),
+            },
+        };
+        // the txpool keeps at most one txn for a sender and sequence number, so only an executed
+        // txn can have a conflicting pending txn.
+        Ok(self
+            .txpool
+            .txns_of_sender(&txn.sender(), None)
+            .into_iter()
+            .filter(|pending| {
+                pending.sequence_number() == txn.sequence_number() && pending.id() != txn_hash
+            })
+            .map(|pending| pending.id())
+            .collect())
+    }
+
+    fn preview_next_state_root(&self, max_txns: u64) -> Result<StateRootPreview> {
+        let txns = self.txpool.get_pending_txns(Some(max_txns), None);
+        // the template executes the txns on a state forked from the head, and is then dropped.
+        let (template, _) = self.main.create_block_template(
+            genesis_address(),
+            None,
+            txns,
+            vec![],
+            self.config.miner.block_gas_limit,
+        )?;
+        Ok(StateRootPreview {
+            state_root: template.state_root,
+            included_txns: template.body.transactions.len() as u64,
+        })
+    }
+
+    fn get_balance_deltas(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> Result<Vec<(AccountAddress, i128)>> {
+        ensure!(
+            from <= to,
+            "Invalid block range, from {} is greater than to {}",
+            from,
+            to
+        );
+        let mut balances_before: HashMap<AccountAddress, u128> =
+            self.snapshot_balances(from)?.into_iter().collect();
+        let mut deltas = vec![];
+        for (address, balance) in self.snapshot_balances(to)? {
+            let balance_before = balances_before.remove(&address).unwrap_or_default();
+            if balance != balance_before {
+                deltas.push((
+                    address,
+                    balance_to_i128(address, balance)? - balance_to_i128(address, balance_before)?,
+                ));
+            }
+        }
+        // the balances removed in between.
+        for (address, balance) in balances_before {
+            if balance > 0 {
+                deltas.push((address, -balance_to_i128(address, balance)?));
+            }
+        }
+        deltas.sort_by_key(|(address, _)| *address);
+        Ok(deltas)
+    }
+
+    fn validate_block_template(&self, template: BlockTemplate) -> Result<TemplateValidation> {
+        // fails if the parent does not exist.
+        let chain = BlockChain::new(
+            self.config.net().time_service(),
+            template.parent_hash,
+            self.storage.clone(),
+        )?;
+        chain.validate_block_template(template)
+    }
+
+    fn get_transaction_by_hash(
+        &self,
+        txn_hash: HashValue,
+    ) -> Result<Option<SignedUserTransaction>> {
+        match self.storage.get_transaction(txn_hash)? {
+            Some(Transaction::UserTransaction(txn)) => Ok(Some(txn)),
+            _ => Ok(None),
+        }
+    }
+
+    fn get_block_touched_addresses(&self, block_id: HashValue) -> Result<Vec<AccountAddress>> {
+        let addresses: BTreeSet<AccountAddress> = self
+            .main
+            .get_block_write_set(block_id)?
+            .iter()
+            .map(|(access_path, _)| access_path.address)
+            .collect();
+        Ok(addresses.into_iter().collect())
+    }
+
+    fn get_next_epoch_boundary(&self) -> Result<BlockNumber> {
+        Ok(self.main.epoch().end_block_number())
+    }
+
+    fn main_blocks_by_range(&self, start: BlockNumber, count: u64) -> Result<Vec<Block>> {
+        let count = count.min(self.config.storage.block_range_max_count());
+        let head_number = self.main.current_header().number();
+        if count == 0 || start > head_number {
+            return Ok(vec![]);
+        }
+        let end = start.saturating_add(count - 1).min(head_number);
+        (start..=end)
+            .map(|number| {
+                self.main
+                    .get_block_by_number(number)?
+                    .ok_or_else(|| format_err!("Can not find block by number {}", number))
+            })
+            .collect()
+    }
+
+    fn get_blocks_by_author(&self, author: AccountAddress, limit: u64) -> Result<Vec<Block>> {
+        let limit = limit as usize;
+        let mut blocks = vec![];
+        let mut max_number = Some(self.main.current_header().number());
+        // the author index covers all branches, only the blocks on the main chain are kept.
+        while let Some(number) = max_number {
+            let remaining = limit.saturating_sub(blocks.len());
+            if remaining == 0 {
+                break;
+            }
+            let page = self
+                .storage
+                .get_block_ids_by_author(author, Some(number), remaining)?;
+            let page_len = page.len();
+            for (number, block_ids) in page {
+                max_number = number.checked_sub(1);
+                if let Some(block_id) = self.main.get_hash_by_number(number)? {
+                    if block_ids.contains(&block_id) {
+                        blocks.push(self.storage.get_block_by_hash(block_id)?.ok_or_else(
+                            || format_err!("Can not find block by hash {:?}", block_id),
+                        )?);
+                    }
+                }
+            }
+            if page_len < remaining {
+                break;
+            }
+        }
+        Ok(blocks)
+    }
+
+    fn get_block_size_breakdown(&self, block_id: HashValue) -> Result<BlockSizeBreakdown> {
+        let block = self
+            .storage
+            .get_block_by_hash(block_id)?
+            .ok_or_else(|| format_err!("Can not find block by hash {:?}", block_id))?;
+        Ok(BlockSizeBreakdown {
+            header: bcs_ext::to_bytes(&block.header)?.len() as u64,
+            body: bcs_ext::to_bytes(&block.body)?.len() as u64,
+            total: bcs_ext::to_bytes(&block)?.len() as u64,
+        })
+    }
+
+    fn get_blocks_by_hash_batch(&self, ids: Vec<HashValue>) -> Result<Vec<Option<Block>>> {
+        ensure!(
+            ids.len() as u64 <= MAX_BLOCK_BATCH_SIZE,
+            "The batch size {} exceeds the max {}",
+            ids.len(),
+            MAX_BLOCK_BATCH_SIZE
+        );
+        self.storage.get_blocks(ids)
+    }
+
+    fn verify_header_proof(&self, header: BlockHeader, proof: AccumulatorProof) -> Result<bool> {
+        let accumulator_root = self.main.get_block_accumulator().root_hash();
+        Ok(proof
+            .verify(accumulator_root, header.id(), header.number())
+            .is_ok())
+    }
+
+    fn list_governance_proposals(&self) -> Result<Vec<ProposalInfo>> {
+        let token = stc_struct_tag();
+        let state = self.main.chain_state_reader();
+        let head = self.main.current_header();
+        // a proposal is stored under its proposer, the proposers are indexed from the proposal
+        // created events when the events are saved.
+        let proposers = self.storage.get_dao_proposers()?;
+
+        let mut proposals = vec![];
+        for proposer in proposers {
+            let resource_set = match state.get_account_state_set(&proposer)? {
+                Some(account_state_set) => account_state_set.resource_set().cloned(),
+                None => None,
+            };
+            for (key, value) in resource_set.unwrap_or_default().iter() {
+                let struct_tag = bcs_ext::from_bytes::<StructTag>(key.as_slice())?;
+                if !ProposalSummary::is_proposal_of(&struct_tag, &token) {
+                    continue;
+                }
+                let summary = ProposalSummary::decode(value.as_slice())?;
+                proposals.push(ProposalInfo {
+                    id: summary.id,
+                    proposer: summary.proposer,
+                    state: summary.state(head.timestamp()),
+                    voting_deadline: summary.end_time,
+                });
+            }
+        }
+        proposals.sort_by_key(|proposal| proposal.id);
+        Ok(proposals)
+    }
+
+    fn check_sequence_usage(
+        &self,
+        sender: AccountAddress,
+        sequence_number: u64,
+    ) -> Result<SequenceUsage> {
+        // the account not created yet has not used any sequence number.
+        let confirmed_sequence_number = self
+            .main
+            .chain_state_reader()
+            .get_account_resource(sender)?
+            .map(|account| account.sequence_number())
+            .unwrap_or_default();
+        if sequence_number < confirmed_sequence_number {
+            return Ok(SequenceUsage::AlreadyConfirmed);
+        }
+        let pending = self
+            .txpool
+            .txns_of_sender(&sender, None)
+            .iter()
+            .any(|txn| txn.sequence_number() == sequence_number);
+        Ok(if pending {
+            SequenceUsage::PendingInPool
+        } else {
+            SequenceUsage::Free
+        })
+    }
+
+    fn get_branches(&self) -> Result<Vec<(HashValue, U256)>> {
+        let mut branches = self.storage.get_branch_heads()?;
+        branches.sort_by(|a, b| b.1.cmp(&a.1));
+        Ok(branches)
+    }
+
+    fn create_block_template_with_txns(
+        &self,
+        txns: Vec<SignedUserTransaction>,
+    ) -> Result<BlockTemplate> {
+        let state = self.main.chain_state_reader();
+        let mut next_sequence_numbers = HashMap::new();
+        for txn in &txns {
+            let sender = txn.sender();
+            let expected_sequence_number = match next_sequence_numbers.get(&sender) {
+                Some(sequence_number) => *sequence_number,
+                None => state
+                    .get_account_resource(sender)?
+                    .map(|account| account.sequence_number())
+                    .unwrap_or_default(),
+            };
+            ensure!(
+                txn.sequence_number() == expected_sequence_number,
+                "The sequence number of txn {} from {} should be {}, but got {}.",
+                txn.id(),
+                sender,
+                expected_sequence_number,
+                txn.sequence_number()
+            );
+            next_sequence_numbers.insert(sender, expected_sequence_number + 1);
+        }
+        let (template, _) = self.main.create_block_template(
+            genesis_address(),
+            None,
+            txns,
+            vec![],
+            self.config.miner.block_gas_limit,
+        )?;
+        Ok(template)
+    }
+
+    fn get_account_balances(&self, address: AccountAddress) -> Result<AccountBalances> {
+        let state = self.main.chain_state_reader();
+        let liquid = state.get_balance(address)?.unwrap_or_default();
+        // the stake of a vote is locked until the vote is revoked or the proposal is done.
+        let locked = state
+            .get_resource_by_access_path::<Vote>(Vote::resource_path_for(
+                address,
+                stc_struct_tag(),
+            ))?
+            .map(|vote| vote.stake)
+            .unwrap_or_default();
+        Ok(AccountBalances {
+            liquid,
+            locked,
+            total: liquid
+                .checked_add(locked)
+                .ok_or_else(|| format_err!("The total balance of {} overflows u128.", address))?,
+        })
+    }
+
+    fn exists_block(&self, block_id: HashValue) -> Result<bool> {
+        self.main.has_block_in_storage(block_id)
+    }
+
+    fn get_frozen_subtree_roots(&self) -> Result<Vec<HashValue>> {
+        Ok(self.main.get_block_accumulator().get_frozen_subtree_roots())
+    }
+
+    fn get_txn_index_in_block(&self, txn_hash: HashValue) -> Result<Option<u64>> {
+        let txn_info = match self.main.get_transaction_info(txn_hash)? {
+            Some(txn_info) => txn_info,
+            None => return Ok(None),
+        };
+        self.storage.get_txn_info_index(txn_info.id())
+    }
+
+    fn get_cumulative_difficulty(&self, number: BlockNumber) -> Result<Option<U256>> {
+        let head = self.main.current_header();
+        if number > head.number() {
+            return Ok(None);
+        }
+        if number == head.number() {
+            return Ok(Some(self.main.get_total_difficulty()?));
+        }
+        match self.main.get_hash_by_number(number)? {
+            Some(block_id) => Ok(self
+                .main
+                .get_block_info(Some(block_id))?
+                .map(|block_info| block_info.total_difficulty)),
+            None => Ok(None),
+        }
+    }
+
+    fn batch_verify_txn_signatures(&self, txns: Vec<SignedUserTransaction>) -> Result<Vec<bool>> {
+        let state_reader = self.main.chain_state_reader();
+        // read the account keys first, the signatures are checked in parallel on the rayon pool.
+        let account_keys = txns
+            .iter()
+            .map(|txn| {
+                Ok(state_reader
+                    .get_account_resource(txn.sender())?
+                    .map(|account| account.authentication_key().to_vec()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(txns
+            .par_iter()
+            .zip(account_keys.par_iter())
+            .map(|(txn, account_key)| check_txn_signature(txn, account_key.as_deref()))
+            .collect())
+    }
+
+    fn get_cache_stats(&self) -> Result<CacheStats> {
+        let block_cache = match self.storage.get_cache_storage() {
+            Some(cache) => CacheStat {
+                hits: cache.hits(),
+                misses: cache.misses(),
+                entries: cache.get_len()?,
+            },
+            None => CacheStat::default(),
+        };
+        let config_cache = self.on_chain_config_cache.borrow();
+        let statedb = self.main.get_statedb();
+        Ok(CacheStats {
+            block_cache,
+            config_cache: CacheStat {
+                hits: config_cache.hits(),
+                misses: config_cache.state_reads(),
+                entries: config_cache.len() as u64,
+            },
+            state_cache: CacheStat {
+                hits: statedb.cache_hits(),
+                misses: statedb.cache_misses(),
+                entries: statedb.cache_len() as u64,
+            },
+        })
+    }
+
+    fn get_txn_dependencies(&self, txn: SignedUserTransaction) -> Result<Vec<ModuleId>> {
+        // the modules published by the txn itself are not dependencies.
+        let mut published = BTreeSet::new();
+        let mut pending: Vec<ModuleId> = match txn.payload() {
+            TransactionPayload::Script(script) => {
+                let compiled = CompiledScript::deserialize(script.code())
+                    .map_err(|e| e.finish(Location::Undefined).into_vm_status())?;
+                script_dependencies(&compiled)
+            }
+            TransactionPayload::ScriptFunction(script_function) => {
+                vec![script_function.module().clone()]
+            }
+            TransactionPayload::Package(package) => {
+                let mut module_ids = vec![];
+                for module in package.modules() {
+                    let compiled = CompiledModule::deserialize(module.code())
+                        .map_err(|e| e.finish(Location::Undefined).into_vm_status())?;
+                    published.insert(compiled.self_id());
+                    module_ids.extend(module_dependencies(&compiled));
+                }
+                if let Some(init_script) = package.init_script() {
+                    module_ids.push(init_script.module().clone());
+                }
+                module_ids
+            }
+        };
+        let state_reader = self.main.chain_state_reader();
+        let mut dependencies = BTreeSet::new();
+        while let Some(module_id) = pending.pop() {
+            if published.contains(&module_id) || !dependencies.insert(module_id.clone()) {
+                continue;
+            }
+            if let Some(code) = state_reader.get(&AccessPath::from(&module_id))? {
+                let compiled = CompiledModule::deserialize(&code)
+                    .map_err(|e| e.finish(Location::Undefined).into_vm_status())?;
+                pending.extend(module_dependencies(&compiled));
+            }
+        }
+        Ok(dependencies.into_iter().collect())
+    }
+
+    fn get_block_propagation_delay(&self, block_id: HashValue) -> Result<u64> {
+        let received_at = self
+            .block_receipts
+            .iter()
+            .rev()
+            .find(|(id, _)| *id == block_id)
+            .map(|(_, received_at)| *received_at)
+            .ok_or_else(|| format_err!("Can not find the receipt time of block {}.", block_id))?;
+        let header = self
+            .storage
+            .get_block_header_by_hash(block_id)?
+            .ok_or_else(|| format_err!("Can not find block header by {}.", block_id))?;
+        if received_at < header.timestamp() {
+            warn!(
+                "Block {} is received at {} before its timestamp {}, the local clock may be skewed.",
+                block_id,
+                received_at,
+                header.timestamp()
+            );
+            return Ok(0);
+        }
+        Ok((received_at - header.timestamp()) / 1000)
+    }
+
+    fn get_account_tokens(&self, address: AccountAddress) -> Result<Vec<(TokenCode, u128)>> {
+        let state_set = match self
+            .main
+            .chain_state_reader()
+            .get_account_state_set(&address)?
+        {
+            Some(state_set) => state_set,
+            None => return Ok(vec![]),
+        };
+        let mut balances = vec![];
+        if let Some(resources) = state_set.resource_set() {
+            for (struct_tag, resource) in resources.iter() {
+                let struct_tag = bcs_ext::from_bytes::<StructTag>(struct_tag.as_slice())?;
+                if let Some(token_code) = BalanceResource::token_code(&struct_tag) {
+                    let balance = bcs_ext::from_bytes::<BalanceResource>(resource.as_slice())?;
+                    balances.push((token_code, balance.token()));
+                }
+            }
+        }
+        Ok(balances)
+    }
+
+    fn will_fit_next_block(&self, txn: SignedUserTransaction) -> Result<bool> {
+        let on_chain_block_gas_limit = self.main.epoch().block_gas_limit();
+        let block_gas_limit = self
+            .config
+            .miner
+            .block_gas_limit
+            .map(|block_gas_limit| block_gas_limit.min(on_chain_block_gas_limit))
+            .unwrap_or(on_chain_block_gas_limit);
+        // the pending txns are packed by gas price, and a txn of the same gas price which is
+        // already pending is packed first.
+        let gas_ahead = self
+            .txpool
+            .get_pending_txns(None, None)
+            .iter()
+            .filter(|pending| {
+                pending.id() != txn.id() && pending.gas_unit_price() >= txn.gas_unit_price()
+            })
+            .fold(0u64, |gas, pending| {
+                gas.saturating_add(pending.max_gas_amount())
+            });
+        Ok(gas_ahead.saturating_add(txn.max_gas_amount()) <= block_gas_limit)
+    }
+
+    fn get_recent_reorgs(&self, limit: u64) -> Result<Vec<ReorgRecord>> {
+        Ok(self
+            .recent_reorgs
+            .iter()
+            .rev()
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use starcoin_chain_api::ChainAsyncService;
+    use starcoin_chain_mock::MockChain;
     use starcoin_config::NodeConfig;
-    use starcoin_service_registry::{RegistryAsyncService, RegistryService};
 
     #[stest::test]
-    async fn test_actor_launch() -> Result<()> {
+    async fn test_on_chain_config_cache() -> Result<()> {
         let config = Arc::new(NodeConfig::random_for_test());
         let (storage, chain_info, _) = test_helper::Genesis::init_storage_for_test(config.net())?;
-        let registry = RegistryService::launch();
-        registry.put_shared(config).await?;
-        registry.put_shared(storage).await?;
-        let service_ref = registry.register::<ChainReaderService>().await?;
-        let chain_status = service_ref.main_status().await?;
-        assert_eq!(&chain_status, chain_info.status());
+        let txpool = TxPoolService::new(config.clone(), storage.clone(), chain_info.head().clone());
+        let mut inner = ChainReaderServiceInner::new(
+            config.clone(),
+            StartupInfo::new(chain_info.head().id()),
+            storage.clone(),
+            txpool,
+        )?;
+        let state_reads = || inner.on_chain_config_cache.borrow().state_reads();
+
+        assert!(inner.get_on_chain_config::<VMConfig>()?.is_some());
+        assert!(inner.get_on_chain_config::<VMConfig>()?.is_some());
+        assert_eq!(state_reads(), 1);
+        assert!(inner.get_on_chain_config::<ConsensusConfig>()?.is_some());
+        assert_eq!(state_reads(), 2);
+
+        // advance the head, the cached configs are dropped.
+        let chain = BlockChain::new(
+            config.net().time_service(),
+            chain_info.head().id(),
+            storage.clone(),
+        )?;
+        let mut main = MockChain::new_with_chain(config.net().clone(), chain)?;
+        main.produce_and_apply_times(1)?;
+        inner.switch_main(main.head().current_header().id())?;
+        assert!(inner.get_on_chain_config::<VMConfig>()?.is_some());
+        assert_eq!(inner.on_chain_config_cache.borrow().state_reads(), 3);
+        Ok(())
+    }
+
+    #[stest::test]
+    fn test_recover_startup_info_with_unknown_head() -> Result<()> {
+        let config = NodeConfig::random_for_test();
+        let (storage, chain_info, _) = test_helper::Genesis::init_storage_for_test(config.net())?;
+        // the parent of a missing head header is unknown.
+        let startup_info =
+            recover_startup_info(storage.as_ref(), StartupInfo::new(HashValue::random()))?;
+        assert_eq!(startup_info.main, chain_info.genesis_hash());
         Ok(())
     }
 }
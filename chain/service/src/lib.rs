@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod chain_service;
+pub mod metrics;
+mod on_chain_config_cache;
 
 pub use chain_service::ChainReaderService;
 pub use starcoin_chain_api::{ChainAsyncService, ReadableChainService, WriteableChainService};
@@ -0,0 +1,24 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use once_cell::sync::Lazy;
+use starcoin_metrics::{
+    register_histogram_vec, register_int_counter, HistogramOpts, HistogramVec, IntCounter,
+};
+
+pub static SELF_AUDIT_FAILURES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "starcoin_self_audit_failures",
+        "Counter of the failed chain head self audits"
+    )
+    .unwrap()
+});
+
+pub static CHAIN_REQUEST_HISTOGRAM: Lazy<HistogramVec> = Lazy::new(|| {
+    let opts = HistogramOpts::new(
+        "chain_request_time",
+        "Histogram of the chain request handle time",
+    )
+    .namespace("starcoin");
+    register_histogram_vec!(opts, &["request"]).unwrap()
+});
@@ -0,0 +1,68 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_crypto::HashValue;
+use starcoin_types::access_path::AccessPath;
+use starcoin_vm_types::on_chain_config::ConfigID;
+use std::collections::HashMap;
+
+/// The max count of the cached configs, the on chain configs are few, so it is enough.
+const MAX_CACHED_CONFIGS: usize = 32;
+
+/// A bounded cache of the raw on chain configs read at a head block.
+/// All cached configs are dropped once the head changes.
+#[derive(Default)]
+pub struct OnChainConfigCache {
+    head: Option<HashValue>,
+    configs: HashMap<ConfigID, Option<Vec<u8>>>,
+    hits: u64,
+    state_reads: u64,
+}
+
+impl OnChainConfigCache {
+    /// Get the raw config at the `head` block, read it by `fetch` if it is not cached.
+    pub fn get_or_fetch<F>(
+        &mut self,
+        head: HashValue,
+        config_id: ConfigID,
+        fetch: F,
+    ) -> Result<Option<Vec<u8>>>
+    where
+        F: FnOnce(&AccessPath) -> Result<Option<Vec<u8>>>,
+    {
+        if self.head != Some(head) {
+            self.configs.clear();
+            self.head = Some(head);
+        }
+        if let Some(config) = self.configs.get(&config_id) {
+            self.hits = self.hits.saturating_add(1);
+            return Ok(config.clone());
+        }
+        self.state_reads = self.state_reads.saturating_add(1);
+        let config = fetch(&config_id.clone().access_path())?;
+        if self.configs.len() < MAX_CACHED_CONFIGS {
+            self.configs.insert(config_id, config.clone());
+        }
+        Ok(config)
+    }
+
+    /// The count of the config reads which are not served by the cache.
+    pub fn state_reads(&self) -> u64 {
+        self.state_reads
+    }
+
+    /// The count of the config reads served by the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The count of the cached configs.
+    pub fn len(&self) -> usize {
+        self.configs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.configs.is_empty()
+    }
+}
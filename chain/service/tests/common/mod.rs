@@ -0,0 +1,72 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_chain::BlockChain;
+use starcoin_chain_mock::MockChain;
+use starcoin_chain_service::ChainReaderService;
+use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_service_registry::{RegistryAsyncService, RegistryService, ServiceRef};
+use starcoin_storage::storage::{InnerStore, StorageInstance};
+use starcoin_storage::{Storage, BLOCK_BODY_PREFIX_NAME, BLOCK_PREFIX_NAME};
+use std::sync::Arc;
+
+/// Init the genesis storage of the config, and a mock chain at the genesis on it.
+pub fn init_mock_chain(config: NodeConfig) -> Result<(Arc<NodeConfig>, Arc<Storage>, MockChain)> {
+    let config = Arc::new(config);
+    let net = config.net();
+    let (storage, chain_info, _) = test_helper::Genesis::init_storage_for_test(net)?;
+    let chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage.clone())?;
+    let main = MockChain::new_with_chain(net.clone(), chain)?;
+    Ok((config, storage, main))
+}
+
+/// Init the genesis storage of the config like `init_mock_chain`, and keep the storage instance
+/// under it, so that the test can drop rows which the storage does not expose.
+pub fn init_mock_chain_with_instance(
+    config: NodeConfig,
+) -> Result<(Arc<NodeConfig>, Arc<Storage>, StorageInstance, MockChain)> {
+    let config = Arc::new(config);
+    let net = config.net();
+    let instance = StorageInstance::new_cache_instance();
+    let storage = Arc::new(Storage::new(instance.clone())?);
+    let genesis = test_helper::Genesis::load_or_build(net)?;
+    let chain_info = genesis.execute_genesis_block(net, storage.clone())?;
+    let chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage.clone())?;
+    let main = MockChain::new_with_chain(net.clone(), chain)?;
+    Ok((config, storage, instance, main))
+}
+
+/// Delete the block and its body from the storage instance, the header and block info are kept.
+pub fn delete_block_body(instance: &StorageInstance, block_id: HashValue) -> Result<()> {
+    instance.remove(BLOCK_PREFIX_NAME, block_id.to_vec())?;
+    instance.remove(BLOCK_BODY_PREFIX_NAME, block_id.to_vec())
+}
+
+/// Launch a chain reader service on the storage, the main chain starts at the startup head.
+pub async fn launch_chain_reader_service(
+    config: &Arc<NodeConfig>,
+    storage: &Arc<Storage>,
+) -> Result<(ServiceRef<RegistryService>, ServiceRef<ChainReaderService>)> {
+    let registry = RegistryService::launch();
+    registry.put_shared(config.clone()).await?;
+    registry.put_shared(storage.clone()).await?;
+    let service_ref = registry.register::<ChainReaderService>().await?;
+    Ok((registry, service_ref))
+}
+
+/// Launch a chain reader service on a new genesis storage of the config.
+pub async fn start_chain_reader_service(
+    config: NodeConfig,
+) -> Result<(
+    ServiceRef<RegistryService>,
+    ServiceRef<ChainReaderService>,
+    Arc<Storage>,
+    Arc<NodeConfig>,
+)> {
+    let config = Arc::new(config);
+    let (storage, _, _) = test_helper::Genesis::init_storage_for_test(config.net())?;
+    let (registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+    Ok((registry, service_ref, storage, config))
+}
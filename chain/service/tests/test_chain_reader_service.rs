@@ -0,0 +1,2502 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    delete_block_body, init_mock_chain, init_mock_chain_with_instance, launch_chain_reader_service,
+    start_chain_reader_service,
+};
+use anyhow::Result;
+use futures::future::join;
+use futures::StreamExt;
+use network_api::messages::PeerEvent;
+use network_api::TxnBroadcaster;
+use starcoin_accumulator::inmemory::InMemoryAccumulator;
+use starcoin_accumulator::{Accumulator, AccumulatorProof};
+use starcoin_chain::{BlockChain, ChainReader, ChainWriter};
+use starcoin_chain_api::message::ChainRequest;
+use starcoin_chain_api::{
+    ChainStatsEvent, PendingTxnEvent, SequenceUsage, TxnTypeBreakdown, MAX_BLOCK_BATCH_SIZE,
+};
+use starcoin_chain_service::metrics::{CHAIN_REQUEST_HISTOGRAM, SELF_AUDIT_FAILURES};
+use starcoin_chain_service::ChainAsyncService;
+use starcoin_config::NodeConfig;
+use starcoin_consensus::difficulty::MAX_TARGET_ADJUST_RATIO;
+use starcoin_consensus::Consensus;
+use starcoin_crypto::HashValue;
+use starcoin_service_registry::bus::{Bus, BusService};
+use starcoin_service_registry::{
+    ActorService, EventHandler, RegistryAsyncService, ServiceContext, ServiceHandler,
+    ServiceRequest,
+};
+use starcoin_state_api::{ChainStateReader, StateReaderExt};
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::{BlockStore, IntoSuper, Store};
+use starcoin_transaction_builder::{
+    create_signed_txn_with_association_account, encode_transfer_script_function,
+    DEFAULT_MAX_GAS_AMOUNT,
+};
+use starcoin_txpool::TxPoolService;
+use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::{Block, BlockNumber, ExecutedBlock};
+use starcoin_types::peer_info::PeerId;
+use starcoin_types::startup_info::{ChainStats, StartupInfo};
+use starcoin_types::system_events::{BlockReceived, ChainReorg, MinedBlock, NewHeadBlock};
+use starcoin_types::transaction::{
+    Package, Script, ScriptFunction, SignedUserTransaction, TransactionPayload,
+};
+use starcoin_types::write_set::{WriteOp, WriteSetMut};
+use starcoin_types::U256;
+use starcoin_vm_types::account_config::{
+    association_address, core_code_address, genesis_address, stc_type_tag,
+};
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::ModuleId;
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::on_chain_config::DaoConfig;
+use starcoin_vm_types::on_chain_resource::dao::ProposalState;
+use starcoin_vm_types::on_chain_resource::{Epoch, GlobalTimeOnChain};
+use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
+use starcoin_vm_types::token::token_code::TokenCode;
+use starcoin_vm_types::transaction::{TransactionError, TransactionStatus};
+use starcoin_vm_types::vm_status::KeptVMStatus;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use stest::actix_export::time::delay_for;
+use test_helper::executor::{compile_modules_with_address, compile_script};
+use test_helper::Account;
+
+pub mod common;
+
+#[stest::test]
+async fn test_actor_launch() -> Result<()> {
+    let (_registry, service_ref, storage, _) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let chain_info = storage.get_chain_info()?.expect("chain info should exist.");
+    let chain_status = service_ref.main_status().await?;
+    assert_eq!(&chain_status, chain_info.status());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_branch_info() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let chain_info = main.chain_info();
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let genesis_id = chain_info.head().id();
+    main.produce_and_apply_times(3)?;
+    let mut branch = main.fork(Some(genesis_id))?;
+    let branch_tip = branch.produce_and_apply()?;
+
+    let branch_info = service_ref
+        .get_branch_info(branch_tip.id())
+        .await?
+        .expect("branch info should exist.");
+    assert_eq!(branch_info, branch.chain_info());
+    assert_eq!(branch_info.status().head().number(), 1);
+    assert_ne!(branch_info, main.chain_info());
+
+    let main_info = service_ref
+        .get_branch_info(main.head().current_header().id())
+        .await?
+        .expect("main info should exist.");
+    assert_eq!(main_info, main.chain_info());
+
+    assert!(service_ref
+        .get_branch_info(HashValue::random())
+        .await?
+        .is_none());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_create_block_template_on() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    main.produce_and_apply_times(3)?;
+    let parent = main
+        .head()
+        .get_header_by_number(1)?
+        .expect("block 1 should exist.");
+    assert_ne!(parent.id(), main.head().current_header().id());
+
+    let template = service_ref
+        .create_block_template_on(parent.id(), 10)
+        .await?;
+    assert_eq!(template.parent_hash, parent.id());
+    assert_eq!(template.number, parent.number() + 1);
+
+    assert!(service_ref
+        .create_block_template_on(HashValue::random(), 10)
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_chain_stats() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    main.produce_and_apply_times(5)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let stats = service_ref.get_chain_stats().await?;
+    let status = service_ref.main_status().await?;
+    let head = service_ref.main_head_header().await?;
+    assert_eq!(stats.head_number, head.number());
+    assert_eq!(stats.head_number, 5);
+    assert_eq!(
+        stats.total_txns,
+        status.info().txn_accumulator_info.num_leaves
+    );
+    assert_eq!(stats.total_difficulty, status.total_difficulty());
+    assert_eq!(stats.current_difficulty, head.difficulty());
+    let stc_info = main
+        .head()
+        .chain_state_reader()
+        .get_stc_info()?
+        .expect("stc info should exist.");
+    assert_eq!(stats.total_supply, stc_info.total_value);
+
+    let window = service_ref.epoch_info().await?.block_difficulty_window();
+    let blocks = service_ref
+        .main_blocks_by_number(None, window.saturating_add(1))
+        .await?;
+    let mut intervals: Vec<u64> = blocks
+        .windows(2)
+        .map(|pair| pair[0].header().timestamp() - pair[1].header().timestamp())
+        .collect();
+    intervals.sort_unstable();
+    assert_eq!(stats.median_block_time, intervals[intervals.len() / 2]);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_min_gas_price() -> Result<()> {
+    let (_registry, service_ref, storage, config) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let chain_info = storage.get_chain_info()?.expect("chain info should exist.");
+
+    let min_gas_price = service_ref.get_min_gas_price().await?;
+    assert_eq!(min_gas_price, config.tx_pool.min_gas_price());
+
+    let txpool = TxPoolService::new(config.clone(), storage, chain_info.head().clone());
+    let txn = SignedUserTransaction::mock();
+    assert!(txn.gas_unit_price() < min_gas_price);
+    let result = txpool
+        .add_txns(vec![txn])
+        .pop()
+        .expect("add txns should return a result.");
+    assert!(matches!(
+        result,
+        Err(TransactionError::InsufficientGasPrice { minimal, .. }) if minimal == min_gas_price
+    ));
+    Ok(())
+}
+
+#[derive(Default)]
+struct PendingTxnCollector {
+    txns: Vec<HashValue>,
+}
+
+impl ActorService for PendingTxnCollector {}
+
+impl EventHandler<Self, PendingTxnEvent> for PendingTxnCollector {
+    fn handle_event(&mut self, event: PendingTxnEvent, _ctx: &mut ServiceContext<Self>) {
+        self.txns.push(event.txn.id());
+    }
+}
+
+#[derive(Debug)]
+struct GetCollectedTxns;
+
+impl ServiceRequest for GetCollectedTxns {
+    type Response = Vec<HashValue>;
+}
+
+impl ServiceHandler<Self, GetCollectedTxns> for PendingTxnCollector {
+    fn handle(
+        &mut self,
+        _msg: GetCollectedTxns,
+        _ctx: &mut ServiceContext<Self>,
+    ) -> Vec<HashValue> {
+        self.txns.clone()
+    }
+}
+
+#[stest::test]
+async fn test_subscribe_pending_txns() -> Result<()> {
+    let (registry, service_ref, _, config) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let txpool = registry.get_shared::<TxPoolService>().await?;
+    let collector = registry.register::<PendingTxnCollector>().await?;
+    service_ref
+        .subscribe_pending_txns(collector.clone().event_notifier())
+        .await?;
+
+    let txns: Vec<SignedUserTransaction> = test_helper::txn::create_account(config.net(), 0, 3)
+        .into_iter()
+        .map(|(_, txn)| txn)
+        .collect();
+    assert!(txpool
+        .add_txns(txns.clone())
+        .into_iter()
+        .all(|result| result.is_ok()));
+    // the duplicated txn and the txn with too low gas price are rejected.
+    let rejected_txns = vec![txns[0].clone(), SignedUserTransaction::mock()];
+    assert!(txpool
+        .add_txns(rejected_txns)
+        .into_iter()
+        .all(|result| result.is_err()));
+    delay_for(Duration::from_millis(200)).await;
+
+    let mut collected_txns = collector.send(GetCollectedTxns).await?;
+    collected_txns.sort();
+    let mut expect_txns: Vec<HashValue> = txns.iter().map(|txn| txn.id()).collect();
+    expect_txns.sort();
+    assert_eq!(collected_txns, expect_txns);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_block_txn_root() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let head = main.produce_and_apply()?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let txn_root = service_ref.get_block_txn_root(head.id()).await?;
+    assert_eq!(txn_root, Some(head.txn_accumulator_root()));
+    assert!(service_ref
+        .get_block_txn_root(HashValue::random())
+        .await?
+        .is_none());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_simulate_write_set() -> Result<()> {
+    let (_registry, service_ref, storage, _) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let chain_info = storage.get_chain_info()?.expect("chain info should exist.");
+
+    let head_state_root = chain_info.head().state_root();
+    let time_path =
+        AccessPath::resource_access_path(genesis_address(), GlobalTimeOnChain::struct_tag());
+    let write_set = WriteSetMut::new(vec![
+        (
+            time_path,
+            // the bcs bytes of GlobalTimeOnChain { milliseconds: u64::MAX }
+            WriteOp::Value(u64::max_value().to_le_bytes().to_vec()),
+        ),
+        (
+            AccessPath::resource_access_path(AccountAddress::random(), Epoch::struct_tag()),
+            WriteOp::Value(vec![1, 2, 3]),
+        ),
+    ])
+    .freeze()?;
+    let result = service_ref.simulate_write_set(write_set.clone()).await?;
+    assert_ne!(result.state_root, head_state_root);
+    let write_paths: Vec<AccessPath> = write_set
+        .iter()
+        .map(|(access_path, _)| access_path.clone())
+        .collect();
+    assert_eq!(result.changed_paths, write_paths);
+
+    // the simulation is not persisted.
+    let head = service_ref.main_head_header().await?;
+    assert_eq!(head.state_root(), head_state_root);
+    let again = service_ref.simulate_write_set(write_set).await?;
+    assert_eq!(again, result);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_block_from_head() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let chain_info = main.chain_info();
+    main.produce_and_apply_times(3)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let head = service_ref.main_head_block().await?;
+    assert_eq!(service_ref.get_block_from_head(0).await?, Some(head));
+    let block = service_ref
+        .get_block_from_head(2)
+        .await?
+        .expect("block should exist.");
+    assert_eq!(block.header().number(), 1);
+    let genesis = service_ref
+        .get_block_from_head(3)
+        .await?
+        .expect("genesis should exist.");
+    assert_eq!(genesis.id(), chain_info.head().id());
+    assert!(service_ref.get_block_from_head(4).await?.is_none());
+    assert!(service_ref
+        .get_block_from_head(u64::max_value())
+        .await?
+        .is_none());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_block_txn_hashes() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let txns: Vec<SignedUserTransaction> = test_helper::txn::create_account(config.net(), 0, 2)
+        .into_iter()
+        .map(|(_, txn)| txn)
+        .collect();
+    let (template, excluded) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        txns.clone(),
+        vec![],
+        None,
+    )?;
+    assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, config.net().time_service().as_ref())?;
+    let block_id = block.id();
+    main.apply(block)?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let txn_hashes = service_ref
+        .get_block_txn_hashes(block_id)
+        .await?
+        .expect("block should exist.");
+    assert_eq!(
+        txn_hashes,
+        txns.iter().map(|txn| txn.id()).collect::<Vec<_>>()
+    );
+    assert!(service_ref
+        .get_block_txn_hashes(HashValue::random())
+        .await?
+        .is_none());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_is_best_head() -> Result<()> {
+    let (registry, service, storage, _) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let chain_info = storage.get_chain_info()?.expect("chain info should exist.");
+    let bus = registry.service_ref::<BusService>().await?;
+    // no network, no peer head is known.
+    assert!(service.is_best_head().await?);
+
+    let peer_id = PeerId::random();
+    let mut heavier_peer_info = chain_info.clone();
+    let mut heavier_status = chain_info.status().clone();
+    heavier_status.info.total_difficulty = chain_info.total_difficulty() + U256::one();
+    heavier_peer_info.update_status(heavier_status);
+    bus.broadcast(PeerEvent::Open(
+        peer_id.clone(),
+        Box::new(heavier_peer_info),
+    ))?;
+    delay_for(Duration::from_millis(200)).await;
+    assert!(!service.is_best_head().await?);
+
+    bus.broadcast(PeerEvent::Open(peer_id.clone(), Box::new(chain_info)))?;
+    delay_for(Duration::from_millis(200)).await;
+    assert!(service.is_best_head().await?);
+
+    bus.broadcast(PeerEvent::Close(peer_id))?;
+    delay_for(Duration::from_millis(200)).await;
+    assert!(service.is_best_head().await?);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_consensus_info() -> Result<()> {
+    let (_registry, service, _, config) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let consensus_info = service.get_consensus_info().await?;
+    // the test network runs the dummy consensus.
+    assert_eq!(consensus_info.name, "dummy");
+    assert_eq!(
+        consensus_info.target_block_time,
+        config
+            .net()
+            .genesis_config()
+            .consensus_config
+            .base_block_time_target
+    );
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_signed_txn_bytes() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let txn = test_helper::txn::create_account(config.net(), 0, 1)
+        .pop()
+        .map(|(_, txn)| txn)
+        .expect("txn should exist.");
+    let (template, excluded) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![txn.clone()],
+        vec![],
+        None,
+    )?;
+    assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, config.net().time_service().as_ref())?;
+    main.apply(block)?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let bytes = service_ref
+        .get_signed_txn_bytes(txn.id())
+        .await?
+        .expect("txn should exist.");
+    assert_eq!(bcs_ext::from_bytes::<SignedUserTransaction>(&bytes)?, txn);
+    assert!(service_ref
+        .get_signed_txn_bytes(HashValue::random())
+        .await?
+        .is_none());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_self_audit() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.storage.self_audit_interval = Some(1);
+    let (_registry, _, storage, _) = start_chain_reader_service(config).await?;
+    let chain_info = storage.get_chain_info()?.expect("chain info should exist.");
+
+    // replace the head state root node with another state node.
+    let storage: Arc<dyn Store> = storage;
+    let state_root = chain_info.head().state_root();
+    let other_root = ChainStateDB::new(storage.clone().into_super_arc(), Some(state_root))
+        .get_account_state(&genesis_address())?
+        .expect("account should exist.")
+        .resource_root();
+    let other_node = storage.get(&other_root)?.expect("state node should exist.");
+    let failures = SELF_AUDIT_FAILURES.get();
+    storage.put(state_root, other_node)?;
+    delay_for(Duration::from_millis(2500)).await;
+    assert!(SELF_AUDIT_FAILURES.get() > failures);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_difficulty_params() -> Result<()> {
+    let (_registry, service, _, config) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let params = service.get_difficulty_params().await?;
+    assert_eq!(
+        params.window,
+        config
+            .net()
+            .genesis_config()
+            .consensus_config
+            .base_block_difficulty_window
+    );
+    assert_eq!(params.max_adjust_ratio, MAX_TARGET_ADJUST_RATIO);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_submit_and_mine() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.miner.enable_submit_and_mine = Some(true);
+    let (registry, service_ref, storage, config) = start_chain_reader_service(config).await?;
+    let bus = registry.service_ref::<BusService>().await?;
+    let mut mined_blocks = bus.channel::<MinedBlock>().await?;
+
+    let (account, txn) = test_helper::txn::create_account(config.net(), 0, 1)
+        .pop()
+        .expect("txn should exist.");
+    let head = service_ref.main_head_header().await?;
+    // connect the mined block as the block connector does, the result is returned after that.
+    let connect_block = async {
+        let MinedBlock(block) = mined_blocks
+            .next()
+            .await
+            .expect("mined block should be sent.");
+        assert_eq!(block.header().parent_hash(), head.id());
+        // the reader only sends the mined block, nothing is written until the block is connected.
+        assert!(service_ref.get_block_by_hash(block.id()).await?.is_none());
+        assert_eq!(service_ref.main_head_header().await?, head);
+        let mut chain = BlockChain::new(config.net().time_service(), head.id(), storage.clone())?;
+        let executed_block = chain.apply(block.as_ref().clone())?;
+        bus.broadcast(NewHeadBlock(Arc::new(executed_block)))?;
+        Ok::<_, anyhow::Error>(block.id())
+    };
+    let (result, block_id) = join(service_ref.submit_and_mine(txn.clone()), connect_block).await;
+    let result = result?;
+    assert_eq!(result.head, block_id?);
+    assert!(matches!(
+        result.output.status(),
+        TransactionStatus::Keep(KeptVMStatus::Executed)
+    ));
+
+    let new_head = service_ref.main_head_header().await?;
+    assert_eq!(new_head.id(), result.head);
+    let before = ChainStateDB::new(storage.clone().into_super_arc(), Some(head.state_root()));
+    assert!(before.get_balance(*account.address())?.is_none());
+    let after = ChainStateDB::new(storage.into_super_arc(), Some(new_head.state_root()));
+    assert!(after.get_balance(*account.address())?.unwrap_or(0) > 0);
+    assert!(service_ref.get_transaction_info(txn.id()).await?.is_some());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_txn_multiproof() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let mut txn_hashes = vec![];
+    let mut seq_number = 0;
+    for _ in 0..2 {
+        let txns: Vec<SignedUserTransaction> =
+            test_helper::txn::create_account(config.net(), seq_number, 3)
+                .into_iter()
+                .map(|(_, txn)| txn)
+                .collect();
+        seq_number += txns.len() as u64;
+        txn_hashes.extend(txns.iter().map(|txn| txn.id()));
+        let (template, excluded) =
+            main.head()
+                .create_block_template(*main.miner().address(), None, txns, vec![], None)?;
+        assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+        let block = main
+            .head()
+            .consensus()
+            .create_block(template, config.net().time_service().as_ref())?;
+        main.apply(block)?;
+    }
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    // skip the first txn of the second block to prove a subset of it.
+    let proved_hashes = vec![txn_hashes[0], txn_hashes[1], txn_hashes[2], txn_hashes[5]];
+    let multiproof = service_ref
+        .get_txn_multiproof(proved_hashes.clone())
+        .await?;
+    assert_eq!(multiproof.blocks.len(), 2);
+    for block_proof in &multiproof.blocks {
+        let header = service_ref
+            .get_header_by_hash(&block_proof.block_id)
+            .await?
+            .expect("block should exist.");
+        block_proof
+            .proof
+            .verify(header.txn_accumulator_root(), &block_proof.txn_infos)?;
+    }
+    for txn_hash in &proved_hashes {
+        let txn_info = service_ref
+            .get_transaction_info(*txn_hash)
+            .await?
+            .expect("txn info should exist.");
+        let block_proof = multiproof
+            .blocks
+            .iter()
+            .find(|block_proof| block_proof.block_id == txn_info.block_id())
+            .expect("block proof should exist.");
+        assert!(block_proof
+            .txn_infos
+            .iter()
+            .any(|(_, txn_info_id)| *txn_info_id == txn_info.id()));
+    }
+    let proved_count: usize = multiproof
+        .blocks
+        .iter()
+        .map(|block_proof| block_proof.txn_infos.len())
+        .sum();
+    assert_eq!(proved_count, proved_hashes.len());
+    assert!(service_ref
+        .get_txn_multiproof(vec![HashValue::random()])
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_framework_version() -> Result<()> {
+    let (_registry, service, _, config) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    assert_eq!(
+        service.get_framework_version().await?,
+        config.net().genesis_config().version.major
+    );
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_oldest_available_block() -> Result<()> {
+    let (config, storage, instance, mut main) =
+        init_mock_chain_with_instance(NodeConfig::random_for_test())?;
+    main.produce_and_apply_times(5)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+    assert_eq!(service_ref.get_oldest_available_block().await?, 0);
+
+    for number in 0..3 {
+        let block_id = main
+            .head()
+            .get_hash_by_number(number)?
+            .expect("block should exist.");
+        delete_block_body(&instance, block_id)?;
+    }
+    assert_eq!(service_ref.get_oldest_available_block().await?, 3);
+    assert!(service_ref.main_block_by_number(2).await?.is_none());
+    assert!(service_ref.main_block_by_number(3).await?.is_some());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_fee_revenue() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.storage.block_range_max_count = Some(3);
+    let (config, storage, mut main) = init_mock_chain(config)?;
+    let mut txns = vec![];
+    for i in 0..3 {
+        let block_txns: Vec<SignedUserTransaction> =
+            test_helper::txn::create_account(config.net(), i * 2, 2)
+                .into_iter()
+                .map(|(_, txn)| txn)
+                .collect();
+        txns.push(block_txns.clone());
+        let (template, excluded) = main.head().create_block_template(
+            *main.miner().address(),
+            None,
+            block_txns,
+            vec![],
+            None,
+        )?;
+        assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+        let block = main
+            .head()
+            .consensus()
+            .create_block(template, config.net().time_service().as_ref())?;
+        main.apply(block)?;
+    }
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let mut block_fees = vec![];
+    for block_txns in &txns {
+        let mut fee = 0u128;
+        for txn in block_txns {
+            let txn_info = service_ref
+                .get_transaction_info(txn.id())
+                .await?
+                .expect("txn info should exist.");
+            fee += u128::from(txn_info.gas_used()) * u128::from(txn.gas_unit_price());
+        }
+        assert!(fee > 0);
+        block_fees.push(fee);
+    }
+    assert_eq!(
+        service_ref.get_fee_revenue(1, 3).await?,
+        block_fees.iter().sum::<u128>()
+    );
+    assert_eq!(
+        service_ref.get_fee_revenue(2, 3).await?,
+        block_fees[1] + block_fees[2]
+    );
+    // the genesis block has no user txn.
+    assert_eq!(service_ref.get_fee_revenue(0, 0).await?, 0);
+    assert!(service_ref.get_fee_revenue(3, 1).await.is_err());
+    assert!(service_ref.get_fee_revenue(1, 4).await.is_err());
+    // the range is larger than the block range max count.
+    assert!(service_ref.get_fee_revenue(0, 3).await.is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_genesis_config() -> Result<()> {
+    let (_registry, service, _, config) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let genesis_config = service.get_genesis_config().await?;
+    assert_eq!(genesis_config.chain_id, config.net().chain_id());
+    assert_eq!(
+        genesis_config.reward_delay,
+        config.net().genesis_config().reward_delay
+    );
+    assert_eq!(
+        genesis_config.consensus_config,
+        config.net().genesis_config().consensus_config
+    );
+    Ok(())
+}
+
+/// A network which only records the broadcast txns.
+#[derive(Default)]
+struct RecordTxnBroadcaster {
+    txns: Mutex<Vec<HashValue>>,
+}
+
+impl TxnBroadcaster for RecordTxnBroadcaster {
+    fn broadcast_txns(&self, txns: Vec<SignedUserTransaction>) {
+        self.txns
+            .lock()
+            .unwrap()
+            .extend(txns.iter().map(|txn| txn.id()));
+    }
+}
+
+#[stest::test]
+async fn test_rebroadcast_txn() -> Result<()> {
+    let (registry, service_ref, _, config) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let txpool = registry.get_shared::<TxPoolService>().await?;
+    let (_, txn) = test_helper::txn::create_account(config.net(), 0, 1)
+        .pop()
+        .expect("should create one txn.");
+    txpool.add_txns(vec![txn.clone()]).pop().unwrap()?;
+
+    // no network, no broadcast.
+    assert!(service_ref.rebroadcast_txn(txn.id()).await.is_err());
+
+    let network = Arc::new(RecordTxnBroadcaster::default());
+    registry
+        .put_shared::<Arc<dyn TxnBroadcaster>>(network.clone())
+        .await?;
+    assert!(!service_ref.rebroadcast_txn(HashValue::random()).await?);
+    assert!(service_ref.rebroadcast_txn(txn.id()).await?);
+    assert_eq!(*network.txns.lock().unwrap(), vec![txn.id()]);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_gas_used_range() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.storage.block_range_max_count = Some(3);
+    let (config, storage, mut main) = init_mock_chain(config)?;
+    for i in 0..3 {
+        let block_txns: Vec<SignedUserTransaction> =
+            test_helper::txn::create_account(config.net(), i, 1)
+                .into_iter()
+                .map(|(_, txn)| txn)
+                .collect();
+        let (template, excluded) = main.head().create_block_template(
+            *main.miner().address(),
+            None,
+            block_txns,
+            vec![],
+            None,
+        )?;
+        assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+        let block = main
+            .head()
+            .consensus()
+            .create_block(template, config.net().time_service().as_ref())?;
+        main.apply(block)?;
+    }
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let mut block_gas = vec![];
+    for number in 0..=3 {
+        let block = service_ref
+            .main_block_by_number(number)
+            .await?
+            .expect("block should exist.");
+        let gas_used = service_ref
+            .get_block_txn_infos(block.id())
+            .await?
+            .iter()
+            .map(|txn_info| u128::from(txn_info.gas_used()))
+            .sum::<u128>();
+        block_gas.push(gas_used);
+    }
+    assert!(block_gas[1..].iter().all(|gas_used| *gas_used > 0));
+    assert_eq!(
+        service_ref.get_gas_used_range(1, 3).await?,
+        block_gas[1..].iter().sum::<u128>()
+    );
+    assert_eq!(
+        service_ref.get_gas_used_range(2, 3).await?,
+        block_gas[2] + block_gas[3]
+    );
+    assert_eq!(service_ref.get_gas_used_range(1, 1).await?, block_gas[1]);
+    assert!(service_ref.get_gas_used_range(3, 1).await.is_err());
+    assert!(service_ref.get_gas_used_range(1, 4).await.is_err());
+    // the range is larger than the block range max count.
+    assert!(service_ref.get_gas_used_range(0, 3).await.is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_estimate_time_to_block() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    // produce the blocks one hour apart.
+    let interval = 60 * 60 * 1000;
+    let base_timestamp = config.net().time_service().now_millis();
+    for i in 1..=5 {
+        main.head().time_service().adjust(GlobalTimeOnChain {
+            milliseconds: base_timestamp + i * interval,
+        });
+        main.produce_and_apply()?;
+    }
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    assert_eq!(service_ref.estimate_time_to_block(15).await?, 10 * 60 * 60);
+    assert_eq!(service_ref.estimate_time_to_block(6).await?, 60 * 60);
+    assert_eq!(service_ref.estimate_time_to_block(5).await?, 0);
+    assert_eq!(service_ref.estimate_time_to_block(1).await?, 0);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_accumulator_siblings() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    main.produce_and_apply_times(5)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let accumulator_root = service_ref
+        .main_status()
+        .await?
+        .info()
+        .get_block_accumulator_info()
+        .accumulator_root;
+    for number in 0..=5 {
+        let block_id = service_ref
+            .main_block_by_number(number)
+            .await?
+            .expect("block should exist.")
+            .id();
+        let siblings = service_ref.get_accumulator_siblings(number).await?;
+        assert!(!siblings.is_empty());
+        AccumulatorProof::new(siblings).verify(accumulator_root, block_id, number)?;
+    }
+    assert!(service_ref.get_accumulator_siblings(6).await.is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_block_txn_type_breakdown() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let chain_info = main.chain_info();
+
+    let (_, script_function_txn) = test_helper::txn::create_account(net, 0, 1)
+        .pop()
+        .expect("txn should exist.");
+    let expiration_timestamp_secs = net.time_service().now_secs() + 3600;
+    let script = Script::new(compile_script("script { fun main() {} }"), vec![], vec![]);
+    let script_txn = create_signed_txn_with_association_account(
+        TransactionPayload::Script(script),
+        1,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        expiration_timestamp_secs,
+        net,
+    );
+    let module = compile_modules_with_address(
+        association_address(),
+        "module {{sender}}::M { public fun f() {} }",
+    )
+    .pop()
+    .expect("module should exist.");
+    let package_txn = create_signed_txn_with_association_account(
+        TransactionPayload::Package(Package::new_with_module(module)?),
+        2,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        expiration_timestamp_secs,
+        net,
+    );
+    let (template, excluded) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![script_function_txn, script_txn, package_txn],
+        vec![],
+        None,
+    )?;
+    assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    let block_id = block.id();
+    main.apply(block)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let breakdown = service_ref.get_block_txn_type_breakdown(block_id).await?;
+    assert_eq!(
+        breakdown,
+        TxnTypeBreakdown {
+            scripts: 1,
+            modules: 1,
+            script_functions: 1,
+        }
+    );
+    // the genesis block includes no user txn.
+    assert_eq!(
+        service_ref
+            .get_block_txn_type_breakdown(chain_info.head().id())
+            .await?,
+        TxnTypeBreakdown::default()
+    );
+    assert!(service_ref
+        .get_block_txn_type_breakdown(HashValue::random())
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_verify_txn_signature() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let (account, create_account_txn) = test_helper::txn::create_account(net, 0, 1)
+        .pop()
+        .expect("txn should exist.");
+    let (template, _) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![create_account_txn],
+        vec![],
+        None,
+    )?;
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    main.apply(block)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let expiration_timestamp_secs = net.time_service().now_secs() + 3600;
+    let sign_txn = |signer: &Account, sequence_number: u64| {
+        signer.create_signed_txn_impl(
+            *account.address(),
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            sequence_number,
+            DEFAULT_MAX_GAS_AMOUNT,
+            1,
+            expiration_timestamp_secs,
+            net.chain_id(),
+        )
+    };
+    let txn = sign_txn(&account, 0);
+    assert!(service_ref.verify_txn_signature(txn.clone()).await?);
+
+    // the signature of another txn.
+    let tampered_txn =
+        SignedUserTransaction::new(sign_txn(&account, 1).raw_txn().clone(), txn.authenticator());
+    assert!(!service_ref.verify_txn_signature(tampered_txn).await?);
+    // a valid signature, but not by the sender's key.
+    let other_signer_txn = sign_txn(&Account::new(), 0);
+    assert!(!service_ref.verify_txn_signature(other_signer_txn).await?);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_snapshot_balances() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let (accounts, txns): (Vec<_>, Vec<_>) = test_helper::txn::create_account(net, 0, 3)
+        .into_iter()
+        .unzip();
+    let (template, _) =
+        main.head()
+            .create_block_template(*main.miner().address(), None, txns, vec![], None)?;
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    main.apply(block)?;
+    main.produce_and_apply_times(1)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let genesis_snapshot: HashMap<_, _> = service_ref
+        .snapshot_balances(0)
+        .await?
+        .into_iter()
+        .collect();
+    let snapshot: HashMap<_, _> = service_ref
+        .snapshot_balances(1)
+        .await?
+        .into_iter()
+        .collect();
+    let block_1 = main
+        .head()
+        .get_header_by_number(1)?
+        .expect("block 1 should exist.");
+    let state_reader = ChainStateDB::new(storage.into_super_arc(), Some(block_1.state_root()));
+    for account in accounts.iter() {
+        assert!(!genesis_snapshot.contains_key(account.address()));
+        let balance = state_reader
+            .get_balance(*account.address())?
+            .expect("balance should exist.");
+        assert!(balance > 0);
+        assert_eq!(snapshot.get(account.address()), Some(&balance));
+    }
+    assert_eq!(
+        snapshot.get(&association_address()),
+        state_reader.get_balance(association_address())?.as_ref()
+    );
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_reward_schedule() -> Result<()> {
+    let (_registry, service, _, config) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let schedule = service.get_reward_schedule().await?;
+    let genesis_config = config.net().genesis_config();
+    assert_eq!(
+        schedule.base_reward_per_block,
+        genesis_config.consensus_config.base_reward_per_block
+    );
+    assert_eq!(schedule.reward_delay, genesis_config.reward_delay);
+    assert_eq!(
+        schedule.epoch_block_count,
+        genesis_config.consensus_config.epoch_block_count
+    );
+    let epoch = service.epoch_info().await?;
+    assert_eq!(schedule.reward_per_block, epoch.epoch().reward_per_block());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_find_conflicting_txns() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let (registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+    let txpool = registry.get_shared::<TxPoolService>().await?;
+
+    // two txns of the association account with the same sequence number.
+    let (_, pending_txn) = test_helper::txn::create_account(net, 0, 1)
+        .pop()
+        .expect("txn should exist.");
+    let (_, executed_txn) = test_helper::txn::create_account(net, 0, 1)
+        .pop()
+        .expect("txn should exist.");
+    txpool.add_txns(vec![pending_txn.clone()]).pop().unwrap()?;
+    let (template, _) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![executed_txn.clone()],
+        vec![],
+        None,
+    )?;
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    main.apply(block)?;
+
+    assert_eq!(
+        service_ref.find_conflicting_txns(executed_txn.id()).await?,
+        vec![pending_txn.id()]
+    );
+    // a pending txn does not conflict with another pending txn, the txpool does not keep
+    // two txns of the same sequence number.
+    assert!(service_ref
+        .find_conflicting_txns(pending_txn.id())
+        .await?
+        .is_empty());
+    assert!(service_ref
+        .find_conflicting_txns(HashValue::random())
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_preview_next_state_root() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let chain_info = main.chain_info();
+    let (registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+    let txpool = registry.get_shared::<TxPoolService>().await?;
+    let txns: Vec<_> = test_helper::txn::create_account(net, 0, 3)
+        .into_iter()
+        .map(|(_, txn)| txn)
+        .collect();
+    for result in txpool.add_txns(txns.clone()) {
+        result?;
+    }
+
+    let preview = service_ref.preview_next_state_root(1).await?;
+    assert_eq!(preview.included_txns, 1);
+    let preview = service_ref.preview_next_state_root(10).await?;
+    assert_eq!(preview.included_txns, 3);
+    // the preview does not change the head.
+    assert_eq!(
+        service_ref.main_head_header().await?.id(),
+        chain_info.head().id()
+    );
+
+    let (template, _) = main.head().create_block_template(
+        genesis_address(),
+        None,
+        txns,
+        vec![],
+        config.miner.block_gas_limit,
+    )?;
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    main.apply(block)?;
+    assert_eq!(
+        main.head().current_header().state_root(),
+        preview.state_root
+    );
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_balance_deltas() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let mut accounts = test_helper::txn::create_account(net, 0, 2);
+    let (receiver, create_receiver_txn) = accounts.pop().expect("txn should exist.");
+    let (sender, create_sender_txn) = accounts.pop().expect("txn should exist.");
+    let expiration_timestamp_secs = net.time_service().now_secs() + 3600;
+    let amount = 100;
+    let transfer_txn = sender.create_signed_txn_with_args(
+        TransactionPayload::ScriptFunction(encode_transfer_script_function(
+            *receiver.address(),
+            amount,
+        )),
+        0,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        expiration_timestamp_secs,
+        net.chain_id(),
+    );
+    for txns in vec![
+        vec![create_sender_txn, create_receiver_txn],
+        vec![transfer_txn.clone()],
+    ] {
+        let (template, excluded) =
+            main.head()
+                .create_block_template(*main.miner().address(), None, txns, vec![], None)?;
+        assert!(excluded.discarded_txns.is_empty());
+        let block = main
+            .head()
+            .consensus()
+            .create_block(template, net.time_service().as_ref())?;
+        main.apply(block)?;
+    }
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+    let transfer_gas_used = storage
+        .get_block_transaction_infos(main.head().current_header().id())?
+        .iter()
+        .find(|txn_info| txn_info.transaction_hash() == transfer_txn.id())
+        .expect("txn info should exist.")
+        .gas_used();
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    // the accounts created in between report the whole balance.
+    let deltas: HashMap<_, _> = service_ref
+        .get_balance_deltas(0, 1)
+        .await?
+        .into_iter()
+        .collect();
+    for account in &[&sender, &receiver] {
+        assert!(deltas[account.address()] > 0);
+    }
+    assert!(deltas[&association_address()] < 0);
+
+    let deltas: HashMap<_, _> = service_ref
+        .get_balance_deltas(1, 2)
+        .await?
+        .into_iter()
+        .collect();
+    assert_eq!(deltas[receiver.address()], amount as i128);
+    assert_eq!(
+        deltas[sender.address()],
+        -(amount as i128 + transfer_gas_used as i128)
+    );
+    assert!(service_ref.get_balance_deltas(2, 1).await.is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_validate_block_template() -> Result<()> {
+    let (config, storage, main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let (_, txn) = test_helper::txn::create_account(net, 0, 1)
+        .pop()
+        .expect("txn should exist.");
+    let (template, _) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![txn],
+        vec![],
+        None,
+    )?;
+    let validation = service_ref
+        .validate_block_template(template.clone())
+        .await?;
+    assert_eq!(validation.state_root, template.state_root);
+    assert_eq!(
+        validation.txn_accumulator_root,
+        template.txn_accumulator_root
+    );
+    assert_eq!(
+        validation.block_accumulator_root,
+        template.block_accumulator_root
+    );
+    assert_eq!(validation.gas_used, template.gas_used);
+
+    // a bogus txn which reuses the sequence number of the packed txn.
+    let (_, bogus_txn) = test_helper::txn::create_account(net, 0, 1)
+        .pop()
+        .expect("txn should exist.");
+    let mut tampered = template.clone();
+    tampered.body.transactions.push(bogus_txn);
+    tampered.body_hash = tampered.body.hash();
+    assert!(service_ref.validate_block_template(tampered).await.is_err());
+
+    let mut tampered = template;
+    tampered.state_root = HashValue::random();
+    assert!(service_ref.validate_block_template(tampered).await.is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_transaction_by_hash() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let txn = test_helper::txn::create_account(config.net(), 0, 1)
+        .pop()
+        .map(|(_, txn)| txn)
+        .expect("txn should exist.");
+    let (template, excluded) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![txn.clone()],
+        vec![],
+        None,
+    )?;
+    assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, config.net().time_service().as_ref())?;
+    main.apply(block)?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    assert_eq!(
+        service_ref.get_transaction_by_hash(&txn.id()).await?,
+        Some(txn)
+    );
+    assert!(service_ref
+        .get_transaction_by_hash(&HashValue::random())
+        .await?
+        .is_none());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_block_touched_addresses() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let mut accounts = test_helper::txn::create_account(net, 0, 2);
+    let (receiver, create_receiver_txn) = accounts.pop().expect("txn should exist.");
+    let (sender, create_sender_txn) = accounts.pop().expect("txn should exist.");
+    let transfer_txn = sender.create_signed_txn_with_args(
+        TransactionPayload::ScriptFunction(encode_transfer_script_function(
+            *receiver.address(),
+            100,
+        )),
+        0,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        net.time_service().now_secs() + 3600,
+        net.chain_id(),
+    );
+    for txns in vec![
+        vec![create_sender_txn, create_receiver_txn],
+        vec![transfer_txn],
+    ] {
+        let (template, excluded) =
+            main.head()
+                .create_block_template(*main.miner().address(), None, txns, vec![], None)?;
+        assert!(excluded.discarded_txns.is_empty());
+        let block = main
+            .head()
+            .consensus()
+            .create_block(template, net.time_service().as_ref())?;
+        main.apply(block)?;
+    }
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let addresses = service_ref
+        .get_block_touched_addresses(main.head().current_header().id())
+        .await?;
+    for account in &[&sender, &receiver] {
+        assert_eq!(
+            addresses
+                .iter()
+                .filter(|address| *address == account.address())
+                .count(),
+            1
+        );
+    }
+    assert!(service_ref
+        .get_block_touched_addresses(HashValue::random())
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_next_epoch_boundary() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let (registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+    let bus = registry.service_ref::<BusService>().await?;
+    let epoch_block_count = config
+        .net()
+        .genesis_config()
+        .consensus_config
+        .epoch_block_count;
+
+    let boundary = service_ref.get_next_epoch_boundary().await?;
+    let epoch_info = service_ref.epoch_info().await?;
+    assert_eq!(boundary, epoch_info.end_block_number());
+    assert_eq!(
+        boundary,
+        epoch_info.start_block_number() + epoch_block_count
+    );
+
+    main.produce_and_apply_times(boundary)?;
+    let executed_block = ExecutedBlock::new(
+        main.head().head_block(),
+        main.head().status().info().clone(),
+    );
+    bus.broadcast(NewHeadBlock(Arc::new(executed_block)))?;
+    delay_for(Duration::from_millis(200)).await;
+
+    let epoch_info = service_ref.epoch_info().await?;
+    assert_eq!(epoch_info.start_block_number(), boundary);
+    assert_eq!(
+        service_ref.get_next_epoch_boundary().await?,
+        boundary + epoch_block_count
+    );
+    Ok(())
+}
+
+#[stest::test]
+async fn test_chain_request_histogram() -> Result<()> {
+    let (_registry, service_ref, _, _) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+
+    let header_name = ChainRequest::CurrentHeader().name();
+    let schedule_name = ChainRequest::GetRewardSchedule().name();
+    assert_ne!(header_name, schedule_name);
+    let sample_count = |name: &str| {
+        CHAIN_REQUEST_HISTOGRAM
+            .with_label_values(&[name])
+            .get_sample_count()
+    };
+    let header_count = sample_count(header_name);
+    let schedule_count = sample_count(schedule_name);
+    for _ in 0..3 {
+        service_ref.main_head_header().await?;
+    }
+    service_ref.get_reward_schedule().await?;
+    // other tests may issue requests concurrently, so only the lower bounds are checked.
+    assert!(sample_count(header_name) >= header_count + 3);
+    assert!(sample_count(schedule_name) >= schedule_count + 1);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_main_blocks_by_range() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.storage.block_range_max_count = Some(2);
+    let (config, storage, mut main) = init_mock_chain(config)?;
+    main.produce_and_apply_times(3)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    // the count is clamped to the configured max.
+    let numbers = |blocks: Vec<Block>| -> Vec<BlockNumber> {
+        blocks.iter().map(|block| block.header().number()).collect()
+    };
+    assert_eq!(
+        numbers(service_ref.main_blocks_by_range(0, 10).await?),
+        vec![0, 1]
+    );
+    // the range stops at the head.
+    assert_eq!(
+        numbers(service_ref.main_blocks_by_range(2, 2).await?),
+        vec![2, 3]
+    );
+    assert_eq!(
+        numbers(service_ref.main_blocks_by_range(3, 2).await?),
+        vec![3]
+    );
+    assert!(service_ref.main_blocks_by_range(4, 2).await?.is_empty());
+    assert!(service_ref.main_blocks_by_range(1, 0).await?.is_empty());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_block_info_by_hash() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    main.produce_and_apply_times(3)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let head = main.head().current_header();
+    let block_info = service_ref
+        .get_block_info_by_hash(&head.id())
+        .await?
+        .expect("block info should exist.");
+    assert_eq!(block_info.block_id, head.id());
+    assert_eq!(
+        block_info.txn_accumulator_info.accumulator_root,
+        main.head().get_txn_accumulator().root_hash()
+    );
+    assert_eq!(
+        block_info.block_accumulator_info.accumulator_root,
+        main.head().get_block_accumulator().root_hash()
+    );
+    assert_eq!(
+        block_info.total_difficulty,
+        main.head().status().info().total_difficulty
+    );
+    for number in 1..=head.number() {
+        let header = main
+            .head()
+            .get_header_by_number(number)?
+            .expect("header should exist.");
+        let block_info = service_ref
+            .get_block_info_by_hash(&header.id())
+            .await?
+            .expect("block info should exist.");
+        assert_eq!(
+            block_info.txn_accumulator_info.accumulator_root,
+            header.txn_accumulator_root()
+        );
+    }
+    assert!(service_ref
+        .get_block_info_by_hash(&HashValue::random())
+        .await?
+        .is_none());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_blocks_by_author() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let author_a = AccountAddress::random();
+    let author_b = AccountAddress::random();
+    let mut blocks_of_a = vec![];
+    for author in vec![author_a, author_b, author_a, author_a] {
+        let (template, _) =
+            main.head()
+                .create_block_template(author, None, vec![], vec![], None)?;
+        let block = main
+            .head()
+            .consensus()
+            .create_block(template, config.net().time_service().as_ref())?;
+        if author == author_a {
+            blocks_of_a.push(block.id());
+        } else {
+            // a block of author a on another branch, which is skipped when paging the index.
+            let (template, _) =
+                main.head()
+                    .create_block_template(author_a, None, vec![], vec![], None)?;
+            let fork_block = main
+                .head()
+                .consensus()
+                .create_block(template, config.net().time_service().as_ref())?;
+            storage.commit_block(fork_block)?;
+        }
+        main.apply(block)?;
+    }
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let ids =
+        |blocks: Vec<Block>| -> Vec<HashValue> { blocks.iter().map(|block| block.id()).collect() };
+    blocks_of_a.reverse();
+    assert_eq!(
+        ids(service_ref.get_blocks_by_author(author_a, 10).await?),
+        blocks_of_a
+    );
+    assert_eq!(
+        ids(service_ref.get_blocks_by_author(author_a, 2).await?),
+        blocks_of_a[..2].to_vec()
+    );
+    assert_eq!(
+        ids(service_ref.get_blocks_by_author(author_a, 3).await?),
+        blocks_of_a
+    );
+    let blocks_of_b = service_ref.get_blocks_by_author(author_b, 10).await?;
+    assert_eq!(blocks_of_b.len(), 1);
+    assert_eq!(blocks_of_b[0].header().author(), author_b);
+    assert!(service_ref
+        .get_blocks_by_author(AccountAddress::random(), 10)
+        .await?
+        .is_empty());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_recover_startup_info() -> Result<()> {
+    let (config, storage, instance, mut main) =
+        init_mock_chain_with_instance(NodeConfig::random_for_test())?;
+    main.produce_and_apply_times(3)?;
+    let head = main.head().current_header();
+    let last_good = main
+        .head()
+        .get_header_by_number(head.number() - 1)?
+        .expect("header should exist.");
+    // the head header is kept, but its block is lost.
+    delete_block_body(&instance, head.id())?;
+    storage.save_startup_info(StartupInfo::new(head.id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+    assert_eq!(service_ref.main_head_header().await?.id(), last_good.id());
+    assert_eq!(
+        storage.get_startup_info()?.map(|info| info.main),
+        Some(last_good.id())
+    );
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_block_size_breakdown() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let txn = test_helper::txn::create_account(config.net(), 0, 1)
+        .pop()
+        .map(|(_, txn)| txn)
+        .expect("txn should exist.");
+    let (template, _) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![txn],
+        vec![],
+        None,
+    )?;
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, config.net().time_service().as_ref())?;
+    main.apply(block.clone())?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let breakdown = service_ref.get_block_size_breakdown(block.id()).await?;
+    assert!(breakdown.body > 0);
+    assert_eq!(breakdown.header + breakdown.body, breakdown.total);
+    assert_eq!(breakdown.total, bcs_ext::to_bytes(&block)?.len() as u64);
+    assert!(service_ref
+        .get_block_size_breakdown(HashValue::random())
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_blocks_by_hash_batch() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let chain_info = main.chain_info();
+    main.produce_and_apply_times(2)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let head_id = main.head().current_header().id();
+    let genesis_id = chain_info.head().id();
+    let unknown_id = HashValue::random();
+    let blocks = service_ref
+        .get_blocks_by_hash_batch(vec![head_id, unknown_id, genesis_id])
+        .await?;
+    assert_eq!(blocks.len(), 3);
+    assert_eq!(blocks[0].as_ref().map(|block| block.id()), Some(head_id));
+    assert!(blocks[1].is_none());
+    assert_eq!(blocks[2].as_ref().map(|block| block.id()), Some(genesis_id));
+
+    let oversized = vec![genesis_id; MAX_BLOCK_BATCH_SIZE as usize + 1];
+    assert!(service_ref
+        .get_blocks_by_hash_batch(oversized)
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[derive(Default)]
+struct ChainStatsCollector {
+    stats: Vec<ChainStats>,
+}
+
+impl ActorService for ChainStatsCollector {}
+
+impl EventHandler<Self, ChainStatsEvent> for ChainStatsCollector {
+    fn handle_event(&mut self, event: ChainStatsEvent, _ctx: &mut ServiceContext<Self>) {
+        self.stats.push(event.stats);
+    }
+}
+
+#[derive(Debug)]
+struct GetCollectedStats;
+
+impl ServiceRequest for GetCollectedStats {
+    type Response = Vec<ChainStats>;
+}
+
+impl ServiceHandler<Self, GetCollectedStats> for ChainStatsCollector {
+    fn handle(
+        &mut self,
+        _msg: GetCollectedStats,
+        _ctx: &mut ServiceContext<Self>,
+    ) -> Vec<ChainStats> {
+        self.stats.clone()
+    }
+}
+
+#[stest::test]
+async fn test_subscribe_chain_stats() -> Result<()> {
+    let (registry, service_ref, _, _) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let collector = registry.register::<ChainStatsCollector>().await?;
+    assert!(service_ref
+        .subscribe_chain_stats(0, collector.clone().event_notifier())
+        .await
+        .is_err());
+    service_ref
+        .subscribe_chain_stats(1, collector.clone().event_notifier())
+        .await?;
+    // the first snapshot is pushed at once, and the second after one interval.
+    delay_for(Duration::from_millis(1500)).await;
+
+    let collected_stats = collector.send(GetCollectedStats).await?;
+    assert!(collected_stats.len() >= 2);
+    let expect_stats = service_ref.get_chain_stats().await?;
+    assert!(collected_stats.iter().all(|stats| *stats == expect_stats));
+    Ok(())
+}
+
+#[stest::test]
+async fn test_verify_header_proof() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    main.produce_and_apply_times(5)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let header = main
+        .head()
+        .get_header_by_number(3)?
+        .expect("header should exist.");
+    let proof = main
+        .head()
+        .get_block_accumulator()
+        .get_proof(header.number())?
+        .expect("proof should exist.");
+    assert!(
+        service_ref
+            .verify_header_proof(header.clone(), proof.clone())
+            .await?
+    );
+
+    let mut siblings = proof.siblings().to_vec();
+    siblings[0] = HashValue::random();
+    assert!(
+        !service_ref
+            .verify_header_proof(header.clone(), AccumulatorProof::new(siblings))
+            .await?
+    );
+    // the proof of another block does not prove the header.
+    let other_proof = main
+        .head()
+        .get_block_accumulator()
+        .get_proof(header.number().saturating_add(1))?
+        .expect("proof should exist.");
+    assert!(!service_ref.verify_header_proof(header, other_proof).await?);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_list_governance_proposals() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let (proposer, create_proposer_txn) = test_helper::txn::create_account(net, 0, 1)
+        .pop()
+        .expect("txn should exist.");
+    let propose_txn = proposer.create_signed_txn_with_args(
+        TransactionPayload::ScriptFunction(test_helper::dao::vote_reward_scripts(net, 10)),
+        0,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        net.time_service().now_secs() + 3600,
+        net.chain_id(),
+    );
+    for txns in vec![vec![create_proposer_txn], vec![propose_txn]] {
+        let (template, excluded) =
+            main.head()
+                .create_block_template(*main.miner().address(), None, txns, vec![], None)?;
+        assert!(excluded.discarded_txns.is_empty());
+        let block = main
+            .head()
+            .consensus()
+            .create_block(template, net.time_service().as_ref())?;
+        main.apply(block)?;
+    }
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let head_state = ChainStateDB::new(
+        storage.into_super_arc(),
+        Some(main.head().current_header().state_root()),
+    );
+    let dao_config = head_state
+        .get_on_chain_config::<DaoConfig>()?
+        .expect("DaoConfig should exist.");
+    let proposals = service_ref.list_governance_proposals().await?;
+    assert_eq!(proposals.len(), 1);
+    let proposal = &proposals[0];
+    assert_eq!(proposal.id, 0);
+    assert_eq!(proposal.proposer, *proposer.address());
+    // the voting has not begun at the block creating the proposal.
+    assert_eq!(proposal.state, ProposalState::Pending);
+    assert_eq!(
+        proposal.voting_deadline,
+        main.head().current_header().timestamp()
+            + dao_config.voting_delay
+            + dao_config.voting_period
+    );
+    Ok(())
+}
+
+#[stest::test]
+async fn test_check_sequence_usage() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let sender = association_address();
+    let confirmed_sequence_number = main
+        .head()
+        .chain_state_reader()
+        .get_account_resource(sender)?
+        .expect("association account should exist.")
+        .sequence_number();
+    let (_, confirmed_txn) = test_helper::txn::create_account(net, confirmed_sequence_number, 1)
+        .pop()
+        .expect("txn should exist.");
+    let (template, excluded) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![confirmed_txn],
+        vec![],
+        None,
+    )?;
+    assert!(excluded.discarded_txns.is_empty());
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    main.apply(block)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+    let txpool = registry.get_shared::<TxPoolService>().await?;
+    let pending_sequence_number = confirmed_sequence_number + 1;
+    let (_, pending_txn) = test_helper::txn::create_account(net, pending_sequence_number, 1)
+        .pop()
+        .expect("txn should exist.");
+    txpool.add_txns(vec![pending_txn]).pop().unwrap()?;
+
+    assert_eq!(
+        service_ref
+            .check_sequence_usage(sender, confirmed_sequence_number)
+            .await?,
+        SequenceUsage::AlreadyConfirmed
+    );
+    assert_eq!(
+        service_ref
+            .check_sequence_usage(sender, pending_sequence_number)
+            .await?,
+        SequenceUsage::PendingInPool
+    );
+    assert_eq!(
+        service_ref
+            .check_sequence_usage(sender, pending_sequence_number + 1)
+            .await?,
+        SequenceUsage::Free
+    );
+    assert_eq!(
+        service_ref
+            .check_sequence_usage(AccountAddress::random(), 0)
+            .await?,
+        SequenceUsage::Free
+    );
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_branches() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let chain_info = main.chain_info();
+    let genesis_id = chain_info.head().id();
+    main.produce_and_apply_times(3)?;
+    let mut branch = main.fork(Some(genesis_id))?;
+    branch.produce_and_apply()?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let branches = service_ref.get_branches().await?;
+    assert_eq!(
+        branches,
+        vec![
+            (
+                main.head().current_header().id(),
+                main.head().get_total_difficulty()?
+            ),
+            (
+                branch.head().current_header().id(),
+                branch.head().get_total_difficulty()?
+            ),
+        ]
+    );
+    Ok(())
+}
+
+#[stest::test]
+async fn test_create_block_template_with_txns() -> Result<()> {
+    let (_registry, service_ref, storage, config) =
+        start_chain_reader_service(NodeConfig::random_for_test()).await?;
+    let net = config.net();
+    let chain_info = storage.get_chain_info()?.expect("chain info should exist.");
+    let chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage.clone())?;
+    let sequence_number = chain
+        .chain_state_reader()
+        .get_account_resource(association_address())?
+        .expect("association account should exist.")
+        .sequence_number();
+
+    let txns: Vec<SignedUserTransaction> =
+        test_helper::txn::create_account(net, sequence_number, 2)
+            .into_iter()
+            .map(|(_, txn)| txn)
+            .collect();
+    let template = service_ref
+        .create_block_template_with_txns(txns.clone())
+        .await?;
+    assert_eq!(template.parent_hash, chain_info.head().id());
+    assert_eq!(template.body.transactions, txns);
+
+    // a gap or a duplicate in the sequence numbers of a sender.
+    assert!(service_ref
+        .create_block_template_with_txns(vec![txns[1].clone()])
+        .await
+        .is_err());
+    assert!(service_ref
+        .create_block_template_with_txns(vec![txns[0].clone(), txns[0].clone()])
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_account_balances() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let chain_info = main.chain_info();
+    let (voter, create_voter_txn) = test_helper::txn::create_account(net, 0, 1)
+        .pop()
+        .expect("txn should exist.");
+    let stake = 1000u128;
+    let sign_txn = |sequence_number, script_function| {
+        voter.create_signed_txn_with_args(
+            TransactionPayload::ScriptFunction(script_function),
+            sequence_number,
+            DEFAULT_MAX_GAS_AMOUNT,
+            1,
+            net.time_service().now_secs() + 3600,
+            net.chain_id(),
+        )
+    };
+    let propose_txn = sign_txn(0, test_helper::dao::vote_reward_scripts(net, 10));
+    let cast_vote_txn = sign_txn(
+        1,
+        ScriptFunction::new(
+            ModuleId::new(
+                core_code_address(),
+                Identifier::new("DaoVoteScripts").unwrap(),
+            ),
+            Identifier::new("cast_vote").unwrap(),
+            vec![
+                stc_type_tag(),
+                test_helper::dao::on_chain_config_type_tag(
+                    test_helper::dao::reward_config_type_tag(),
+                ),
+            ],
+            vec![
+                bcs_ext::to_bytes(voter.address()).unwrap(),
+                bcs_ext::to_bytes(&0u64).unwrap(),
+                bcs_ext::to_bytes(&true).unwrap(),
+                bcs_ext::to_bytes(&stake).unwrap(),
+            ],
+        ),
+    );
+    let dao_config = ChainStateDB::new(
+        storage.clone().into_super_arc(),
+        Some(chain_info.head().state_root()),
+    )
+    .get_on_chain_config::<DaoConfig>()?
+    .expect("DaoConfig should exist.");
+    let mut apply_txns = |txns| -> Result<()> {
+        let (template, excluded) =
+            main.head()
+                .create_block_template(*main.miner().address(), None, txns, vec![], None)?;
+        assert!(excluded.discarded_txns.is_empty());
+        let block = main
+            .head()
+            .consensus()
+            .create_block(template, net.time_service().as_ref())?;
+        main.apply(block)?;
+        Ok(())
+    };
+    apply_txns(vec![create_voter_txn])?;
+    apply_txns(vec![propose_txn])?;
+    // the voting begins after the voting delay.
+    net.time_service().sleep(dao_config.voting_delay + 1000);
+    apply_txns(vec![cast_vote_txn])?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let balances = service_ref.get_account_balances(*voter.address()).await?;
+    let liquid = main
+        .head()
+        .chain_state_reader()
+        .get_balance(*voter.address())?
+        .expect("voter balance should exist.");
+    assert_eq!(balances.liquid, liquid);
+    assert_eq!(balances.locked, stake);
+    assert_eq!(balances.total, liquid + stake);
+
+    let balances = service_ref
+        .get_account_balances(*main.miner().address())
+        .await?;
+    assert_eq!(balances.locked, 0);
+    assert_eq!(balances.total, balances.liquid);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_exists_block() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let chain_info = main.chain_info();
+    let genesis_id = chain_info.head().id();
+    main.produce_and_apply_times(2)?;
+    let mut branch = main.fork(Some(genesis_id))?;
+    let branch_block = branch.produce_and_apply()?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    assert!(service_ref.exists_block(genesis_id).await?);
+    assert!(
+        service_ref
+            .exists_block(main.head().current_header().id())
+            .await?
+    );
+    // a saved block of another branch exists too.
+    assert!(service_ref.exists_block(branch_block.id()).await?);
+    assert!(!service_ref.exists_block(HashValue::random()).await?);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_frozen_subtree_roots() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    main.produce_and_apply_times(6)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let accumulator_info = service_ref
+        .main_status()
+        .await?
+        .info()
+        .get_block_accumulator_info()
+        .clone();
+    let frozen_subtree_roots = service_ref.get_frozen_subtree_roots().await?;
+    // 7 leaves, the genesis and 6 blocks, freeze into subtrees of 4, 2 and 1 leaves.
+    assert_eq!(frozen_subtree_roots.len(), 3);
+    let accumulator = InMemoryAccumulator::new(frozen_subtree_roots, accumulator_info.num_leaves)?;
+    assert_eq!(accumulator.root_hash(), accumulator_info.accumulator_root);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_txn_index_in_block() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let txns: Vec<_> = test_helper::txn::create_account(net, 0, 3)
+        .into_iter()
+        .map(|(_, txn)| txn)
+        .collect();
+    let (template, _) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        txns.clone(),
+        vec![],
+        None,
+    )?;
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    assert_eq!(block.transactions().len(), 3);
+    let block_id = block.id();
+    main.apply(block)?;
+    storage.save_startup_info(StartupInfo::new(block_id))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    // the block metadata transaction comes first.
+    for (i, txn) in txns.iter().enumerate() {
+        let index = service_ref
+            .get_txn_index_in_block(txn.id())
+            .await?
+            .expect("txn index should exist.");
+        assert_eq!(index, i as u64 + 1);
+        let txn_info = service_ref
+            .get_txn_info_by_block_and_index(block_id, index)
+            .await?
+            .expect("txn info should exist.");
+        assert_eq!(txn_info.transaction_hash(), txn.id());
+    }
+
+    let (_, pending_txn) = test_helper::txn::create_account(net, 3, 1)
+        .pop()
+        .expect("txn should exist.");
+    assert!(service_ref
+        .get_txn_index_in_block(pending_txn.id())
+        .await?
+        .is_none());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_cumulative_difficulty() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    main.produce_and_apply_times(5)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let mut total_difficulty = U256::zero();
+    for number in 0..=5 {
+        let header = service_ref
+            .main_block_header_by_number(number)
+            .await?
+            .expect("header should exist.");
+        total_difficulty = total_difficulty + header.difficulty();
+        assert_eq!(
+            service_ref.get_cumulative_difficulty(number).await?,
+            Some(total_difficulty)
+        );
+    }
+    assert_eq!(
+        total_difficulty,
+        service_ref.main_status().await?.total_difficulty()
+    );
+    assert!(service_ref.get_cumulative_difficulty(6).await?.is_none());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_batch_verify_txn_signatures() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let (account, create_account_txn) = test_helper::txn::create_account(net, 0, 1)
+        .pop()
+        .expect("txn should exist.");
+    let (template, _) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![create_account_txn],
+        vec![],
+        None,
+    )?;
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    main.apply(block)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let expiration_timestamp_secs = net.time_service().now_secs() + 3600;
+    let sign_txn = |signer: &Account, sender: AccountAddress, sequence_number: u64| {
+        signer.create_signed_txn_impl(
+            sender,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            sequence_number,
+            DEFAULT_MAX_GAS_AMOUNT,
+            1,
+            expiration_timestamp_secs,
+            net.chain_id(),
+        )
+    };
+    let txn = sign_txn(&account, *account.address(), 0);
+    let tampered_txn = SignedUserTransaction::new(
+        sign_txn(&account, *account.address(), 1).raw_txn().clone(),
+        txn.authenticator(),
+    );
+    let other_signer_txn = sign_txn(&Account::new(), *account.address(), 0);
+    // the sender account does not exist, signed by the key the address derived from.
+    let new_account = Account::new();
+    let new_account_txn = sign_txn(&new_account, *new_account.address(), 0);
+
+    let results = service_ref
+        .batch_verify_txn_signatures(vec![
+            txn.clone(),
+            tampered_txn,
+            new_account_txn,
+            other_signer_txn,
+            txn,
+        ])
+        .await?;
+    assert_eq!(results, vec![true, false, true, false, true]);
+    assert!(service_ref
+        .batch_verify_txn_signatures(vec![])
+        .await?
+        .is_empty());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_cache_stats() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    main.produce_and_apply_times(2)?;
+    let head_id = main.head().current_header().id();
+    storage.save_startup_info(StartupInfo::new(head_id))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let stats = service_ref.get_cache_stats().await?;
+    assert!(stats.block_cache.entries > 0);
+    assert!(stats.state_cache.entries > 0);
+
+    assert!(service_ref
+        .get_block_by_hash(HashValue::random())
+        .await?
+        .is_none());
+    let after_miss = service_ref.get_cache_stats().await?;
+    assert!(after_miss.block_cache.misses > stats.block_cache.misses);
+
+    // warm up the block cache.
+    assert!(service_ref.get_block_by_hash(head_id).await?.is_some());
+    let after_hit = service_ref.get_cache_stats().await?;
+    assert!(after_hit.block_cache.hits > after_miss.block_cache.hits);
+
+    service_ref.get_reward_schedule().await?;
+    service_ref.get_reward_schedule().await?;
+    let after_config = service_ref.get_cache_stats().await?;
+    assert!(after_config.config_cache.hits > after_hit.config_cache.hits);
+    assert!(after_config.config_cache.entries > 0);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_txn_dependencies() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+
+    let expiration_timestamp_secs = net.time_service().now_secs() + 3600;
+    let modules = compile_modules_with_address(
+        association_address(),
+        "module {{sender}}::M { public fun f() {} }
+         module {{sender}}::N { use {{sender}}::M; public(script) fun g() { M::f() } }",
+    );
+    let package_txn = create_signed_txn_with_association_account(
+        TransactionPayload::Package(Package::new_with_modules(modules)?),
+        0,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        expiration_timestamp_secs,
+        net,
+    );
+    let (template, excluded) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![package_txn.clone()],
+        vec![],
+        None,
+    )?;
+    assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    main.apply(block)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let module_m = ModuleId::new(association_address(), Identifier::new("M")?);
+    let module_n = ModuleId::new(association_address(), Identifier::new("N")?);
+    let call_txn = create_signed_txn_with_association_account(
+        TransactionPayload::ScriptFunction(ScriptFunction::new(
+            module_n.clone(),
+            Identifier::new("g")?,
+            vec![],
+            vec![],
+        )),
+        1,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        expiration_timestamp_secs,
+        net,
+    );
+    // the published module and its dependency are resolved at the head state.
+    assert_eq!(
+        service_ref.get_txn_dependencies(call_txn).await?,
+        vec![module_m, module_n]
+    );
+    // the package depends on none but its own modules.
+    assert!(service_ref
+        .get_txn_dependencies(package_txn)
+        .await?
+        .is_empty());
+
+    let script = Script::new(
+        compile_script(
+            "script { use 0x1::Account; use 0x1::Signer; use 0x1::STC::STC;
+             fun main(account: signer) { let _ = Account::balance<STC>(Signer::address_of(&account)); } }",
+        ),
+        vec![],
+        vec![],
+    );
+    let script_txn = create_signed_txn_with_association_account(
+        TransactionPayload::Script(script),
+        1,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        expiration_timestamp_secs,
+        net,
+    );
+    let dependencies = service_ref.get_txn_dependencies(script_txn).await?;
+    // Token is a transitive dependency through Account.
+    for name in &["Account", "Signer", "STC", "Token"] {
+        let module_id = ModuleId::new(core_code_address(), Identifier::new(*name)?);
+        assert!(dependencies.contains(&module_id), "{} is missing.", name);
+    }
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_block_propagation_delay() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+    let (registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+    let bus = registry.service_ref::<BusService>().await?;
+
+    main.produce_and_apply_times(1)?;
+    let block = main.head().head_block();
+    // the block reaches the local node 3 seconds after its timestamp on the mock clock.
+    net.time_service().sleep(3000);
+    bus.broadcast(BlockReceived {
+        block_id: block.id(),
+        received_at: net.time_service().now_millis(),
+    })?;
+    delay_for(Duration::from_millis(200)).await;
+
+    assert_eq!(
+        service_ref.get_block_propagation_delay(block.id()).await?,
+        3
+    );
+    assert!(service_ref
+        .get_block_propagation_delay(HashValue::random())
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_account_tokens() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let net = config.net();
+
+    let mint_txn = create_signed_txn_with_association_account(
+        TransactionPayload::ScriptFunction(ScriptFunction::new(
+            ModuleId::new(core_code_address(), Identifier::new("DummyTokenScripts")?),
+            Identifier::new("mint")?,
+            vec![],
+            vec![bcs_ext::to_bytes(&100u128)?],
+        )),
+        0,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        net.time_service().now_secs() + 3600,
+        net,
+    );
+    let (template, excluded) = main.head().create_block_template(
+        *main.miner().address(),
+        None,
+        vec![mint_txn],
+        vec![],
+        None,
+    )?;
+    assert!(excluded.discarded_txns.is_empty(), "txn is discarded.");
+    let block = main
+        .head()
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    main.apply(block)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+    let stc_balance = main
+        .head()
+        .chain_state_reader()
+        .get_balance(association_address())?
+        .expect("stc balance should exist.");
+
+    let (_registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+
+    let mut tokens = service_ref
+        .get_account_tokens(association_address())
+        .await?;
+    tokens.sort_by(|(a, _), (b, _)| a.to_string().cmp(&b.to_string()));
+    assert_eq!(
+        tokens,
+        vec![
+            (TokenCode::from_str("0x1::DummyToken::DummyToken")?, 100),
+            (STC_TOKEN_CODE.clone(), stc_balance),
+        ]
+    );
+    assert!(service_ref
+        .get_account_tokens(AccountAddress::random())
+        .await?
+        .is_empty());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_will_fit_next_block() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    // the next block has room for a single txn of the max gas amount.
+    config.miner.block_gas_limit = Some(DEFAULT_MAX_GAS_AMOUNT);
+    let (registry, service_ref, _, config) = start_chain_reader_service(config).await?;
+    let net = config.net();
+    let txpool = registry.get_shared::<TxPoolService>().await?;
+
+    let expiration_timestamp_secs = net.time_service().now_secs() + 3600;
+    let transfer_txn = |sequence_number, gas_unit_price| {
+        create_signed_txn_with_association_account(
+            TransactionPayload::ScriptFunction(encode_transfer_script_function(
+                AccountAddress::random(),
+                1000,
+            )),
+            sequence_number,
+            DEFAULT_MAX_GAS_AMOUNT,
+            gas_unit_price,
+            expiration_timestamp_secs,
+            net,
+        )
+    };
+    let cheap_txn = transfer_txn(3, 1);
+    // the pool is empty.
+    assert!(service_ref.will_fit_next_block(cheap_txn.clone()).await?);
+
+    // the pending txns of a higher gas price take the whole gas budget of the next block.
+    let txns: Vec<_> = (0..3).map(|i| transfer_txn(i, 10)).collect();
+    for result in txpool.add_txns(txns) {
+        result?;
+    }
+    assert!(!service_ref.will_fit_next_block(cheap_txn).await?);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_get_recent_reorgs() -> Result<()> {
+    let (config, storage, mut main) = init_mock_chain(NodeConfig::random_for_test())?;
+    let genesis_id = main.chain_info().head().id();
+    main.produce_and_apply_times(2)?;
+    let fork_point = main
+        .head()
+        .get_hash_by_number(1)?
+        .expect("block should exist.");
+    // a branch forked from the block 1, and a longer one forked from the genesis.
+    let mut branch_a = main.fork(Some(fork_point))?;
+    branch_a.produce_and_apply_times(2)?;
+    let mut branch_b = main.fork(Some(genesis_id))?;
+    branch_b.produce_and_apply_times(4)?;
+    storage.save_startup_info(StartupInfo::new(main.head().current_header().id()))?;
+
+    let (registry, service_ref) = launch_chain_reader_service(&config, &storage).await?;
+    let bus = registry.service_ref::<BusService>().await?;
+    assert!(service_ref.get_recent_reorgs(10).await?.is_empty());
+
+    // the block connector emits the reorg after the new head.
+    let mut old_head = main.head();
+    for (branch, fork_number) in &[(&branch_a, 1), (&branch_b, 0)] {
+        let new_head = branch.head();
+        let fork_number: u64 = *fork_number;
+        let block_ids = |chain: &BlockChain| -> Result<Vec<HashValue>> {
+            (fork_number + 1..=chain.current_header().number())
+                .map(|number| {
+                    Ok(chain
+                        .get_hash_by_number(number)?
+                        .expect("block should exist."))
+                })
+                .collect()
+        };
+        let reorg = ChainReorg {
+            from: old_head.current_header().id(),
+            to: new_head.current_header().id(),
+            retracted: block_ids(old_head)?,
+            applied: block_ids(new_head)?,
+        };
+        let executed_block =
+            ExecutedBlock::new(new_head.head_block(), new_head.status().info().clone());
+        bus.broadcast(NewHeadBlock(Arc::new(executed_block)))?;
+        bus.broadcast(reorg)?;
+        delay_for(Duration::from_millis(200)).await;
+        old_head = new_head;
+    }
+
+    let reorgs = service_ref.get_recent_reorgs(10).await?;
+    assert_eq!(reorgs.len(), 2);
+    // the head 3 of the branch a is switched to the branch b forked from the genesis.
+    assert_eq!(reorgs[0].depth, 3);
+    assert_eq!(reorgs[0].common_ancestor, genesis_id);
+    // the head 2 of the main is switched to the branch a forked from the block 1.
+    assert_eq!(reorgs[1].depth, 1);
+    assert_eq!(reorgs[1].common_ancestor, fork_point);
+    assert_eq!(
+        service_ref.get_recent_reorgs(1).await?,
+        reorgs[..1].to_vec()
+    );
+    Ok(())
+}
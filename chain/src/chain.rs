@@ -1,8 +1,8 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::verifier::{BlockVerifier, FullVerifier};
-use anyhow::{ensure, format_err, Result};
+use crate::verifier::{BasicVerifier, BlockVerifier, FullVerifier};
+use anyhow::{bail, ensure, format_err, Result};
 use consensus::Consensus;
 use crypto::HashValue;
 use logger::prelude::*;
@@ -11,27 +11,38 @@ use starcoin_accumulator::{
     accumulator_info::AccumulatorInfo, node::AccumulatorStoreType, Accumulator, MerkleAccumulator,
 };
 use starcoin_chain_api::{
-    verify_block, ChainReader, ChainWriter, ConnectBlockError, ExcludedTxns, ExecutedBlock,
-    MintedUncleNumber, VerifiedBlock, VerifyBlockField,
+    verify_block, BlockTxnMultiProof, ChainReader, ChainWriter, ConnectBlockError, ExcludedTxns,
+    ExecutedBlock, MintedUncleNumber, SimulateResult, StateRepairResult, TemplateValidation,
+    TxnMultiProof, VerifiedBlock, VerifyBlockField,
 };
+use starcoin_executor::BlockExecutedData;
 use starcoin_open_block::OpenedBlock;
-use starcoin_state_api::{AccountStateReader, ChainState, ChainStateReader, ChainStateWriter};
+use starcoin_state_api::{
+    AccountStateReader, ChainState, ChainStateReader, ChainStateWriter, StateReaderExt, StateView,
+};
 use starcoin_statedb::ChainStateDB;
 use starcoin_types::block::BlockIdAndNumber;
 use starcoin_types::contract_event::ContractEventInfo;
 use starcoin_types::filter::Filter;
-use starcoin_types::startup_info::{ChainInfo, ChainStatus};
+use starcoin_types::startup_info::{ChainInfo, ChainStats, ChainStatus};
+use starcoin_types::state_set::ChainStateSet;
 use starcoin_types::transaction::BlockTransactionInfo;
 use starcoin_types::{
+    access_path::AccessPath,
     account_address::AccountAddress,
-    block::{Block, BlockHeader, BlockInfo, BlockNumber, BlockTemplate},
+    block::{Block, BlockHeader, BlockHeaderExtra, BlockInfo, BlockNumber, BlockTemplate},
     contract_event::ContractEvent,
     error::BlockExecutorError,
-    transaction::{SignedUserTransaction, Transaction, TransactionInfo},
+    transaction::{
+        SignedUserTransaction, Transaction, TransactionInfo, TransactionOutput, TransactionStatus,
+    },
+    write_set::{WriteOp, WriteSet, WriteSetMut},
     U256,
 };
 use starcoin_vm_types::account_config::genesis_address;
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::on_chain_config::OnChainConfig;
 use starcoin_vm_types::on_chain_resource::{Epoch, EpochData, EpochInfo, GlobalTimeOnChain};
 use starcoin_vm_types::time::TimeService;
 use std::cmp::min;
@@ -305,6 +316,10 @@ impl BlockChain {
         self.storage.clone()
     }
 
+    pub fn get_statedb(&self) -> &ChainStateDB {
+        &self.statedb
+    }
+
     pub fn can_be_uncle(&self, block_header: &BlockHeader) -> Result<bool> {
         FullVerifier::can_be_uncle(self, block_header)
     }
@@ -406,7 +421,10 @@ impl BlockChain {
         verify_block!(
             VerifyBlockField::State,
             executed_accumulator_root == header.txn_accumulator_root(),
-            "verify block: txn accumulator root mismatch"
+            "verify block:{:?} txn accumulator root mismatch, expect: {:?}, executed: {:?}",
+            block_id,
+            header.txn_accumulator_root(),
+            executed_accumulator_root,
         );
 
         watch(CHAIN_WATCH_NAME, "n23");
@@ -459,6 +477,8 @@ impl BlockChain {
         txn_infos: (Vec<TransactionInfo>, Vec<Vec<ContractEvent>>),
     ) -> Result<()> {
         let block_id = block.id();
+        let block_header = block.header().clone();
+        let total_difficulty = block_info.get_total_difficulty();
         let (txn_infos, txn_events) = txn_infos;
         debug_assert!(
             transactions.len() == txn_infos.len(),
@@ -490,6 +510,7 @@ impl BlockChain {
         storage.save_transaction_batch(transactions)?;
         storage.commit_block(block)?;
         storage.save_block_info(block_info)?;
+        storage.save_branch_head(&block_header, total_difficulty)?;
         Ok(())
     }
 
@@ -620,6 +641,10 @@ impl ChainReader for BlockChain {
         Ok(false)
     }
 
+    fn has_block_in_storage(&self, block_id: HashValue) -> Result<bool> {
+        self.storage.contains_block(block_id)
+    }
+
     fn epoch_info(&self) -> Result<EpochInfo> {
         self.get_epoch_info_by_number(None)
     }
@@ -864,6 +889,553 @@ impl BlockChain {
         }
         Ok(event_with_infos)
     }
+
+    /// Re-execute the main chain blocks in [from, to] on a scratch state based on the parent of `from`,
+    /// return the header of the first block whose recomputed state root diverges from the stored one.
+    /// The scratch state is never flushed, so the storage is not changed.
+    pub fn replay(&self, from: BlockNumber, to: BlockNumber) -> Result<Option<BlockHeader>> {
+        // genesis block has no parent state, so start from block 1.
+        let from = from.max(1);
+        let to = to.min(self.current_header().number());
+        if from > to {
+            return Ok(None);
+        }
+        let parent_number = from.saturating_sub(1);
+        let mut parent_header = self
+            .get_header_by_number(parent_number)?
+            .ok_or_else(|| format_err!("Can not find block header by number {}", parent_number))?;
+        let statedb = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(parent_header.state_root()),
+        );
+        for number in from..=to {
+            let block = self
+                .get_block_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+            let header = block.header().clone();
+            let executed_data =
+                match Self::execute_block_on(&statedb, &block, parent_header.gas_used()) {
+                    Ok(executed_data) => executed_data,
+                    Err(e) => {
+                        warn!("Replay block {:?} failed: {:?}", header.id(), e);
+                        return Ok(Some(header));
+                    }
+                };
+            let stored_roots: Vec<HashValue> = self
+                .storage
+                .get_block_transaction_infos(header.id())?
+                .iter()
+                .map(|info| info.state_root_hash())
+                .collect();
+            let replayed_roots: Vec<HashValue> = executed_data
+                .txn_infos
+                .iter()
+                .map(|info| info.state_root_hash())
+                .collect();
+            if executed_data.state_root != header.state_root() || replayed_roots != stored_roots {
+                warn!(
+                    "Replay block {:?}(number: {}) state root diverged, stored: {:?}, replayed: {:?}",
+                    header.id(),
+                    number,
+                    header.state_root(),
+                    executed_data.state_root
+                );
+                return Ok(Some(header));
+            }
+            parent_header = header;
+        }
+        Ok(None)
+    }
+
+    /// Execute the block on the given state without flush, the state must be the block's parent state.
+    fn execute_block_on(
+        statedb: &ChainStateDB,
+        block: &Block,
+        parent_gas_used: u64,
+    ) -> Result<BlockExecutedData> {
+        let epoch = get_epoch_from_statedb(statedb)?;
+        let mut txns = vec![Transaction::BlockMetadata(
+            block.to_metadata(parent_gas_used),
+        )];
+        txns.extend(
+            block
+                .transactions()
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction),
+        );
+        Ok(starcoin_executor::block_execute(
+            statedb,
+            txns,
+            epoch.block_gas_limit(),
+        )?)
+    }
+
+    /// Check the code and resource trees of the `address` account at the `state_root` are intact,
+    /// the trees rebuilt from the read back state set must reach the stored roots.
+    fn is_account_subtree_intact(&self, state_root: HashValue, address: AccountAddress) -> bool {
+        let stored = ChainStateDB::new(self.storage.clone().into_super_arc(), Some(state_root));
+        match (
+            stored.get_account_state(&address),
+            stored.get_account_state_set(&address),
+        ) {
+            (Ok(Some(account_state)), Ok(Some(state_set))) => {
+                let rebuilt = ChainStateDB::mock();
+                rebuilt
+                    .apply(ChainStateSet::new(vec![(address, state_set)]))
+                    .is_ok()
+                    && rebuilt.get_account_state(&address).ok().flatten() == Some(account_state)
+            }
+            (Ok(None), Ok(None)) => true,
+            _ => false,
+        }
+    }
+
+    /// Find the nearest ancestor of the `header`, the `header` included, whose trees of the
+    /// `address` account are intact.
+    fn find_intact_ancestor(
+        &self,
+        header: &BlockHeader,
+        address: AccountAddress,
+    ) -> Result<Option<BlockHeader>> {
+        let mut current = header.clone();
+        loop {
+            if self.is_account_subtree_intact(current.state_root(), address) {
+                return Ok(Some(current));
+            }
+            if current.number() == 0 {
+                return Ok(None);
+            }
+            current = self.get_header(current.parent_hash())?.ok_or_else(|| {
+                format_err!(
+                    "Can not find block header by hash {}",
+                    current.parent_hash()
+                )
+            })?;
+        }
+    }
+
+    /// Repair the code and resource trees of the `address` account at the chain head.
+    /// State nodes are content addressed, so older states may share the corrupted node. The nearest
+    /// ancestor whose account trees are intact is the checkpoint, the main chain is replayed from
+    /// its stored state without flush, and the account's trees are rewritten from the replayed
+    /// state if the stored ones diverge. The genesis is executed again when no stored state is intact.
+    pub fn repair_state_subtree(&self, address: AccountAddress) -> Result<StateRepairResult> {
+        let head = self.current_header();
+        let (replayed, mut parent_header) = match self.find_intact_ancestor(&head, address)? {
+            Some(checkpoint) => (
+                ChainStateDB::new(
+                    self.storage.clone().into_super_arc(),
+                    Some(checkpoint.state_root()),
+                ),
+                checkpoint,
+            ),
+            None => {
+                let genesis = self
+                    .get_block_by_number(0)?
+                    .ok_or_else(|| format_err!("Can not find genesis block"))?;
+                let replayed = ChainStateDB::mock();
+                let genesis_txns = genesis
+                    .transactions()
+                    .iter()
+                    .cloned()
+                    .map(Transaction::UserTransaction)
+                    .collect();
+                for output in starcoin_executor::execute_transactions(&replayed, genesis_txns)? {
+                    let (write_set, _, _, _) = output.into_inner();
+                    replayed.apply_write_set(write_set)?;
+                }
+                replayed.commit()?;
+                if replayed.state_root() != genesis.header().state_root() {
+                    return Ok(StateRepairResult::Diverged {
+                        block: genesis.header().clone(),
+                        replayed_state_root: replayed.state_root(),
+                    });
+                }
+                (replayed, genesis.header().clone())
+            }
+        };
+        debug!(
+            "Repair state subtree of account {} from block {}",
+            address,
+            parent_header.number()
+        );
+
+        for number in parent_header.number().saturating_add(1)..=head.number() {
+            let block = self
+                .get_block_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+            let executed_data =
+                Self::execute_block_on(&replayed, &block, parent_header.gas_used())?;
+            if executed_data.state_root != block.header().state_root() {
+                return Ok(StateRepairResult::Diverged {
+                    block: block.header().clone(),
+                    replayed_state_root: executed_data.state_root,
+                });
+            }
+            parent_header = block.header().clone();
+        }
+
+        let expected = replayed
+            .get_account_state_set(&address)?
+            .ok_or_else(|| format_err!("Account {} does not exist at chain head", address))?;
+        // read with a new statedb to bypass the account cache.
+        let read_stored = || {
+            ChainStateDB::new(
+                self.storage.clone().into_super_arc(),
+                Some(head.state_root()),
+            )
+            .get_account_state_set(&address)
+        };
+        if let Ok(Some(stored)) = read_stored() {
+            if stored == expected {
+                return Ok(StateRepairResult::Consistent);
+            }
+        }
+        // rebuild the account trees from scratch, the rebuilt nodes overwrite the corrupted ones.
+        ChainStateDB::new(self.storage.clone().into_super_arc(), None)
+            .apply(ChainStateSet::new(vec![(address, expected.clone())]))?;
+        match read_stored() {
+            Ok(Some(stored)) if stored == expected => {
+                info!("Repaired state subtree of account {}", address);
+                Ok(StateRepairResult::Repaired)
+            }
+            _ => bail!(
+                "Repair state subtree of account {} failed, the global state tree may be corrupted",
+                address
+            ),
+        }
+    }
+
+    /// Get the on chain config at the chain head.
+    pub fn get_on_chain_config<C>(&self) -> Result<Option<C>>
+    where
+        C: OnChainConfig,
+    {
+        AccountStateReader::new(&self.statedb).get_on_chain_config::<C>()
+    }
+
+    /// Collect the statistics of the chain head, all fields are read from the same head.
+    pub fn chain_stats(&self) -> Result<ChainStats> {
+        let status = self.status();
+        let head = status.head();
+        let total_supply = self
+            .statedb
+            .get_stc_info()?
+            .map(|info| info.total_value)
+            .unwrap_or_default();
+        Ok(ChainStats {
+            head_number: head.number(),
+            total_txns: status.info().txn_accumulator_info.num_leaves,
+            total_difficulty: status.total_difficulty(),
+            current_difficulty: head.difficulty(),
+            total_supply,
+            median_block_time: self.median_block_time(self.epoch.block_difficulty_window())?,
+        })
+    }
+
+    /// The median of the timestamp intervals of the latest `window` blocks on the main chain.
+    pub fn median_block_time(&self, window: u64) -> Result<u64> {
+        let head_number = self.current_header().number();
+        let start = head_number.saturating_sub(window);
+        let mut timestamps = Vec::new();
+        for number in start..=head_number {
+            let header = self
+                .get_header_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find header by number {}", number))?;
+            timestamps.push(header.timestamp());
+        }
+        let mut intervals: Vec<u64> = timestamps
+            .windows(2)
+            .map(|pair| pair[1].saturating_sub(pair[0]))
+            .collect();
+        intervals.sort_unstable();
+        Ok(intervals
+            .len()
+            .checked_div(2)
+            .and_then(|mid| intervals.get(mid).copied())
+            .unwrap_or_default())
+    }
+
+    /// Apply the write set to a scratch copy of the head state, and collect the changed paths.
+    /// The state is committed in memory only, nothing is flushed to the storage.
+    pub fn simulate_write_set(&self, write_set: WriteSet) -> Result<SimulateResult> {
+        let statedb = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(self.current_header().state_root()),
+        );
+        let mut changed_paths = vec![];
+        for (access_path, write_op) in write_set.iter() {
+            let current = statedb.get(access_path)?;
+            let changed = match write_op {
+                WriteOp::Value(value) => current.as_ref() != Some(value),
+                WriteOp::Deletion => current.is_some(),
+            };
+            if changed {
+                changed_paths.push(access_path.clone());
+            }
+        }
+        statedb.apply_write_set(write_set)?;
+        let state_root = statedb.commit()?;
+        Ok(SimulateResult {
+            state_root,
+            changed_paths,
+        })
+    }
+
+    /// Build one multi proof for each block including some of the transactions, in the order the
+    /// blocks first appear. Every transaction must be included by the main chain.
+    pub fn get_txn_multiproof(&self, txn_hashes: Vec<HashValue>) -> Result<TxnMultiProof> {
+        let mut block_txn_infos: Vec<(HashValue, Vec<HashValue>)> = vec![];
+        for txn_hash in txn_hashes {
+            let txn_info = self
+                .get_transaction_info(txn_hash)?
+                .ok_or_else(|| format_err!("Can not find txn {} on the main chain", txn_hash))?;
+            let block_id = txn_info.block_id();
+            match block_txn_infos.iter_mut().find(|(id, _)| *id == block_id) {
+                Some((_, txn_info_ids)) => txn_info_ids.push(txn_info.id()),
+                None => block_txn_infos.push((block_id, vec![txn_info.id()])),
+            }
+        }
+
+        let mut blocks = vec![];
+        for (block_id, txn_info_ids) in block_txn_infos {
+            let block_info = self
+                .storage
+                .get_block_info(block_id)?
+                .ok_or_else(|| format_err!("Can not find block info by hash {:?}", block_id))?;
+            let block_txn_info_ids = self.storage.get_block_txn_info_ids(block_id)?;
+            let txn_accumulator_info = block_info.get_txn_accumulator_info().clone();
+            // the txn infos of the block are the last leaves of its txn accumulator.
+            let first_leaf_index = txn_accumulator_info
+                .get_num_leaves()
+                .checked_sub(block_txn_info_ids.len() as u64)
+                .ok_or_else(|| format_err!("Invalid txn accumulator of block {}", block_id))?;
+            let mut txn_infos = vec![];
+            for txn_info_id in txn_info_ids {
+                let position = block_txn_info_ids
+                    .iter()
+                    .position(|id| *id == txn_info_id)
+                    .ok_or_else(|| {
+                        format_err!(
+                            "Can not find txn info {} in block {}",
+                            txn_info_id,
+                            block_id
+                        )
+                    })?;
+                txn_infos.push((
+                    first_leaf_index.saturating_add(position as u64),
+                    txn_info_id,
+                ));
+            }
+            txn_infos.sort_by_key(|(index, _)| *index);
+            txn_infos.dedup();
+            let indexes: Vec<u64> = txn_infos.iter().map(|(index, _)| *index).collect();
+            let proof = info_2_accumulator(
+                txn_accumulator_info,
+                AccumulatorStoreType::Transaction,
+                self.storage.as_ref(),
+            )
+            .get_multi_proof(&indexes)?;
+            blocks.push(BlockTxnMultiProof {
+                block_id,
+                txn_infos,
+                proof,
+            });
+        }
+        Ok(TxnMultiProof { blocks })
+    }
+
+    /// Execute the transactions of a block built on the head against the head state without
+    /// saving anything, and return their outputs, the block metadata transaction first.
+    pub fn dry_run_block(&self, block: &Block) -> Result<Vec<TransactionOutput>> {
+        let head = self.current_header();
+        ensure!(
+            block.header().parent_hash() == head.id(),
+            "Block {} is not built on the head {}",
+            block.id(),
+            head.id()
+        );
+        let statedb = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(head.state_root()),
+        );
+        let mut txns = vec![Transaction::BlockMetadata(
+            block.to_metadata(head.gas_used()),
+        )];
+        txns.extend(
+            block
+                .transactions()
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction),
+        );
+        starcoin_executor::execute_transactions(&statedb, txns)
+    }
+
+    /// Verify the template is well formed on the head, then re-execute it without saving anything,
+    /// the state root, gas used and txn accumulator root declared by the template must be
+    /// recomputable. The consensus is not verified, as the template is not mined yet.
+    pub fn validate_block_template(&self, template: BlockTemplate) -> Result<TemplateValidation> {
+        let head = self.current_header();
+        let VerifiedBlock(block) = BasicVerifier::verify_block(
+            self,
+            template.into_block(0, BlockHeaderExtra::new([0u8; 4])),
+        )?;
+        let mut txns = vec![Transaction::BlockMetadata(
+            block.to_metadata(head.gas_used()),
+        )];
+        txns.extend(
+            block
+                .transactions()
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction),
+        );
+        let txn_count = txns.len();
+        let statedb = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(head.state_root()),
+        );
+        let executed_data =
+            starcoin_executor::block_execute(&statedb, txns, self.epoch.block_gas_limit())?;
+        ensure!(
+            executed_data.txn_infos.len() == txn_count,
+            "Only {} of the {} txns in the template are executed within the block gas limit",
+            executed_data.txn_infos.len(),
+            txn_count
+        );
+        let header = block.header();
+        ensure!(
+            executed_data.state_root == header.state_root(),
+            "Template state root mismatch, declared: {}, executed: {}",
+            header.state_root(),
+            executed_data.state_root
+        );
+        let gas_used = executed_data
+            .txn_infos
+            .iter()
+            .fold(0u64, |acc, info| acc.saturating_add(info.gas_used()));
+        ensure!(
+            gas_used == header.gas_used(),
+            "Template gas used mismatch, declared: {}, executed: {}",
+            header.gas_used(),
+            gas_used
+        );
+        // append to a fork of the txn accumulator, which is dropped without flushing.
+        let txn_accumulator = info_2_accumulator(
+            self.txn_accumulator.get_info(),
+            AccumulatorStoreType::Transaction,
+            self.storage.as_ref(),
+        );
+        let txn_info_hashes: Vec<_> = executed_data
+            .txn_infos
+            .iter()
+            .map(|info| info.id())
+            .collect();
+        let txn_accumulator_root = txn_accumulator.append(&txn_info_hashes)?;
+        ensure!(
+            txn_accumulator_root == header.txn_accumulator_root(),
+            "Template txn accumulator root mismatch, declared: {}, executed: {}",
+            header.txn_accumulator_root(),
+            txn_accumulator_root
+        );
+        Ok(TemplateValidation {
+            state_root: executed_data.state_root,
+            txn_accumulator_root,
+            block_accumulator_root: header.block_accumulator_root(),
+            gas_used,
+        })
+    }
+
+    /// Re-execute the block on its parent state without flush, and merge the write sets of the
+    /// kept txns in execution order. The genesis block has no parent state, so it is rejected.
+    pub fn get_block_write_set(&self, block_id: HashValue) -> Result<WriteSet> {
+        let block = self
+            .storage
+            .get_block_by_hash(block_id)?
+            .ok_or_else(|| format_err!("Can not find block by hash {:?}", block_id))?;
+        let parent_hash = block.header().parent_hash();
+        let parent_header = self
+            .storage
+            .get_block_header_by_hash(parent_hash)?
+            .ok_or_else(|| format_err!("Can not find parent block header {:?}", parent_hash))?;
+        let statedb = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(parent_header.state_root()),
+        );
+        let epoch = get_epoch_from_statedb(&statedb)?;
+        let mut txns = vec![Transaction::BlockMetadata(
+            block.to_metadata(parent_header.gas_used()),
+        )];
+        txns.extend(
+            block
+                .transactions()
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction),
+        );
+        let outputs =
+            starcoin_executor::execute_block_transactions(&statedb, txns, epoch.block_gas_limit())?;
+        // the later writes overwrite the earlier ones of the same access path when frozen.
+        let mut write_set = WriteSetMut::default();
+        for output in outputs
+            .iter()
+            .filter(|output| matches!(output.status(), TransactionStatus::Keep(_)))
+        {
+            for (access_path, write_op) in output.write_set().iter() {
+                write_set.push((access_path.clone(), write_op.clone()));
+            }
+        }
+        write_set.freeze()
+    }
+
+    /// Cheaply verify the stored data of the head: the head is indexed by the block accumulator,
+    /// its block info is consistent with the accumulators, and the head state root can prove
+    /// the on chain time resource, which only touches one path of the state tree.
+    pub fn verify_head_integrity(&self) -> Result<()> {
+        let head = self.current_header();
+        let indexed_id = self.block_accumulator.get_leaf(head.number())?;
+        ensure!(
+            indexed_id == Some(head.id()),
+            "Head block {} is not indexed at number {}, indexed: {:?}",
+            head.id(),
+            head.number(),
+            indexed_id
+        );
+        let block_info = self
+            .storage
+            .get_block_info(head.id())?
+            .ok_or_else(|| format_err!("Can not find block info of head block {}", head.id()))?;
+        ensure!(
+            block_info.block_accumulator_info == self.block_accumulator.get_info()
+                && block_info.txn_accumulator_info == self.txn_accumulator.get_info(),
+            "Block info of head block {} is inconsistent with the accumulators",
+            head.id()
+        );
+        let statedb = ChainStateDB::new(
+            self.storage.clone().into_super_arc(),
+            Some(head.state_root()),
+        );
+        let access_path =
+            AccessPath::resource_access_path(genesis_address(), GlobalTimeOnChain::struct_tag());
+        let state_with_proof = statedb.get_with_proof(&access_path)?;
+        state_with_proof
+            .proof
+            .verify(
+                head.state_root(),
+                access_path,
+                state_with_proof.state.as_deref(),
+            )
+            .map_err(|e| {
+                format_err!(
+                    "Verify state root {} of head block {} failed: {:?}",
+                    head.state_root(),
+                    head.id(),
+                    e
+                )
+            })
+    }
 }
 
 impl ChainWriter for BlockChain {
@@ -3,18 +3,23 @@
 
 use anyhow::Result;
 use consensus::Consensus;
-use crypto::{ed25519::Ed25519PrivateKey, Genesis, PrivateKey};
+use crypto::{ed25519::Ed25519PrivateKey, Genesis, HashValue, PrivateKey};
 use starcoin_account_api::AccountInfo;
 use starcoin_accumulator::Accumulator;
 use starcoin_chain::BlockChain;
 use starcoin_chain::{ChainReader, ChainWriter};
+use starcoin_chain_api::StateRepairResult;
 use starcoin_chain_mock::MockChain;
 use starcoin_config::NodeConfig;
 use starcoin_config::{BuiltinNetworkID, ChainNetwork};
 use starcoin_executor::{build_transfer_from_association, DEFAULT_EXPIRATION_TIME};
+use starcoin_state_api::ChainStateReader;
+use starcoin_statedb::ChainStateDB;
 use starcoin_types::account_address;
+use starcoin_types::account_config::association_address;
 use starcoin_types::block::{Block, BlockHeader};
 use starcoin_types::filter::Filter;
+use starcoin_types::transaction::{BlockTransactionInfo, TransactionInfo};
 use starcoin_vm_types::account_config::genesis_address;
 use starcoin_vm_types::event::EventKey;
 use std::sync::Arc;
@@ -472,3 +477,150 @@ fn test_get_blocks_by_number() -> Result<()> {
     assert_eq!(blocks.len(), 11);
     Ok(())
 }
+
+#[stest::test]
+fn test_replay_chain() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    mock_chain.produce_and_apply_times(5)?;
+    assert!(mock_chain.head().replay(0, 5)?.is_none());
+    assert!(mock_chain.head().replay(2, u64::max_value())?.is_none());
+    Ok(())
+}
+
+#[stest::test]
+fn test_replay_chain_with_tampered_root() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    mock_chain.produce_and_apply_times(5)?;
+    let storage = mock_chain.head().get_storage();
+    let tampered_header = mock_chain
+        .head()
+        .get_header_by_number(3)?
+        .expect("block 3 should exist.");
+    let tampered_infos: Vec<BlockTransactionInfo> = storage
+        .get_block_transaction_infos(tampered_header.id())?
+        .into_iter()
+        .map(|info| {
+            BlockTransactionInfo::new(
+                info.block_id(),
+                TransactionInfo::new(
+                    info.transaction_hash(),
+                    HashValue::random(),
+                    &[],
+                    info.gas_used(),
+                    info.status().clone(),
+                ),
+            )
+        })
+        .collect();
+    storage.save_block_txn_info_ids(
+        tampered_header.id(),
+        tampered_infos.iter().map(|info| info.id()).collect(),
+    )?;
+    storage.save_transaction_infos(tampered_infos)?;
+
+    let divergent = mock_chain.head().replay(1, 5)?;
+    assert_eq!(
+        divergent.map(|header| header.id()),
+        Some(tampered_header.id())
+    );
+    assert!(mock_chain.head().replay(1, 2)?.is_none());
+    Ok(())
+}
+
+#[stest::test]
+fn test_repair_state_subtree() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    mock_chain.produce_and_apply_times(3)?;
+    let storage = mock_chain.head().get_storage();
+    let state_root = mock_chain.head().current_header().state_root();
+    let read_state = || ChainStateDB::new(storage.clone().into_super_arc(), Some(state_root));
+
+    let corrupted_address = association_address();
+    let other_address = genesis_address();
+    let corrupted_set = read_state().get_account_state_set(&corrupted_address)?;
+    let other_set = read_state().get_account_state_set(&other_address)?;
+    assert_eq!(
+        mock_chain.head().repair_state_subtree(corrupted_address)?,
+        StateRepairResult::Consistent
+    );
+
+    // replace the root node of the account's resource tree with other account's node.
+    let corrupted_root = read_state()
+        .get_account_state(&corrupted_address)?
+        .expect("account should exist.")
+        .resource_root();
+    let other_root = read_state()
+        .get_account_state(&other_address)?
+        .expect("account should exist.")
+        .resource_root();
+    let other_node = storage.get(&other_root)?.expect("state node should exist.");
+    storage.put(corrupted_root, other_node)?;
+    assert_ne!(
+        read_state()
+            .get_account_state_set(&corrupted_address)
+            .ok()
+            .flatten(),
+        corrupted_set
+    );
+
+    assert_eq!(
+        mock_chain.head().repair_state_subtree(corrupted_address)?,
+        StateRepairResult::Repaired
+    );
+    assert_eq!(
+        read_state().get_account_state_set(&corrupted_address)?,
+        corrupted_set
+    );
+    assert_eq!(
+        read_state().get_account_state_set(&other_address)?,
+        other_set
+    );
+    assert_eq!(read_state().state_root(), state_root);
+    Ok(())
+}
+
+#[stest::test]
+fn test_block_txn_root() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    let block = mock_chain.produce()?;
+    let header = block.header().clone();
+    mock_chain.apply(block)?;
+    let block_info = mock_chain
+        .head()
+        .get_block_info(Some(header.id()))?
+        .expect("block info should exist.");
+    assert_eq!(
+        block_info.txn_accumulator_info.accumulator_root,
+        header.txn_accumulator_root()
+    );
+
+    let block = mock_chain.produce()?;
+    let header = block
+        .header()
+        .as_builder()
+        .with_accumulator_root(HashValue::random())
+        .build();
+    let tampered_block = Block::new(header, block.body.clone());
+    assert!(mock_chain.apply(tampered_block).is_err());
+    mock_chain.apply(block)?;
+    Ok(())
+}
+
+#[stest::test]
+fn test_verify_head_integrity() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+    mock_chain.produce_and_apply_times(3)?;
+    mock_chain.head().verify_head_integrity()?;
+
+    // replace the head state root node with another state node.
+    let storage = mock_chain.head().get_storage();
+    let state_root = mock_chain.head().current_header().state_root();
+    let other_root = ChainStateDB::new(storage.clone().into_super_arc(), Some(state_root))
+        .get_account_state(&genesis_address())?
+        .expect("account should exist.")
+        .resource_root();
+    let other_node = storage.get(&other_root)?.expect("state node should exist.");
+    storage.put(state_root, other_node)?;
+    assert!(mock_chain.head().verify_head_integrity().is_err());
+    Ok(())
+}
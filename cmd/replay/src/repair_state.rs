@@ -0,0 +1,67 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use starcoin_chain::BlockChain;
+use starcoin_config::RocksdbConfig;
+use starcoin_config::{BuiltinNetworkID, ChainNetwork};
+use starcoin_genesis::Genesis;
+use starcoin_storage::cache_storage::CacheStorage;
+use starcoin_storage::db_storage::DBStorage;
+use starcoin_storage::storage::StorageInstance;
+use starcoin_storage::Storage;
+use starcoin_vm_types::account_address::AccountAddress;
+use std::path::PathBuf;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "repair-state")]
+pub struct RepairStateOpt {
+    #[structopt(long, short = "n")]
+    /// Chain Network of the data dir.
+    pub net: Option<BuiltinNetworkID>,
+    #[structopt(short = "d", long, parse(from_os_str))]
+    /// Data dir of the stopped node.
+    pub data_dir: PathBuf,
+    #[structopt(short = "a", long)]
+    /// The account whose state subtree is repaired.
+    pub address: AccountAddress,
+}
+
+fn main() {
+    let _logger = starcoin_logger::init();
+    let opts = RepairStateOpt::from_args();
+
+    let network = match opts.net {
+        Some(network) => network,
+        None => BuiltinNetworkID::Proxima,
+    };
+    let net = ChainNetwork::new_builtin(network);
+
+    let db_storage = DBStorage::new(
+        opts.data_dir.join("starcoindb/db"),
+        RocksdbConfig::default(),
+    )
+    .unwrap();
+    let storage = Arc::new(
+        Storage::new(StorageInstance::new_cache_and_db_instance(
+            CacheStorage::new(),
+            db_storage,
+        ))
+        .unwrap(),
+    );
+    let (chain_info, _) =
+        Genesis::init_and_check_storage(&net, storage.clone(), opts.data_dir.as_ref())
+            .expect("init storage by genesis fail.");
+    let chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage)
+        .expect("create block chain should success.");
+    let result = chain
+        .repair_state_subtree(opts.address)
+        .expect("repair state subtree should success.");
+    println!(
+        "repair state subtree of account {} at block {}: {:?}",
+        opts.address,
+        chain_info.head().number(),
+        result
+    );
+}
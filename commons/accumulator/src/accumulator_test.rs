@@ -169,6 +169,42 @@ fn test_proof() {
     proof_verify(&accumulator, root_hash1, &batch1, 0);
 }
 
+#[test]
+fn test_multi_proof() {
+    let mock_store = MockAccumulatorStore::new();
+    let accumulator = MerkleAccumulator::new(
+        *ACCUMULATOR_PLACEHOLDER_HASH,
+        vec![],
+        0,
+        0,
+        Arc::new(mock_store),
+    );
+    let leaves = create_leaves(0..100);
+    let root_hash = accumulator.append(&leaves).unwrap();
+    accumulator.flush().unwrap();
+    for indexes in vec![vec![0], vec![99], vec![0, 1], vec![3, 4, 63, 64, 98, 99]] {
+        let elements: Vec<(u64, HashValue)> = indexes
+            .iter()
+            .map(|index| (*index, leaves[*index as usize]))
+            .collect();
+        let proof = accumulator.get_multi_proof(&indexes).unwrap();
+        proof.verify(root_hash, &elements).unwrap();
+        let mut wrong_elements = elements.clone();
+        wrong_elements[0].1 = HashValue::random();
+        assert!(proof.verify(root_hash, &wrong_elements).is_err());
+    }
+    // the shared siblings are only kept once.
+    let single_proof = accumulator.get_proof(10).unwrap().unwrap();
+    let multi_proof = accumulator.get_multi_proof(&[10, 11]).unwrap();
+    assert_eq!(
+        multi_proof.siblings().len() + 1,
+        single_proof.siblings().len()
+    );
+    assert!(accumulator.get_multi_proof(&[]).is_err());
+    assert!(accumulator.get_multi_proof(&[2, 1]).is_err());
+    assert!(accumulator.get_multi_proof(&[100]).is_err());
+}
+
 #[test]
 fn test_multiple_leaves() {
     let mut batch1 = create_leaves(600..608);
@@ -3,11 +3,11 @@
 
 use crate::accumulator_info::AccumulatorInfo;
 use crate::node_index::NodeIndex;
-use crate::proof::AccumulatorProof;
 use crate::tree::AccumulatorTree;
 use anyhow::{ensure, format_err, Result};
 pub use node::AccumulatorNode;
 use parking_lot::Mutex;
+pub use proof::{AccumulatorMultiProof, AccumulatorProof};
 use starcoin_crypto::HashValue;
 use std::sync::Arc;
 pub use tree_store::AccumulatorTreeStore;
@@ -41,6 +41,8 @@ pub trait Accumulator {
     fn get_node_by_position(&self, position: u64) -> Result<Option<HashValue>>;
     /// Get proof by leaf index.
     fn get_proof(&self, leaf_index: u64) -> Result<Option<AccumulatorProof>>;
+    /// Get one proof of several leaves, the indexes must be sorted without duplication.
+    fn get_multi_proof(&self, leaf_indexes: &[u64]) -> Result<AccumulatorMultiProof>;
     /// Flush node to storage.
     fn flush(&self) -> Result<()>;
     /// Get current accumulator tree root hash.
@@ -177,6 +179,29 @@ impl Accumulator for MerkleAccumulator {
         Ok(Some(AccumulatorProof::new(siblings)))
     }
 
+    fn get_multi_proof(&self, leaf_indexes: &[u64]) -> Result<AccumulatorMultiProof> {
+        let mut tree_guard = self.tree.lock();
+        ensure!(
+            !leaf_indexes.is_empty(),
+            "get multi proof without leaf index"
+        );
+        ensure!(
+            leaf_indexes.windows(2).all(|w| w[0] < w[1]),
+            "get multi proof with unsorted or duplicated leaf indexes {:?}",
+            leaf_indexes
+        );
+        let last_index = leaf_indexes[leaf_indexes.len() - 1];
+        ensure!(
+            last_index < tree_guard.num_leaves,
+            "get multi proof invalid leaf_index {}, num_leaves {}",
+            last_index,
+            tree_guard.num_leaves
+        );
+
+        let siblings = tree_guard.get_multi_siblings(leaf_indexes)?;
+        Ok(AccumulatorMultiProof::new(siblings))
+    }
+
     fn flush(&self) -> Result<()> {
         self.tree.lock().flush()
     }
@@ -4,7 +4,7 @@
 use crate::node::InternalNode;
 use crate::node_index::NodeIndex;
 use crate::MAX_ACCUMULATOR_PROOF_DEPTH;
-use anyhow::{ensure, Result};
+use anyhow::{ensure, format_err, Result};
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 
@@ -71,3 +71,87 @@ impl AccumulatorProof {
         Ok(())
     }
 }
+
+/// A proof of several leaves of one accumulator. The siblings which can be computed from the
+/// proved leaves are omitted, and the siblings shared by the leaves are only kept once.
+#[derive(Default, Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccumulatorMultiProof {
+    /// The siblings in this proof, ordered from the bottom level to the root level, and from left
+    /// to right in a level.
+    siblings: Vec<HashValue>,
+}
+
+impl AccumulatorMultiProof {
+    /// Constructs a new `AccumulatorMultiProof` using a list of siblings.
+    pub fn new(siblings: Vec<HashValue>) -> Self {
+        AccumulatorMultiProof { siblings }
+    }
+
+    /// Returns the list of siblings in this proof.
+    pub fn siblings(&self) -> &[HashValue] {
+        &self.siblings
+    }
+
+    /// Verifies the elements given as `(element_index, element_hash)`, sorted by index without
+    /// duplication, exist in the accumulator whose root hash is `expected_root_hash`.
+    pub fn verify(
+        &self,
+        expected_root_hash: HashValue,
+        elements: &[(u64, HashValue)],
+    ) -> Result<()> {
+        ensure!(
+            !elements.is_empty(),
+            "Accumulator multi proof must prove at least one element."
+        );
+        ensure!(
+            elements.windows(2).all(|w| w[0].0 < w[1].0),
+            "Elements of accumulator multi proof must be sorted by index without duplication."
+        );
+        let mut siblings = self.siblings.iter().peekable();
+        let mut nodes = elements.to_vec();
+        let mut depth = 0;
+        while nodes.len() > 1 || siblings.peek().is_some() {
+            ensure!(
+                depth < MAX_ACCUMULATOR_PROOF_DEPTH,
+                "Accumulator multi proof is deeper than {}.",
+                MAX_ACCUMULATOR_PROOF_DEPTH
+            );
+            let mut parents = Vec::with_capacity(nodes.len());
+            let mut i = 0;
+            while i < nodes.len() {
+                let (index, hash) = nodes[i];
+                let parent_hash = match nodes.get(i + 1) {
+                    Some((next_index, next_hash)) if index % 2 == 0 && *next_index == index + 1 => {
+                        i += 2;
+                        InternalNode::new(NodeIndex::new(index), hash, *next_hash).hash()
+                    }
+                    _ => {
+                        let sibling_hash = siblings.next().ok_or_else(|| {
+                            format_err!("Accumulator multi proof has too few siblings.")
+                        })?;
+                        i += 1;
+                        if index % 2 == 0 {
+                            // the current node is a left child.
+                            InternalNode::new(NodeIndex::new(index), hash, *sibling_hash).hash()
+                        } else {
+                            // the current node is a right child.
+                            InternalNode::new(NodeIndex::new(index), *sibling_hash, hash).hash()
+                        }
+                    }
+                };
+                parents.push((index / 2, parent_hash));
+            }
+            nodes = parents;
+            depth += 1;
+        }
+        let actual_root_hash = nodes[0].1;
+        ensure!(
+            actual_root_hash == expected_root_hash,
+            "Root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
+            actual_root_hash,
+            expected_root_hash
+        );
+
+        Ok(())
+    }
+}
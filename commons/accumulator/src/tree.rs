@@ -235,6 +235,33 @@ impl AccumulatorTree {
         Ok(siblings)
     }
 
+    /// Get the siblings of the multi proof of the leaves, level by level from the bottom.
+    /// The `leaf_indexes` must be sorted without duplication.
+    pub(crate) fn get_multi_siblings(&mut self, leaf_indexes: &[u64]) -> Result<Vec<HashValue>> {
+        let root_level = NodeIndex::root_from_leaf_count(self.num_leaves).level();
+        let mut indexes = leaf_indexes.to_vec();
+        let mut siblings = vec![];
+        for level in 0..root_level {
+            let mut parents = Vec::with_capacity(indexes.len());
+            let mut i = 0;
+            while i < indexes.len() {
+                let index = indexes[i];
+                if index % 2 == 0 && indexes.get(i + 1) == Some(&(index + 1)) {
+                    // both children are proved, the parent can be computed.
+                    i += 2;
+                } else {
+                    siblings.push(
+                        self.get_node_hash_always(NodeIndex::from_level_and_pos(level, index ^ 1))?,
+                    );
+                    i += 1;
+                }
+                parents.push(index / 2);
+            }
+            indexes = parents;
+        }
+        Ok(siblings)
+    }
+
     /// Get node hash by index.
     pub(crate) fn get_node_hash(&mut self, node_index: NodeIndex) -> Result<Option<HashValue>> {
         let idx = self.rightmost_leaf_index();
@@ -31,6 +31,12 @@ pub struct MinerConfig {
     /// Miner client thread number, not work for dev network, default is 1
     pub miner_thread: Option<u16>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "enable-submit-and-mine")]
+    /// Allow the chain service to mine a submitted txn into a new block at once, for the dev tools,
+    /// default is false. This flag support both cli and config file.
+    pub enable_submit_and_mine: Option<bool>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -49,6 +55,9 @@ impl MinerConfig {
         self.disable_mint_empty_block
             .unwrap_or_else(|| self.base().net().is_dev())
     }
+    pub fn is_submit_and_mine_enabled(&self) -> bool {
+        self.enable_submit_and_mine.unwrap_or(false)
+    }
     pub fn miner_client_config(&self) -> Option<MinerClientConfig> {
         if self.disable_miner_client() {
             return None;
@@ -102,6 +111,9 @@ impl ConfigModule for MinerConfig {
         if opt.miner.block_gas_limit.is_some() {
             self.block_gas_limit = opt.miner.block_gas_limit;
         }
+        if opt.miner.enable_submit_and_mine.is_some() {
+            self.enable_submit_and_mine = opt.miner.enable_submit_and_mine;
+        }
 
         Ok(())
     }
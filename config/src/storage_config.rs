@@ -57,6 +57,8 @@ impl Default for RocksdbConfig {
 
 static DEFAULT_DB_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("starcoindb/db"));
 pub const DEFAULT_CACHE_SIZE: usize = 20000;
+pub const DEFAULT_SELF_AUDIT_INTERVAL: u64 = 300;
+pub const DEFAULT_BLOCK_RANGE_MAX_COUNT: u64 = 1000;
 
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
@@ -77,6 +79,24 @@ pub struct StorageConfig {
     #[structopt(name = "cache-sizes", long, help = "cache sizes")]
     pub cache_size: Option<usize>,
 
+    /// interval (in seconds) of the chain head self audit, 0 disables it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "self-audit-interval",
+        long,
+        help = "interval (in seconds) of the chain head self audit, 0 disables it, default 300."
+    )]
+    pub self_audit_interval: Option<u64>,
+
+    /// max number of blocks returned by one block range query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "block-range-max-count",
+        long,
+        help = "max number of blocks returned by one block range query, default 1000."
+    )]
+    pub block_range_max_count: Option<u64>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -103,6 +123,16 @@ impl StorageConfig {
     pub fn cache_size(&self) -> usize {
         self.cache_size.unwrap_or(DEFAULT_CACHE_SIZE)
     }
+
+    pub fn self_audit_interval(&self) -> u64 {
+        self.self_audit_interval
+            .unwrap_or(DEFAULT_SELF_AUDIT_INTERVAL)
+    }
+
+    pub fn block_range_max_count(&self) -> u64 {
+        self.block_range_max_count
+            .unwrap_or(DEFAULT_BLOCK_RANGE_MAX_COUNT)
+    }
 }
 
 impl ConfigModule for StorageConfig {
@@ -117,6 +147,12 @@ impl ConfigModule for StorageConfig {
         if opt.storage.cache_size.is_some() {
             self.cache_size = opt.storage.cache_size;
         }
+        if opt.storage.self_audit_interval.is_some() {
+            self.self_audit_interval = opt.storage.self_audit_interval;
+        }
+        if opt.storage.block_range_max_count.is_some() {
+            self.block_range_max_count = opt.storage.block_range_max_count;
+        }
         Ok(())
     }
 }
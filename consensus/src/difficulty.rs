@@ -9,6 +9,9 @@ use starcoin_types::{U256, U512};
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 
+/// The max ratio the target can increase or reduce between two adjacent blocks.
+pub const MAX_TARGET_ADJUST_RATIO: u64 = 2;
+
 /// Get the target of next pow work
 pub fn get_next_work_required(chain: &dyn ChainReader) -> Result<U256> {
     let epoch = chain.epoch();
@@ -102,14 +105,21 @@ pub fn get_next_target_helper(blocks: Vec<BlockDiffInfo>, time_plan: u64) -> Res
     }
     // new_target = avg_target * avg_time_used/time_plan
     // avoid the target increase or reduce too fast.
+    let max_adjust_ratio = U256::from(MAX_TARGET_ADJUST_RATIO);
     let new_target = if let Some(new_target) = (avg_target / time_plan).checked_mul(avg_time.into())
     {
-        if new_target / 2 > avg_target {
-            debug!("target increase too fast, limit to 2 times");
-            avg_target * 2
-        } else if new_target < avg_target / 2 {
-            debug!("target reduce too fast, limit to 2 times");
-            avg_target / 2
+        if new_target / max_adjust_ratio > avg_target {
+            debug!(
+                "target increase too fast, limit to {} times",
+                MAX_TARGET_ADJUST_RATIO
+            );
+            avg_target * max_adjust_ratio
+        } else if new_target < avg_target / max_adjust_ratio {
+            debug!(
+                "target reduce too fast, limit to {} times",
+                MAX_TARGET_ADJUST_RATIO
+            );
+            avg_target / max_adjust_ratio
         } else {
             new_target
         }
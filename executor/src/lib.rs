@@ -13,6 +13,7 @@ pub use starcoin_transaction_builder::{
     encode_transfer_script_by_token_code, encode_transfer_script_function,
     peer_to_peer_txn_sent_as_association, DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
 };
+pub use transaction_validator::{TransactionCheck, TransactionRejectReason, TransactionValidator};
 
 pub mod account;
 mod block_executor;
@@ -26,3 +27,6 @@ pub mod module_compatibility_test;
 pub mod readonly_function_call_test;
 #[cfg(test)]
 pub mod script_function_test;
+mod transaction_validator;
+#[cfg(test)]
+pub mod transaction_validator_test;
@@ -0,0 +1,238 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_state_api::{AccountStateReader, ChainStateReader};
+use starcoin_types::genesis_config::ChainId;
+use starcoin_types::transaction::{CallError, SignedUserTransaction, TransactionError};
+use starcoin_types::vm_error::{StatusCode, VMStatus};
+use starcoin_vm_types::token::token_code::TokenCode;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// A single check performed by the `TransactionValidator`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransactionCheck {
+    /// The transaction must not be expired at the validation time.
+    Expiration,
+    /// The transaction's chain id must match the current chain.
+    ChainId,
+    /// The transaction's sequence number must not be lower than the sender's.
+    SequenceNumber,
+    /// The sender must be able to pay for the max gas of the transaction.
+    Balance,
+}
+
+impl TransactionCheck {
+    pub fn all() -> Vec<TransactionCheck> {
+        vec![
+            TransactionCheck::Expiration,
+            TransactionCheck::ChainId,
+            TransactionCheck::SequenceNumber,
+            TransactionCheck::Balance,
+        ]
+    }
+
+    /// The checks which do not read the state, they hold for a txn whatever txns run before it.
+    pub fn stateless() -> Vec<TransactionCheck> {
+        vec![TransactionCheck::Expiration, TransactionCheck::ChainId]
+    }
+}
+
+/// The reason why a transaction is rejected by the `TransactionValidator`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TransactionRejectReason {
+    Expired {
+        expiration_timestamp_secs: u64,
+        now_secs: u64,
+    },
+    InvalidChainId {
+        expect: ChainId,
+        actual: ChainId,
+    },
+    SequenceNumberTooOld {
+        account_sequence_number: u64,
+        txn_sequence_number: u64,
+    },
+    InsufficientBalance {
+        balance: u128,
+        max_gas_cost: u128,
+    },
+}
+
+impl fmt::Display for TransactionRejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionRejectReason::Expired {
+                expiration_timestamp_secs,
+                now_secs,
+            } => write!(
+                f,
+                "Transaction expired, expiration: {}, now: {}",
+                expiration_timestamp_secs, now_secs
+            ),
+            TransactionRejectReason::InvalidChainId { expect, actual } => write!(
+                f,
+                "Transaction chain id mismatch, expect: {}, actual: {}",
+                expect, actual
+            ),
+            TransactionRejectReason::SequenceNumberTooOld {
+                account_sequence_number,
+                txn_sequence_number,
+            } => write!(
+                f,
+                "Transaction sequence number too old, account: {}, txn: {}",
+                account_sequence_number, txn_sequence_number
+            ),
+            TransactionRejectReason::InsufficientBalance {
+                balance,
+                max_gas_cost,
+            } => write!(
+                f,
+                "Insufficient balance for max gas, balance: {}, max gas cost: {}",
+                balance, max_gas_cost
+            ),
+        }
+    }
+}
+
+impl From<TransactionRejectReason> for TransactionError {
+    fn from(reason: TransactionRejectReason) -> Self {
+        match reason {
+            TransactionRejectReason::Expired { .. } => TransactionError::CallErr(
+                CallError::ExecutionError(VMStatus::Error(StatusCode::TRANSACTION_EXPIRED)),
+            ),
+            TransactionRejectReason::InvalidChainId { .. } => TransactionError::InvalidChainId,
+            TransactionRejectReason::SequenceNumberTooOld { .. } => TransactionError::Old,
+            TransactionRejectReason::InsufficientBalance {
+                balance,
+                max_gas_cost,
+            } => TransactionError::InsufficientBalance {
+                balance: u64::try_from(balance).unwrap_or(u64::MAX),
+                cost: u64::try_from(max_gas_cost).unwrap_or(u64::MAX),
+            },
+        }
+    }
+}
+
+/// `TransactionValidator` rejects obviously invalid transactions before they reach the VM.
+/// It is shared by the txpool admission and the block template packing.
+#[derive(Clone, Debug)]
+pub struct TransactionValidator {
+    chain_id: ChainId,
+    now_secs: u64,
+    checks: Vec<TransactionCheck>,
+}
+
+impl TransactionValidator {
+    /// Create a validator which performs all checks.
+    pub fn new(chain_id: ChainId, now_secs: u64) -> Self {
+        Self::with_checks(chain_id, now_secs, TransactionCheck::all())
+    }
+
+    pub fn with_checks(chain_id: ChainId, now_secs: u64, checks: Vec<TransactionCheck>) -> Self {
+        Self {
+            chain_id,
+            now_secs,
+            checks,
+        }
+    }
+
+    pub fn checks(&self) -> &[TransactionCheck] {
+        &self.checks
+    }
+
+    /// Run the checks in order, return the first reject reason, or None if the txn pass all checks.
+    pub fn validate<S>(
+        &self,
+        state: &S,
+        txn: &SignedUserTransaction,
+    ) -> Result<Option<TransactionRejectReason>>
+    where
+        S: ChainStateReader,
+    {
+        let reader = AccountStateReader::new(state);
+        for check in &self.checks {
+            let reason = match check {
+                TransactionCheck::Expiration => self.check_expiration(txn),
+                TransactionCheck::ChainId => self.check_chain_id(txn),
+                TransactionCheck::SequenceNumber => Self::check_sequence_number(&reader, txn)?,
+                TransactionCheck::Balance => Self::check_balance(&reader, txn)?,
+            };
+            if reason.is_some() {
+                return Ok(reason);
+            }
+        }
+        Ok(None)
+    }
+
+    fn check_expiration(&self, txn: &SignedUserTransaction) -> Option<TransactionRejectReason> {
+        // keep same as the txn prologue: now < expiration_timestamp_secs
+        if txn.expiration_timestamp_secs() <= self.now_secs {
+            Some(TransactionRejectReason::Expired {
+                expiration_timestamp_secs: txn.expiration_timestamp_secs(),
+                now_secs: self.now_secs,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn check_chain_id(&self, txn: &SignedUserTransaction) -> Option<TransactionRejectReason> {
+        if txn.chain_id() != self.chain_id {
+            Some(TransactionRejectReason::InvalidChainId {
+                expect: self.chain_id,
+                actual: txn.chain_id(),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn check_sequence_number<S>(
+        reader: &AccountStateReader<S>,
+        txn: &SignedUserTransaction,
+    ) -> Result<Option<TransactionRejectReason>>
+    where
+        S: ChainStateReader,
+    {
+        let account_sequence_number = reader
+            .get_account_resource(&txn.sender())?
+            .map(|resource| resource.sequence_number())
+            .unwrap_or_default();
+        if txn.sequence_number() < account_sequence_number {
+            Ok(Some(TransactionRejectReason::SequenceNumberTooOld {
+                account_sequence_number,
+                txn_sequence_number: txn.sequence_number(),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn check_balance<S>(
+        reader: &AccountStateReader<S>,
+        txn: &SignedUserTransaction,
+    ) -> Result<Option<TransactionRejectReason>>
+    where
+        S: ChainStateReader,
+    {
+        let max_gas_cost = u128::from(txn.max_gas_amount()) * u128::from(txn.gas_unit_price());
+        if max_gas_cost == 0 {
+            return Ok(None);
+        }
+        let token_code = TokenCode::from_str(txn.gas_token_code())?;
+        let balance = reader
+            .get_balance_by_token_code(&txn.sender(), token_code)?
+            .unwrap_or_default();
+        if balance < max_gas_cost {
+            Ok(Some(TransactionRejectReason::InsufficientBalance {
+                balance,
+                max_gas_cost,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
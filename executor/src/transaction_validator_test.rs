@@ -0,0 +1,187 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::Account;
+use crate::{TransactionCheck, TransactionRejectReason, TransactionValidator};
+use anyhow::Result;
+use starcoin_config::ChainNetwork;
+use starcoin_transaction_builder::{
+    create_signed_txn_with_association_account, encode_transfer_script_function,
+    DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
+};
+use starcoin_types::account_config::association_address;
+use starcoin_types::transaction::{SignedUserTransaction, TransactionPayload};
+use starcoin_vm_types::genesis_config::ChainId;
+use starcoin_vm_types::transaction::TransactionError;
+use test_helper::executor::{
+    association_execute_should_success, get_sequence_number, prepare_genesis,
+};
+
+fn transfer_payload() -> TransactionPayload {
+    TransactionPayload::ScriptFunction(encode_transfer_script_function(
+        *Account::new().address(),
+        1000,
+    ))
+}
+
+fn association_txn(
+    net: &ChainNetwork,
+    sequence_number: u64,
+    expiration_timestamp_secs: u64,
+) -> SignedUserTransaction {
+    create_signed_txn_with_association_account(
+        transfer_payload(),
+        sequence_number,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        expiration_timestamp_secs,
+        net,
+    )
+}
+
+#[stest::test]
+fn test_validator_valid_txn() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let now = net.time_service().now_secs();
+    let seq = get_sequence_number(association_address(), &chain_state);
+    let txn = association_txn(&net, seq, now + DEFAULT_EXPIRATION_TIME);
+    let validator = TransactionValidator::new(net.chain_id(), now);
+    assert_eq!(validator.checks(), TransactionCheck::all().as_slice());
+    assert_eq!(validator.validate(&chain_state, &txn)?, None);
+    Ok(())
+}
+
+#[stest::test]
+fn test_validator_expired_txn() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let now = net.time_service().now_secs();
+    let seq = get_sequence_number(association_address(), &chain_state);
+    let txn = association_txn(&net, seq, now);
+    let validator = TransactionValidator::new(net.chain_id(), now);
+    let reason = validator.validate(&chain_state, &txn)?;
+    assert_eq!(
+        reason,
+        Some(TransactionRejectReason::Expired {
+            expiration_timestamp_secs: now,
+            now_secs: now,
+        })
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_validator_invalid_chain_id() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let now = net.time_service().now_secs();
+    let seq = get_sequence_number(association_address(), &chain_state);
+    let txn = association_txn(&net, seq, now + DEFAULT_EXPIRATION_TIME);
+    let other_chain_id = ChainId::new(net.chain_id().id().wrapping_add(1));
+    let validator = TransactionValidator::new(other_chain_id, now);
+    let reason = validator.validate(&chain_state, &txn)?;
+    assert_eq!(
+        reason,
+        Some(TransactionRejectReason::InvalidChainId {
+            expect: other_chain_id,
+            actual: net.chain_id(),
+        })
+    );
+    assert_eq!(
+        TransactionError::from(reason.unwrap()),
+        TransactionError::InvalidChainId
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_validator_sequence_number_too_old() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    association_execute_should_success(&net, &chain_state, transfer_payload())?;
+    let now = net.time_service().now_secs();
+    let seq = get_sequence_number(association_address(), &chain_state);
+    assert!(seq > 0);
+    let txn = association_txn(&net, seq - 1, now + DEFAULT_EXPIRATION_TIME);
+    let validator = TransactionValidator::new(net.chain_id(), now);
+    let reason = validator.validate(&chain_state, &txn)?;
+    assert_eq!(
+        reason,
+        Some(TransactionRejectReason::SequenceNumberTooOld {
+            account_sequence_number: seq,
+            txn_sequence_number: seq - 1,
+        })
+    );
+    assert_eq!(
+        TransactionError::from(reason.unwrap()),
+        TransactionError::Old
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_validator_insufficient_balance() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let now = net.time_service().now_secs();
+    let account = Account::new();
+    let txn = account.create_signed_txn_with_args(
+        transfer_payload(),
+        0,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        now + DEFAULT_EXPIRATION_TIME,
+        net.chain_id(),
+    );
+    let validator = TransactionValidator::new(net.chain_id(), now);
+    let reason = validator.validate(&chain_state, &txn)?;
+    assert_eq!(
+        reason,
+        Some(TransactionRejectReason::InsufficientBalance {
+            balance: 0,
+            max_gas_cost: u128::from(DEFAULT_MAX_GAS_AMOUNT),
+        })
+    );
+
+    // the balance check can be turned off.
+    let validator = TransactionValidator::with_checks(
+        net.chain_id(),
+        now,
+        vec![
+            TransactionCheck::Expiration,
+            TransactionCheck::ChainId,
+            TransactionCheck::SequenceNumber,
+        ],
+    );
+    assert_eq!(validator.validate(&chain_state, &txn)?, None);
+    Ok(())
+}
+
+#[stest::test]
+fn test_validator_stateless_checks() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let now = net.time_service().now_secs();
+    // a txn of an account which does not exist yet, it may be created by an earlier txn.
+    let account = Account::new();
+    let txn = account.create_signed_txn_with_args(
+        transfer_payload(),
+        0,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        now + DEFAULT_EXPIRATION_TIME,
+        net.chain_id(),
+    );
+    let validator =
+        TransactionValidator::with_checks(net.chain_id(), now, TransactionCheck::stateless());
+    assert_eq!(validator.validate(&chain_state, &txn)?, None);
+
+    let expired_txn = account.create_signed_txn_with_args(
+        transfer_payload(),
+        0,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        now,
+        net.chain_id(),
+    );
+    assert!(matches!(
+        validator.validate(&chain_state, &expired_txn)?,
+        Some(TransactionRejectReason::Expired { .. })
+    ));
+    Ok(())
+}
@@ -3,13 +3,14 @@
 
 use crate::messages::{
     GetPeerById, GetPeerSet, GetSelfPeer, NotificationMessage, PeerMessage, PeerReputations,
-    ReportReputation,
+    ReportReputation, TransactionsMessage,
 };
 use anyhow::*;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use starcoin_logger::prelude::*;
 use starcoin_service_registry::{ActorService, EventHandler, ServiceHandler, ServiceRef};
+use starcoin_types::transaction::SignedUserTransaction;
 use std::sync::mpsc::TrySendError;
 
 pub mod messages;
@@ -37,6 +38,24 @@ pub trait NetworkService: Send + Sync + Clone + Sized + std::marker::Unpin + Pee
     fn broadcast(&self, notification: NotificationMessage);
 }
 
+/// The object safe part of the `NetworkService` to broadcast transactions,
+/// for the services which can not depend on the concrete network service.
+pub trait TxnBroadcaster: Send + Sync {
+    /// Broadcast the transactions to all connected peers.
+    fn broadcast_txns(&self, txns: Vec<SignedUserTransaction>);
+}
+
+impl<N> TxnBroadcaster for N
+where
+    N: NetworkService,
+{
+    fn broadcast_txns(&self, txns: Vec<SignedUserTransaction>) {
+        self.broadcast(NotificationMessage::Transactions(TransactionsMessage::new(
+            txns,
+        )));
+    }
+}
+
 pub trait NetworkActor:
     ActorService
     + EventHandler<Self, PeerMessage>
@@ -3,6 +3,7 @@
 
 use crate::peer_message_handler::NodePeerMessageHandler;
 use anyhow::{format_err, Result};
+use network_api::TxnBroadcaster;
 use starcoin_block_relayer::BlockRelayer;
 use starcoin_config::NodeConfig;
 use starcoin_network::{NetworkActorService, NetworkServiceRef};
@@ -38,6 +39,7 @@ impl ServiceFactory<NetworkActorService> for NetworkServiceFactory {
         )?;
         let network_service = actor_service.network_service();
         let network_async_service = NetworkServiceRef::new(network_service, ctx.self_ref());
+        ctx.put_shared::<Arc<dyn TxnBroadcaster>>(Arc::new(network_async_service.clone()))?;
         ctx.put_shared(network_async_service)?;
         Ok(actor_service)
     }
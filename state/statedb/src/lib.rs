@@ -27,6 +27,7 @@ use starcoin_vm_types::language_storage::StructTag;
 use starcoin_vm_types::state_view::StateView;
 use std::collections::HashSet;
 use std::convert::TryInto;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -211,6 +212,8 @@ pub struct ChainStateDB {
     ///global state tree.
     state_tree: StateTree<AccountAddress>,
     cache: Mutex<LruCache<AccountAddress, CacheItem>>,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
     updates: RwLock<HashSet<AccountAddress>>,
 }
 
@@ -226,6 +229,8 @@ impl ChainStateDB {
             store: store.clone(),
             state_tree: StateTree::new(store, root_hash),
             cache: Mutex::new(LruCache::new(DEFAULT_CACHE_SIZE)),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
             updates: RwLock::new(HashSet::new()),
         }
     }
@@ -241,10 +246,27 @@ impl ChainStateDB {
             store: self.store.clone(),
             state_tree: StateTree::new(self.store.clone(), Some(root_hash)),
             cache: Mutex::new(LruCache::new(DEFAULT_CACHE_SIZE)),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
             updates: RwLock::new(HashSet::new()),
         }
     }
 
+    /// The count of the account state lookups served by the cache.
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    /// The count of the account state lookups which read the state tree.
+    pub fn cache_misses(&self) -> u64 {
+        self.cache_misses.load(Ordering::Relaxed)
+    }
+
+    /// The count of the cached account states.
+    pub fn cache_len(&self) -> usize {
+        self.cache.lock().len()
+    }
+
     fn new_state_tree<K: RawKey>(&self, root_hash: HashValue) -> StateTree<K> {
         StateTree::new(self.store.clone(), Some(root_hash))
     }
@@ -281,8 +303,12 @@ impl ChainStateDB {
         let mut cache = self.cache.lock();
         let item = cache.get(account_address);
         let object = match item {
-            Some(item) => item.as_object(),
+            Some(item) => {
+                self.cache_hits.fetch_add(1, Ordering::Relaxed);
+                item.as_object()
+            }
             None => {
+                self.cache_misses.fetch_add(1, Ordering::Relaxed);
                 let object = self
                     .get_account_state(account_address)?
                     .map(|account_state| {
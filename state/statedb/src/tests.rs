@@ -101,3 +101,19 @@ fn test_state_version() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_state_db_cache_stats() -> Result<()> {
+    let storage = MockStateNodeStore::new();
+    let chain_state_db = ChainStateDB::new(Arc::new(storage), None);
+    let access_path = AccessPath::random_resource();
+    assert!(chain_state_db.get(&access_path)?.is_none());
+    assert_eq!(chain_state_db.cache_misses(), 1);
+    assert_eq!(chain_state_db.cache_len(), 1);
+
+    // the absent account is cached too.
+    assert!(chain_state_db.get(&access_path)?.is_none());
+    assert_eq!(chain_state_db.cache_hits(), 1);
+    assert_eq!(chain_state_db.cache_misses(), 1);
+    Ok(())
+}
@@ -1,18 +1,21 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 use crate::define_storage;
-use crate::storage::{CodecKVStore, StorageInstance, ValueCodec};
+use crate::storage::{CodecKVStore, CodecWriteBatch, StorageInstance, ValueCodec};
 use crate::{
-    BLOCK_BODY_PREFIX_NAME, BLOCK_HEADER_PREFIX_NAME, BLOCK_PREFIX_NAME,
-    BLOCK_TRANSACTIONS_PREFIX_NAME, BLOCK_TRANSACTION_INFOS_PREFIX_NAME, FAILED_BLOCK_PREFIX_NAME,
+    BLOCK_AUTHOR_PREFIX_NAME, BLOCK_BODY_PREFIX_NAME, BLOCK_HEADER_PREFIX_NAME, BLOCK_PREFIX_NAME,
+    BLOCK_TRANSACTIONS_PREFIX_NAME, BLOCK_TRANSACTION_INFOS_PREFIX_NAME, BRANCH_HEAD_PREFIX_NAME,
+    BRANCH_HEAD_ROLLBACK_LIMIT, FAILED_BLOCK_PREFIX_NAME, TRANSACTION_INFO_INDEX_PREFIX_NAME,
 };
 use anyhow::{bail, Result};
 use bcs_ext::{BCSCodec, Sample};
 use crypto::HashValue;
 use logger::prelude::*;
 use serde::{Deserialize, Serialize};
-use starcoin_types::block::{Block, BlockBody, BlockHeader};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::{Block, BlockBody, BlockHeader, BlockNumber};
 use starcoin_types::peer_info::PeerId;
+use starcoin_types::U256;
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct FailedBlock {
@@ -48,6 +51,13 @@ impl Sample for FailedBlock {
     }
 }
 
+/// The head of a branch, saved under the id of the head block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BranchHead {
+    pub number: BlockNumber,
+    pub total_difficulty: U256,
+}
+
 define_storage!(BlockInnerStorage, HashValue, Block, BLOCK_PREFIX_NAME);
 define_storage!(
     BlockHeaderStorage,
@@ -73,12 +83,30 @@ define_storage!(
     Vec<HashValue>,
     BLOCK_TRANSACTION_INFOS_PREFIX_NAME
 );
+define_storage!(
+    TransactionInfoIndexStorage,
+    HashValue,
+    u64,
+    TRANSACTION_INFO_INDEX_PREFIX_NAME
+);
 define_storage!(
     FailedBlockStorage,
     HashValue,
     FailedBlock,
     FAILED_BLOCK_PREFIX_NAME
 );
+define_storage!(
+    BlockAuthorStorage,
+    (AccountAddress, BlockNumber),
+    Vec<HashValue>,
+    BLOCK_AUTHOR_PREFIX_NAME
+);
+define_storage!(
+    BranchHeadStorage,
+    HashValue,
+    BranchHead,
+    BRANCH_HEAD_PREFIX_NAME
+);
 
 #[derive(Clone)]
 pub struct BlockStorage {
@@ -87,7 +115,10 @@ pub struct BlockStorage {
     body_store: BlockBodyStorage,
     block_txns_store: BlockTransactionsStorage,
     block_txn_infos_store: BlockTransactionInfosStorage,
+    txn_info_index_store: TransactionInfoIndexStorage,
     failed_block_storage: FailedBlockStorage,
+    author_store: BlockAuthorStorage,
+    branch_head_store: BranchHeadStorage,
 }
 
 impl ValueCodec for Block {
@@ -130,6 +161,16 @@ impl ValueCodec for FailedBlock {
     }
 }
 
+impl ValueCodec for BranchHead {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
 impl BlockStorage {
     pub fn new(instance: StorageInstance) -> Self {
         BlockStorage {
@@ -138,7 +179,10 @@ impl BlockStorage {
             body_store: BlockBodyStorage::new(instance.clone()),
             block_txns_store: BlockTransactionsStorage::new(instance.clone()),
             block_txn_infos_store: BlockTransactionInfosStorage::new(instance.clone()),
-            failed_block_storage: FailedBlockStorage::new(instance),
+            txn_info_index_store: TransactionInfoIndexStorage::new(instance.clone()),
+            failed_block_storage: FailedBlockStorage::new(instance.clone()),
+            author_store: BlockAuthorStorage::new(instance.clone()),
+            branch_head_store: BranchHeadStorage::new(instance),
         }
     }
     pub fn save(&self, block: Block) -> Result<()> {
@@ -179,17 +223,86 @@ impl BlockStorage {
         self.body_store.get(block_id)
     }
 
+    pub fn contains_block(&self, block_id: HashValue) -> Result<bool> {
+        self.block_store.contains_key(block_id)
+    }
+
     pub fn commit_block(&self, block: Block) -> Result<()> {
         let (header, body) = block.clone().into_inner();
         //save header
         let block_id = header.id();
+        let author = header.author();
+        let number = header.number();
         self.save_header(header)?;
+        //index block by author
+        self.put_author_block(author, number, block_id)?;
         //save body
         self.save_body(block_id, body)?;
         //save block cache
         self.save(block)
     }
 
+    fn put_author_block(
+        &self,
+        author: AccountAddress,
+        number: BlockNumber,
+        block_id: HashValue,
+    ) -> Result<()> {
+        // the blocks of different branches may have the same number.
+        let mut block_ids = self.author_store.get((author, number))?.unwrap_or_default();
+        // a block may be committed again, such as when it is re-executed.
+        if !block_ids.contains(&block_id) {
+            block_ids.push(block_id);
+            self.author_store.put((author, number), block_ids)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_block_ids_by_author(
+        &self,
+        author: AccountAddress,
+        max_number: Option<BlockNumber>,
+        limit: usize,
+    ) -> Result<Vec<(BlockNumber, Vec<HashValue>)>> {
+        Ok(self
+            .author_store
+            .rev_scan_prefix(
+                author.to_vec().as_slice(),
+                max_number.map(|number| (author, number)),
+                limit,
+            )?
+            .into_iter()
+            .map(|((_, number), block_ids)| (number, block_ids))
+            .collect())
+    }
+
+    pub fn get_branch_heads(&self) -> Result<Vec<(HashValue, BranchHead)>> {
+        self.branch_head_store
+            .rev_scan_prefix(&[], None, usize::MAX)
+    }
+
+    /// Save the block as the head of its branch, and delete the row of its parent. The heads more
+    /// than `BRANCH_HEAD_ROLLBACK_LIMIT` blocks below the block are pruned.
+    pub fn save_branch_head(&self, header: &BlockHeader, total_difficulty: U256) -> Result<()> {
+        let mut batch = CodecWriteBatch::new();
+        if let Some(min_number) = header.number().checked_sub(BRANCH_HEAD_ROLLBACK_LIMIT) {
+            for (head_id, branch_head) in self.get_branch_heads()? {
+                if branch_head.number < min_number {
+                    batch.delete(head_id)?;
+                }
+            }
+        }
+        batch.delete(header.parent_hash())?;
+        batch.put(
+            header.id(),
+            BranchHead {
+                number: header.number(),
+                total_difficulty,
+            },
+        )?;
+        self.branch_head_store.write_batch(batch)
+    }
+
     pub fn get_block_header_by_hash(&self, block_id: HashValue) -> Result<Option<BlockHeader>> {
         self.header_store.get(block_id)
     }
@@ -224,9 +337,20 @@ impl BlockStorage {
         block_id: HashValue,
         txn_info_ids: Vec<HashValue>,
     ) -> Result<()> {
+        self.txn_info_index_store.put_all(
+            txn_info_ids
+                .iter()
+                .enumerate()
+                .map(|(index, txn_info_id)| (*txn_info_id, index as u64))
+                .collect(),
+        )?;
         self.block_txn_infos_store.put(block_id, txn_info_ids)
     }
 
+    pub fn get_txn_info_index(&self, txn_info_id: HashValue) -> Result<Option<u64>> {
+        self.txn_info_index_store.get(txn_info_id)
+    }
+
     pub fn save_failed_block(
         &self,
         block_id: HashValue,
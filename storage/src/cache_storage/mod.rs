@@ -8,29 +8,48 @@ use anyhow::{Error, Result};
 use lru::LruCache;
 use parking_lot::Mutex;
 use starcoin_config::DEFAULT_CACHE_SIZE;
+use std::sync::atomic::{AtomicU64, Ordering};
 pub struct CacheStorage {
     cache: Mutex<LruCache<Vec<u8>, CacheObject>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
 }
 
 impl CacheStorage {
     pub fn new() -> Self {
-        CacheStorage {
-            cache: Mutex::new(LruCache::new(DEFAULT_CACHE_SIZE)),
-        }
+        Self::new_with_capacity(DEFAULT_CACHE_SIZE)
     }
     pub fn new_with_capacity(size: usize) -> Self {
         CacheStorage {
             cache: Mutex::new(LruCache::new(size)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         }
     }
     pub fn get_obj(&self, prefix_name: &str, key: Vec<u8>) -> Result<Option<CacheObject>> {
-        record_metrics("cache", prefix_name, "get").end_with(|| {
-            Ok(self
-                .cache
-                .lock()
-                .get(&compose_key(prefix_name.to_string(), key))
-                .cloned())
-        })
+        record_metrics("cache", prefix_name, "get")
+            .end_with(|| Ok(self.lookup(compose_key(prefix_name.to_string(), key))))
+    }
+
+    /// The count of the lookups which found the key in the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// The count of the lookups which did not find the key in the cache.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn lookup(&self, key: Vec<u8>) -> Option<CacheObject> {
+        let obj = self.cache.lock().get(&key).cloned();
+        let counter = if obj.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        obj
     }
 
     pub fn put_obj(&self, prefix_name: &str, key: Vec<u8>, obj: CacheObject) -> Result<()> {
@@ -47,6 +66,33 @@ impl CacheStorage {
         InnerStore::contains_key(self, prefix_name, key)
     }
 
+    /// Get at most `limit` cached key value pairs whose key starts with `key_prefix`, in descending
+    /// key order, starting from `from_key` if it is set. All the cached keys are scanned.
+    pub fn rev_scan_prefix(
+        &self,
+        prefix_name: &str,
+        key_prefix: &[u8],
+        from_key: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let composed_prefix = compose_key(prefix_name.to_string(), key_prefix.to_vec());
+        let mut pairs: Vec<(Vec<u8>, Vec<u8>)> = self
+            .cache
+            .lock()
+            .iter()
+            .filter(|(key, _)| key.starts_with(&composed_prefix))
+            .filter_map(|(key, obj)| {
+                let key = key[prefix_name.len()..].to_vec();
+                let value: Option<Vec<u8>> = obj.into();
+                value.map(|value| (key, value))
+            })
+            .filter(|(key, _)| from_key.map_or(true, |from_key| key.as_slice() <= from_key))
+            .collect();
+        pairs.sort_by(|a, b| b.0.cmp(&a.0));
+        pairs.truncate(limit);
+        Ok(pairs)
+    }
+
     pub fn write_batch_obj(&self, prefix_name: &str, batch: WriteBatch) -> Result<()> {
         record_metrics("cache", "batch", prefix_name).end_with(|| {
             for (key, write_op) in &batch.rows {
@@ -72,10 +118,8 @@ impl InnerStore for CacheStorage {
     fn get(&self, prefix_name: &str, key: Vec<u8>) -> Result<Option<Vec<u8>>> {
         record_metrics("cache", prefix_name, "get").end_with(|| {
             Ok(self
-                .cache
-                .lock()
-                .get(&compose_key(prefix_name.to_string(), key))
-                .and_then(|v| v.into()))
+                .lookup(compose_key(prefix_name.to_string(), key))
+                .and_then(|v| (&v).into()))
         })
     }
 
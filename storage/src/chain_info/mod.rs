@@ -6,6 +6,7 @@ use crate::CHAIN_INFO_PREFIX_NAME;
 use anyhow::Result;
 use crypto::HashValue;
 use starcoin_types::startup_info::StartupInfo;
+use starcoin_types::U256;
 use std::convert::TryInto;
 
 #[derive(Clone)]
@@ -25,6 +26,7 @@ pub type ChainInfoStorage = InnerStorage<ChainInfoColumnFamily>;
 impl ChainInfoStorage {
     const STARTUP_INFO_KEY: &'static str = "startup_info";
     const GENESIS_KEY: &'static str = "genesis";
+    const LEGACY_BRANCH_HEADS_KEY: &'static str = "branch_heads";
 
     pub fn get_startup_info(&self) -> Result<Option<StartupInfo>> {
         self.get(Self::STARTUP_INFO_KEY.as_bytes())
@@ -55,4 +57,14 @@ impl ChainInfoStorage {
             genesis_block_hash.to_vec(),
         )
     }
+
+    /// Take the branch heads saved in one row by the previous version, the row is removed.
+    pub fn take_legacy_branch_heads(&self) -> Result<Option<Vec<(HashValue, U256)>>> {
+        let branch_heads = match self.get(Self::LEGACY_BRANCH_HEADS_KEY.as_bytes())? {
+            Some(bytes) => bcs_ext::from_bytes(bytes.as_slice())?,
+            None => return Ok(None),
+        };
+        self.remove(Self::LEGACY_BRANCH_HEADS_KEY.as_bytes().to_vec())?;
+        Ok(Some(branch_heads))
+    }
 }
@@ -2,19 +2,45 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::define_storage;
-use crate::storage::{CodecKVStore, ValueCodec};
-use crate::{ContractEventStore, CONTRACT_EVENT_PREFIX_NAME};
+use crate::storage::{CodecKVStore, StorageInstance, ValueCodec};
+use crate::{ContractEventStore, CONTRACT_EVENT_PREFIX_NAME, DAO_PROPOSAL_PREFIX_NAME};
 use anyhow::Result;
 use bcs_ext::BCSCodec;
 use crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::ProposalCreatedEvent;
 use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::move_resource::MoveResource;
 
 define_storage!(
-    ContractEventStorage,
+    ContractEventInnerStorage,
     HashValue,
     Vec<ContractEvent>,
     CONTRACT_EVENT_PREFIX_NAME
 );
+define_storage!(
+    DaoProposalStorage,
+    (AccountAddress, u64),
+    HashValue,
+    DAO_PROPOSAL_PREFIX_NAME
+);
+
+#[derive(Clone)]
+pub struct ContractEventStorage {
+    event_store: ContractEventInnerStorage,
+    /// index the dao proposals by (proposer, proposal id), the value is the txn info id which
+    /// created the proposal.
+    proposal_store: DaoProposalStorage,
+}
+
+impl ContractEventStorage {
+    pub fn new(instance: StorageInstance) -> Self {
+        Self {
+            event_store: ContractEventInnerStorage::new(instance.clone()),
+            proposal_store: DaoProposalStorage::new(instance),
+        }
+    }
+}
 
 impl ValueCodec for Vec<ContractEvent> {
     fn encode_value(&self) -> Result<Vec<u8>> {
@@ -32,10 +58,30 @@ impl ContractEventStore for ContractEventStorage {
         txn_info_id: HashValue,
         events: Vec<ContractEvent>,
     ) -> Result<()> {
-        self.put(txn_info_id, events)
+        let proposal_created_type = ProposalCreatedEvent::type_tag();
+        for event in &events {
+            if event.type_tag() == &proposal_created_type {
+                let event = ProposalCreatedEvent::try_from_bytes(event.event_data())?;
+                self.proposal_store
+                    .put((event.proposer, event.proposal_id), txn_info_id)?;
+            }
+        }
+        self.event_store.put(txn_info_id, events)
     }
 
     fn get_contract_events(&self, txn_info_id: HashValue) -> Result<Option<Vec<ContractEvent>>> {
-        self.get(txn_info_id)
+        self.event_store.get(txn_info_id)
+    }
+
+    fn get_dao_proposers(&self) -> Result<Vec<AccountAddress>> {
+        let mut proposers: Vec<AccountAddress> = self
+            .proposal_store
+            .rev_scan_prefix(&[], None, usize::MAX)?
+            .into_iter()
+            .map(|((proposer, _), _)| proposer)
+            .collect();
+        proposers.dedup();
+        proposers.reverse();
+        Ok(proposers)
     }
 }
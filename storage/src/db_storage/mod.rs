@@ -190,6 +190,54 @@ impl DBStorage {
     pub fn rev_iter(&self, prefix_name: &str) -> Result<SchemaIterator> {
         self.iter_with_direction(prefix_name, ScanDirection::Backward)
     }
+
+    /// Get at most `limit` key value pairs whose key starts with `key_prefix`, in descending key
+    /// order, starting from `from_key` if it is set.
+    pub fn rev_scan_prefix(
+        &self,
+        prefix_name: &str,
+        key_prefix: &[u8],
+        from_key: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let mut iter = self.rev_iter(prefix_name)?;
+        match from_key
+            .map(|key| key.to_vec())
+            .or_else(|| next_key_prefix(key_prefix))
+        {
+            Some(seek_key) => iter.seek_for_prev(seek_key)?,
+            None => iter.seek_to_last(),
+        }
+        let mut pairs = vec![];
+        for item in iter {
+            let (key, value) = item?;
+            if !key.starts_with(key_prefix) {
+                // the seek may stop at the first key after the prefix.
+                if key.as_slice() > key_prefix {
+                    continue;
+                }
+                break;
+            }
+            if pairs.len() >= limit {
+                break;
+            }
+            pairs.push((key, value));
+        }
+        Ok(pairs)
+    }
+}
+
+/// The smallest key larger than all the keys starting with `key_prefix`, None if there is no such
+/// key.
+fn next_key_prefix(key_prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut next = key_prefix.to_vec();
+    while let Some(last) = next.pop() {
+        if let Some(last) = last.checked_add(1) {
+            next.push(last);
+            return Some(next);
+        }
+    }
+    None
 }
 
 pub enum ScanDirection {
@@ -6,6 +6,7 @@ use crate::accumulator::{
 };
 use crate::block::BlockStorage;
 use crate::block_info::{BlockInfoStorage, BlockInfoStore};
+use crate::cache_storage::CacheStorage;
 use crate::chain_info::ChainInfoStorage;
 use crate::contract_event::ContractEventStorage;
 use crate::state_node::StateStorage;
@@ -18,12 +19,14 @@ use once_cell::sync::Lazy;
 use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_accumulator::AccumulatorTreeStore;
 use starcoin_state_store_api::{StateNode, StateNodeStore};
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::contract_event::ContractEvent;
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
 use starcoin_types::transaction::{BlockTransactionInfo, Transaction};
+use starcoin_types::U256;
 use starcoin_types::{
-    block::{Block, BlockBody, BlockHeader, BlockInfo},
+    block::{Block, BlockBody, BlockHeader, BlockInfo, BlockNumber},
     startup_info::StartupInfo,
 };
 use std::collections::BTreeMap;
@@ -65,6 +68,14 @@ pub const TRANSACTION_INFO_PREFIX_NAME: ColumnFamilyName = "transaction_info";
 pub const TRANSACTION_INFO_HASH_PREFIX_NAME: ColumnFamilyName = "transaction_info_hash";
 pub const CONTRACT_EVENT_PREFIX_NAME: ColumnFamilyName = "contract_event";
 pub const FAILED_BLOCK_PREFIX_NAME: ColumnFamilyName = "failed_block";
+pub const BLOCK_AUTHOR_PREFIX_NAME: ColumnFamilyName = "block_author";
+pub const DAO_PROPOSAL_PREFIX_NAME: ColumnFamilyName = "dao_proposal";
+pub const BRANCH_HEAD_PREFIX_NAME: ColumnFamilyName = "branch_head";
+pub const TRANSACTION_INFO_INDEX_PREFIX_NAME: ColumnFamilyName = "transaction_info_index";
+
+/// A branch head more than this number of blocks below a newly saved head is pruned, the main
+/// chain is never rolled back so far.
+pub const BRANCH_HEAD_ROLLBACK_LIMIT: BlockNumber = 1024;
 
 ///db storage use prefix_name vec to init
 /// Please note that adding a prefix needs to be added in vec simultaneously, remember！！
@@ -85,6 +96,10 @@ pub static VEC_PREFIX_NAME: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
         TRANSACTION_INFO_HASH_PREFIX_NAME,
         CONTRACT_EVENT_PREFIX_NAME,
         FAILED_BLOCK_PREFIX_NAME,
+        BLOCK_AUTHOR_PREFIX_NAME,
+        DAO_PROPOSAL_PREFIX_NAME,
+        BRANCH_HEAD_PREFIX_NAME,
+        TRANSACTION_INFO_INDEX_PREFIX_NAME,
     ]
 });
 
@@ -110,6 +125,9 @@ pub trait BlockStore {
 
     fn get_block_by_hash(&self, block_id: HashValue) -> Result<Option<Block>>;
 
+    /// Check the block is committed by its key, without reading the block.
+    fn contains_block(&self, block_id: HashValue) -> Result<bool>;
+
     fn save_block_transaction_ids(
         &self,
         block_id: HashValue,
@@ -120,12 +138,16 @@ pub trait BlockStore {
     /// If block_id doesn't exists, return error.
     fn get_block_txn_info_ids(&self, block_id: HashValue) -> Result<Vec<HashValue>>;
 
+    /// Save the txn info ids of the block, and the index of each txn info in the block.
     fn save_block_txn_info_ids(
         &self,
         block_id: HashValue,
         txn_info_ids: Vec<HashValue>,
     ) -> Result<()>;
 
+    /// Get the index of the txn info in its block, None if the txn info is not saved.
+    fn get_txn_info_index(&self, txn_info_id: HashValue) -> Result<Option<u64>>;
+
     fn save_failed_block(
         &self,
         block_id: HashValue,
@@ -138,6 +160,23 @@ pub trait BlockStore {
         &self,
         block_id: HashValue,
     ) -> Result<Option<(Block, Option<PeerId>, String)>>;
+
+    /// Get the ids of the committed blocks produced by `author` grouped by the block number, in
+    /// descending number order from `max_number` if it is set, at most `limit` numbers. The
+    /// blocks of all branches are included.
+    fn get_block_ids_by_author(
+        &self,
+        author: AccountAddress,
+        max_number: Option<BlockNumber>,
+        limit: usize,
+    ) -> Result<Vec<(BlockNumber, Vec<HashValue>)>>;
+
+    /// Get the head of every known branch with its total difficulty.
+    fn get_branch_heads(&self) -> Result<Vec<(HashValue, U256)>>;
+
+    /// Save the block as the head of its branch, in place of its parent, and prune the heads more
+    /// than `BRANCH_HEAD_ROLLBACK_LIMIT` blocks below it.
+    fn save_branch_head(&self, block_header: &BlockHeader, total_difficulty: U256) -> Result<()>;
 }
 
 pub trait BlockTransactionInfoStore {
@@ -164,6 +203,10 @@ pub trait ContractEventStore {
     /// If the txn_info_id does not exists in the store, return `None`.
     /// NOTICE: *don't exists* is different with *no events produced*.
     fn get_contract_events(&self, txn_info_id: HashValue) -> Result<Option<Vec<ContractEvent>>>;
+
+    /// Get the proposers of all the dao proposals created in the saved events, in address order.
+    /// The proposals of all branches are indexed, and a proposal may be already destroyed.
+    fn get_dao_proposers(&self) -> Result<Vec<AccountAddress>>;
 }
 
 pub trait TransactionStore {
@@ -185,11 +228,12 @@ pub struct Storage {
     block_info_storage: BlockInfoStorage,
     event_storage: ContractEventStorage,
     chain_info_storage: ChainInfoStorage,
+    cache_storage: Option<Arc<CacheStorage>>,
 }
 
 impl Storage {
     pub fn new(instance: StorageInstance) -> Result<Self> {
-        Ok(Self {
+        let storage = Self {
             transaction_info_storage: TransactionInfoStorage::new(instance.clone()),
             transaction_info_hash_storage: TransactionInfoHashStorage::new(instance.clone()),
             transaction_storage: TransactionStorage::new(instance.clone()),
@@ -202,8 +246,27 @@ impl Storage {
                 AccumulatorStorage::new_transaction_accumulator_storage(instance.clone()),
             block_info_storage: BlockInfoStorage::new(instance.clone()),
             event_storage: ContractEventStorage::new(instance.clone()),
+            cache_storage: instance.cache(),
             chain_info_storage: ChainInfoStorage::new(instance),
-        })
+        };
+        storage.migrate_branch_heads()?;
+        Ok(storage)
+    }
+
+    /// Move the branch heads saved in one chain info row by the previous version to their own
+    /// rows.
+    fn migrate_branch_heads(&self) -> Result<()> {
+        let legacy_branch_heads = match self.chain_info_storage.take_legacy_branch_heads()? {
+            Some(legacy_branch_heads) => legacy_branch_heads,
+            None => return Ok(()),
+        };
+        for (head_id, total_difficulty) in legacy_branch_heads {
+            if let Some(header) = self.block_storage.get_block_header_by_hash(head_id)? {
+                self.block_storage
+                    .save_branch_head(&header, total_difficulty)?;
+            }
+        }
+        Ok(())
     }
 
     pub fn get_block_accumulator_storage(&self) -> AccumulatorStorage<BlockAccumulatorStorage> {
@@ -306,6 +369,10 @@ impl BlockStore for Storage {
         self.block_storage.get_block_by_hash(block_id)
     }
 
+    fn contains_block(&self, block_id: HashValue) -> Result<bool> {
+        self.block_storage.contains_block(block_id)
+    }
+
     fn save_block_transaction_ids(
         &self,
         block_id: HashValue,
@@ -332,6 +399,10 @@ impl BlockStore for Storage {
             .put_transaction_infos(block_id, txn_info_ids)
     }
 
+    fn get_txn_info_index(&self, txn_info_id: HashValue) -> Result<Option<u64>> {
+        self.block_storage.get_txn_info_index(txn_info_id)
+    }
+
     fn save_failed_block(
         &self,
         block_id: HashValue,
@@ -349,6 +420,30 @@ impl BlockStore for Storage {
     ) -> Result<Option<(Block, Option<PeerId>, String)>> {
         self.block_storage.get_failed_block_by_id(block_id)
     }
+
+    fn get_block_ids_by_author(
+        &self,
+        author: AccountAddress,
+        max_number: Option<BlockNumber>,
+        limit: usize,
+    ) -> Result<Vec<(BlockNumber, Vec<HashValue>)>> {
+        self.block_storage
+            .get_block_ids_by_author(author, max_number, limit)
+    }
+
+    fn get_branch_heads(&self) -> Result<Vec<(HashValue, U256)>> {
+        Ok(self
+            .block_storage
+            .get_branch_heads()?
+            .into_iter()
+            .map(|(head_id, branch_head)| (head_id, branch_head.total_difficulty))
+            .collect())
+    }
+
+    fn save_branch_head(&self, block_header: &BlockHeader, total_difficulty: U256) -> Result<()> {
+        self.block_storage
+            .save_branch_head(block_header, total_difficulty)
+    }
 }
 
 impl BlockInfoStore for Storage {
@@ -410,7 +505,11 @@ impl ContractEventStore for Storage {
         &self,
         txn_info_id: HashValue,
     ) -> Result<Option<Vec<ContractEvent>>, Error> {
-        self.event_storage.get(txn_info_id)
+        self.event_storage.get_contract_events(txn_info_id)
+    }
+
+    fn get_dao_proposers(&self) -> Result<Vec<AccountAddress>, Error> {
+        self.event_storage.get_dao_proposers()
     }
 }
 
@@ -473,6 +572,9 @@ pub trait Store:
         &self,
         accumulator_type: AccumulatorStoreType,
     ) -> Arc<dyn AccumulatorTreeStore>;
+
+    /// Get the in-memory cache in front of the db, None if the storage has no cache.
+    fn get_cache_storage(&self) -> Option<Arc<CacheStorage>>;
 }
 
 pub trait IntoSuper<Super: ?Sized> {
@@ -509,4 +611,8 @@ impl Store for Storage {
             }
         }
     }
+
+    fn get_cache_storage(&self) -> Option<Arc<CacheStorage>> {
+        self.cache_storage.clone()
+    }
 }
@@ -9,7 +9,8 @@ use byteorder::{BigEndian, ReadBytesExt};
 use crypto::HashValue;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::convert::TryInto;
+use starcoin_types::account_address::AccountAddress;
+use std::convert::{TryFrom, TryInto};
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::sync::Arc;
@@ -109,6 +110,25 @@ impl StorageInstance {
             _ => None,
         }
     }
+
+    /// Get at most `limit` key value pairs of the column family whose key starts with
+    /// `key_prefix`, in descending key order, starting from `from_key` if it is set.
+    pub fn rev_scan_prefix(
+        &self,
+        prefix_name: &str,
+        key_prefix: &[u8],
+        from_key: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        match self {
+            StorageInstance::CACHE { cache } => {
+                cache.rev_scan_prefix(prefix_name, key_prefix, from_key, limit)
+            }
+            StorageInstance::DB { db } | StorageInstance::CacheAndDb { cache: _, db } => {
+                db.rev_scan_prefix(prefix_name, key_prefix, from_key, limit)
+            }
+        }
+    }
 }
 
 impl InnerStore for StorageInstance {
@@ -236,6 +256,16 @@ where
             cf: PhantomData,
         }
     }
+
+    pub fn rev_scan_prefix(
+        &self,
+        key_prefix: &[u8],
+        from_key: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.instance
+            .rev_scan_prefix(self.prefix_name, key_prefix, from_key, limit)
+    }
 }
 
 impl<CF> KVStore for InnerStorage<CF>
@@ -409,6 +439,15 @@ where
     fn get_len(&self) -> Result<u64>;
 
     fn keys(&self) -> Result<Vec<K>>;
+
+    /// Get at most `limit` pairs whose encoded key starts with `key_prefix`, in descending key
+    /// order, starting from `from_key` if it is set.
+    fn rev_scan_prefix(
+        &self,
+        key_prefix: &[u8],
+        from_key: Option<K>,
+        limit: usize,
+    ) -> Result<Vec<(K, V)>>;
 }
 
 impl KeyCodec for u64 {
@@ -432,6 +471,35 @@ impl KeyCodec for HashValue {
     }
 }
 
+impl KeyCodec for AccountAddress {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(AccountAddress::try_from(data)?)
+    }
+}
+
+impl KeyCodec for (AccountAddress, u64) {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut key = self.0.encode_key()?;
+        key.extend(self.1.encode_key()?);
+        Ok(key)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        if data.len() < AccountAddress::LENGTH {
+            bail!("Invalid address and number key length: {}", data.len());
+        }
+        let (address, number) = data.split_at(AccountAddress::LENGTH);
+        Ok((
+            AccountAddress::decode_key(address)?,
+            u64::decode_key(number)?,
+        ))
+    }
+}
+
 impl ValueCodec for HashValue {
     fn encode_value(&self) -> Result<Vec<u8>> {
         Ok(self.to_vec())
@@ -442,6 +510,17 @@ impl ValueCodec for HashValue {
     }
 }
 
+impl ValueCodec for u64 {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    #[allow(clippy::redundant_slicing)]
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok((&data[..]).read_u64::<BigEndian>()?)
+    }
+}
+
 impl ValueCodec for Vec<HashValue> {
     fn encode_value(&self) -> Result<Vec<u8>> {
         bcs_ext::to_bytes(self)
@@ -505,4 +584,23 @@ where
             .map(|key| <K>::decode_key(key.as_slice()))
             .collect()
     }
+
+    fn rev_scan_prefix(
+        &self,
+        key_prefix: &[u8],
+        from_key: Option<K>,
+        limit: usize,
+    ) -> Result<Vec<(K, V)>> {
+        let from_key = from_key.map(|key| key.encode_key()).transpose()?;
+        self.get_store()
+            .rev_scan_prefix(key_prefix, from_key.as_deref(), limit)?
+            .into_iter()
+            .map(|(key, value)| {
+                Ok((
+                    <K>::decode_key(key.as_slice())?,
+                    <V>::decode_value(value.as_slice())?,
+                ))
+            })
+            .collect()
+    }
 }
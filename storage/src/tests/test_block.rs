@@ -3,13 +3,15 @@
 
 extern crate chrono;
 
+use anyhow::Result;
 use chrono::prelude::*;
 use crypto::HashValue;
 
 use crate::cache_storage::CacheStorage;
+use crate::chain_info::ChainInfoStorage;
 use crate::db_storage::DBStorage;
-use crate::storage::StorageInstance;
-use crate::Storage;
+use crate::storage::{KVStore, StorageInstance};
+use crate::{BlockStore, Storage, BRANCH_HEAD_ROLLBACK_LIMIT};
 use starcoin_config::RocksdbConfig;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::block::{Block, BlockBody, BlockHeader, BlockHeaderExtra};
@@ -129,3 +131,236 @@ fn test_block_number() {
     assert!(block2.is_some());
     assert_eq!(block1, block2.unwrap());
 }
+
+#[test]
+fn test_block_ids_by_author() {
+    let storage = Storage::new(StorageInstance::new_cache_instance()).unwrap();
+    let author = AccountAddress::random();
+    let new_block = |number, author| {
+        let header = BlockHeader::new(
+            HashValue::random(),
+            Local::now().timestamp_nanos() as u64,
+            number,
+            author,
+            HashValue::zero(),
+            HashValue::random(),
+            HashValue::zero(),
+            0,
+            U256::zero(),
+            HashValue::random(),
+            ChainId::test(),
+            0,
+            BlockHeaderExtra::new([0u8; 4]),
+        );
+        Block::new(header, BlockBody::new(vec![], None))
+    };
+    let block1 = new_block(1, author);
+    let block2 = new_block(2, AccountAddress::random());
+    let block3 = new_block(3, author);
+    // a block of another branch at the same number.
+    let fork_block3 = new_block(3, author);
+    let block4 = new_block(4, author);
+    for block in &[&block1, &block2, &block3, &fork_block3, &block4, &block1] {
+        storage
+            .block_storage
+            .commit_block((*block).clone())
+            .unwrap();
+    }
+    assert_eq!(
+        storage
+            .block_storage
+            .get_block_ids_by_author(author, None, 10)
+            .unwrap(),
+        vec![
+            (4, vec![block4.id()]),
+            (3, vec![block3.id(), fork_block3.id()]),
+            (1, vec![block1.id()])
+        ]
+    );
+    assert_eq!(
+        storage
+            .block_storage
+            .get_block_ids_by_author(author, Some(3), 1)
+            .unwrap(),
+        vec![(3, vec![block3.id(), fork_block3.id()])]
+    );
+    assert_eq!(
+        storage
+            .block_storage
+            .get_block_ids_by_author(author, Some(2), 10)
+            .unwrap(),
+        vec![(1, vec![block1.id()])]
+    );
+    assert!(storage
+        .block_storage
+        .get_block_ids_by_author(AccountAddress::random(), None, 10)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_block_ids_by_author_on_db() {
+    let tmpdir = starcoin_config::temp_path();
+    let storage = Storage::new(StorageInstance::new_db_instance(
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default()).unwrap(),
+    ))
+    .unwrap();
+    let new_block = |number, author| {
+        let header = BlockHeader::new(
+            HashValue::random(),
+            Local::now().timestamp_nanos() as u64,
+            number,
+            author,
+            HashValue::zero(),
+            HashValue::random(),
+            HashValue::zero(),
+            0,
+            U256::zero(),
+            HashValue::random(),
+            ChainId::test(),
+            0,
+            BlockHeaderExtra::new([0u8; 4]),
+        );
+        Block::new(header, BlockBody::new(vec![], None))
+    };
+    let author = AccountAddress::random();
+    let other = AccountAddress::random();
+    let mut author_blocks = vec![];
+    for number in 0..5 {
+        let block = new_block(number, author);
+        storage.block_storage.commit_block(block.clone()).unwrap();
+        storage
+            .block_storage
+            .commit_block(new_block(number, other))
+            .unwrap();
+        author_blocks.push((number, vec![block.id()]));
+    }
+    author_blocks.reverse();
+    // the scan stops at the end of the author prefix.
+    assert_eq!(
+        storage
+            .block_storage
+            .get_block_ids_by_author(author, None, 10)
+            .unwrap(),
+        author_blocks
+    );
+    assert_eq!(
+        storage
+            .block_storage
+            .get_block_ids_by_author(author, Some(2), 2)
+            .unwrap(),
+        author_blocks[2..4].to_vec()
+    );
+}
+
+#[test]
+fn test_branch_heads() {
+    let storage = Storage::new(StorageInstance::new_cache_instance()).unwrap();
+    let new_header = |parent_hash, number| {
+        BlockHeader::new(
+            parent_hash,
+            Local::now().timestamp_nanos() as u64,
+            number,
+            AccountAddress::random(),
+            HashValue::zero(),
+            HashValue::random(),
+            HashValue::zero(),
+            0,
+            U256::one(),
+            HashValue::random(),
+            ChainId::test(),
+            0,
+            BlockHeaderExtra::new([0u8; 4]),
+        )
+    };
+    let genesis = new_header(HashValue::zero(), 0);
+    let main_1 = new_header(genesis.id(), 1);
+    let main_2 = new_header(main_1.id(), 2);
+    let branch_1 = new_header(genesis.id(), 1);
+    for (header, total_difficulty) in &[(&genesis, 1), (&main_1, 2), (&branch_1, 2), (&main_2, 3)] {
+        storage
+            .save_branch_head(header, U256::from(*total_difficulty))
+            .unwrap();
+    }
+    let mut branch_heads = storage.get_branch_heads().unwrap();
+    branch_heads.sort_by(|a, b| a.1.cmp(&b.1));
+    assert_eq!(
+        branch_heads,
+        vec![(branch_1.id(), U256::from(2)), (main_2.id(), U256::from(3))]
+    );
+
+    // the branch far behind the new head is pruned.
+    let mut parent = main_2;
+    for number in 3..=BRANCH_HEAD_ROLLBACK_LIMIT + 1 {
+        let header = new_header(parent.id(), number);
+        storage
+            .save_branch_head(&header, U256::from(number + 1))
+            .unwrap();
+        parent = header;
+    }
+    assert_eq!(storage.get_branch_heads().unwrap().len(), 2);
+    let header = new_header(parent.id(), BRANCH_HEAD_ROLLBACK_LIMIT + 2);
+    storage
+        .save_branch_head(&header, U256::from(BRANCH_HEAD_ROLLBACK_LIMIT + 3))
+        .unwrap();
+    assert_eq!(
+        storage.get_branch_heads().unwrap(),
+        vec![(header.id(), U256::from(BRANCH_HEAD_ROLLBACK_LIMIT + 3))]
+    );
+}
+
+#[test]
+fn test_migrate_branch_heads() -> Result<()> {
+    let instance = StorageInstance::new_cache_instance();
+    let storage = Storage::new(instance.clone())?;
+    let header = BlockHeader::new(
+        HashValue::random(),
+        Local::now().timestamp_nanos() as u64,
+        1,
+        AccountAddress::random(),
+        HashValue::zero(),
+        HashValue::random(),
+        HashValue::zero(),
+        0,
+        U256::one(),
+        HashValue::random(),
+        ChainId::test(),
+        0,
+        BlockHeaderExtra::new([0u8; 4]),
+    );
+    storage.block_storage.save_header(header.clone())?;
+    let legacy_branch_heads = vec![
+        (header.id(), U256::from(2)),
+        (HashValue::random(), U256::one()),
+    ];
+    let chain_info_storage = ChainInfoStorage::new(instance.clone());
+    chain_info_storage.put(
+        b"branch_heads".to_vec(),
+        bcs_ext::to_bytes(&legacy_branch_heads)?,
+    )?;
+
+    // the head without a saved header is dropped.
+    let storage = Storage::new(instance)?;
+    assert_eq!(
+        storage.get_branch_heads()?,
+        vec![(header.id(), U256::from(2))]
+    );
+    assert!(chain_info_storage.take_legacy_branch_heads()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_txn_info_index() -> Result<()> {
+    let storage = Storage::new(StorageInstance::new_cache_instance())?;
+    let txn_info_ids = vec![
+        HashValue::random(),
+        HashValue::random(),
+        HashValue::random(),
+    ];
+    storage.save_block_txn_info_ids(HashValue::random(), txn_info_ids.clone())?;
+    for (index, txn_info_id) in txn_info_ids.into_iter().enumerate() {
+        assert_eq!(storage.get_txn_info_index(txn_info_id)?, Some(index as u64));
+    }
+    assert!(storage.get_txn_info_index(HashValue::random())?.is_none());
+    Ok(())
+}
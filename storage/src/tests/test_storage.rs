@@ -7,12 +7,18 @@ use crate::cache_storage::CacheStorage;
 use crate::db_storage::DBStorage;
 use crate::storage::{CodecKVStore, InnerStore, StorageInstance, ValueCodec, CACHE_NONE_OBJECT};
 use crate::{
-    BlockTransactionInfoStore, Storage, DEFAULT_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME,
-    VEC_PREFIX_NAME,
+    BlockTransactionInfoStore, ContractEventStore, Storage, DEFAULT_PREFIX_NAME,
+    TRANSACTION_INFO_PREFIX_NAME, VEC_PREFIX_NAME,
 };
 use anyhow::Result;
 use crypto::HashValue;
 use starcoin_config::RocksdbConfig;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::{genesis_address, ProposalCreatedEvent};
+use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::event::EventKey;
+use starcoin_types::language_storage::TypeTag;
+use starcoin_types::move_resource::MoveResource;
 use starcoin_types::transaction::{BlockTransactionInfo, TransactionInfo};
 use starcoin_types::vm_error::KeptVMStatus;
 
@@ -218,3 +224,53 @@ fn test_missing_key_handle() -> Result<()> {
     assert_eq!(contains, false);
     Ok(())
 }
+
+#[test]
+fn test_cache_hits_and_misses() -> Result<()> {
+    let cache = CacheStorage::new();
+    let key = HashValue::random();
+    assert!(cache.get(DEFAULT_PREFIX_NAME, key.to_vec())?.is_none());
+    assert_eq!((cache.hits(), cache.misses()), (0, 1));
+
+    cache.put(DEFAULT_PREFIX_NAME, key.to_vec(), key.to_vec())?;
+    assert_eq!(
+        cache.get(DEFAULT_PREFIX_NAME, key.to_vec())?,
+        Some(key.to_vec())
+    );
+    assert!(cache.get_obj(DEFAULT_PREFIX_NAME, key.to_vec())?.is_some());
+    assert_eq!((cache.hits(), cache.misses()), (2, 1));
+    Ok(())
+}
+
+#[test]
+fn test_dao_proposers() -> Result<()> {
+    let storage = Storage::new(StorageInstance::new_cache_instance())?;
+    let key = EventKey::new_from_address(&genesis_address(), 0);
+    let proposal_created = |proposal_id, proposer| -> Result<ContractEvent> {
+        Ok(ContractEvent::new(
+            key,
+            proposal_id,
+            ProposalCreatedEvent::type_tag(),
+            bcs_ext::to_bytes(&ProposalCreatedEvent {
+                proposal_id,
+                proposer,
+            })?,
+        ))
+    };
+    let proposer_a = AccountAddress::random();
+    let proposer_b = AccountAddress::random();
+    storage.save_contract_events(
+        HashValue::random(),
+        vec![
+            proposal_created(0, proposer_a)?,
+            ContractEvent::new(key, 1, TypeTag::U64, bcs_ext::to_bytes(&1u64)?),
+        ],
+    )?;
+    storage.save_contract_events(HashValue::random(), vec![proposal_created(1, proposer_b)?])?;
+    storage.save_contract_events(HashValue::random(), vec![proposal_created(2, proposer_a)?])?;
+
+    let mut expect_proposers = vec![proposer_a, proposer_b];
+    expect_proposers.sort();
+    assert_eq!(storage.get_dao_proposers()?, expect_proposers);
+    Ok(())
+}
@@ -95,6 +95,32 @@ impl ServiceRequest for SyncStartRequest {
     type Response = Result<()>;
 }
 
+/// Rewind the main head to the block of the number, only supported on the test or dev network.
+#[derive(Debug, Clone)]
+pub struct RewindToRequest {
+    pub number: BlockNumber,
+}
+
+impl ServiceRequest for RewindToRequest {
+    type Response = Result<()>;
+}
+
+/// Stop connecting the new blocks until resumed, the mined, peer and synced blocks are queued.
+#[derive(Debug, Clone)]
+pub struct PauseProcessingRequest;
+
+impl ServiceRequest for PauseProcessingRequest {
+    type Response = Result<()>;
+}
+
+/// Connect the blocks queued while paused, and connect the new blocks again.
+#[derive(Debug, Clone)]
+pub struct ResumeProcessingRequest;
+
+impl ServiceRequest for ResumeProcessingRequest {
+    type Response = Result<()>;
+}
+
 #[derive(Debug, Clone)]
 pub struct PeerScoreRequest;
 
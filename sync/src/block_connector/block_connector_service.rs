@@ -10,17 +10,48 @@ use logger::prelude::*;
 use network::NetworkServiceRef;
 use network_api::PeerProvider;
 use starcoin_chain_api::{ConnectBlockError, WriteableChainService};
-use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_crypto::HashValue;
+use starcoin_service_registry::{
+    ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler,
+};
 use starcoin_storage::{BlockStore, Storage};
-use starcoin_sync_api::PeerNewBlock;
+use starcoin_sync_api::{
+    PauseProcessingRequest, PeerNewBlock, ResumeProcessingRequest, RewindToRequest,
+};
 use starcoin_types::sync_status::SyncStatus;
-use starcoin_types::system_events::{MinedBlock, SyncStatusChangeEvent};
+use starcoin_types::system_events::{BlockReceived, MinedBlock, SyncStatusChangeEvent};
+use std::collections::VecDeque;
 use std::sync::Arc;
 use txpool::TxPoolService;
 
+/// The max count of the blocks queued while the block processing is paused.
+const MAX_PAUSED_BLOCKS: usize = 1024;
+
+/// A new block which arrives while the block processing is paused.
+#[derive(Debug)]
+enum PausedBlock {
+    Mined(MinedBlock),
+    Peer(PeerNewBlock),
+    Synced(BlockConnectedEvent),
+}
+
+impl PausedBlock {
+    fn id(&self) -> HashValue {
+        match self {
+            PausedBlock::Mined(MinedBlock(block)) => block.id(),
+            PausedBlock::Peer(msg) => msg.get_block().id(),
+            PausedBlock::Synced(msg) => msg.block.id(),
+        }
+    }
+}
+
 pub struct BlockConnectorService {
     chain_service: WriteBlockChainService<TxPoolService>,
     sync_status: Option<SyncStatus>,
+    /// The blocks queued while the block processing is paused, `None` if not paused.
+    paused_blocks: Option<VecDeque<PausedBlock>>,
+    /// The count of the blocks dropped since paused, because the queue is full.
+    dropped_paused_blocks: u64,
 }
 
 impl BlockConnectorService {
@@ -28,55 +59,79 @@ impl BlockConnectorService {
         Self {
             chain_service,
             sync_status: None,
+            paused_blocks: None,
+            dropped_paused_blocks: 0,
         }
     }
 
-    pub fn is_synced(&self) -> bool {
-        match self.sync_status.as_ref() {
-            Some(sync_status) => sync_status.is_synced(),
-            None => false,
+    fn pause_processing(&mut self) {
+        if self.paused_blocks.is_none() {
+            info!("Pause the block processing of BlockConnectorService.");
+            self.paused_blocks = Some(VecDeque::new());
         }
     }
-}
 
-impl ServiceFactory<Self> for BlockConnectorService {
-    fn create(ctx: &mut ServiceContext<BlockConnectorService>) -> Result<BlockConnectorService> {
-        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
-        let bus = ctx.bus_ref().clone();
-        let txpool = ctx.get_shared::<TxPoolService>()?;
-        let storage = ctx.get_shared::<Arc<Storage>>()?;
-        let startup_info = storage
-            .get_startup_info()?
-            .ok_or_else(|| format_err!("Startup info should exist."))?;
-        let chain_service =
-            WriteBlockChainService::new(config, startup_info, storage, txpool, bus)?;
+    fn resume_processing(&mut self, ctx: &mut ServiceContext<Self>) {
+        if let Some(paused_blocks) = self.paused_blocks.take() {
+            info!(
+                "Resume the block processing of BlockConnectorService, {} blocks queued, {} blocks dropped.",
+                paused_blocks.len(),
+                self.dropped_paused_blocks
+            );
+            for block in paused_blocks {
+                match block {
+                    PausedBlock::Mined(msg) => self.connect_mined_block(msg, ctx),
+                    PausedBlock::Peer(msg) => self.connect_peer_block(msg, ctx),
+                    PausedBlock::Synced(msg) => self.connect_synced_block(msg),
+                }
+            }
+            if self.dropped_paused_blocks > 0 {
+                self.dropped_paused_blocks = 0;
+                // the dropped blocks are synced from the peers again.
+                if let Ok(sync_service) = ctx.service_ref::<SyncService>() {
+                    let _ = sync_service.notify(CheckSyncEvent::default());
+                }
+            }
+        }
+    }
 
-        Ok(Self::new(chain_service))
+    fn is_paused(&self) -> bool {
+        self.paused_blocks.is_some()
     }
-}
 
-impl ActorService for BlockConnectorService {
-    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
-        //TODO figure out a more suitable value.
-        ctx.set_mailbox_capacity(1024);
-        ctx.subscribe::<SyncStatusChangeEvent>();
-        ctx.subscribe::<MinedBlock>();
-        Ok(())
+    fn queue_paused_block(&mut self, block: PausedBlock) {
+        let paused_blocks = match self.paused_blocks.as_mut() {
+            Some(paused_blocks) => paused_blocks,
+            None => return,
+        };
+        if paused_blocks.len() >= MAX_PAUSED_BLOCKS {
+            warn!(
+                "The block processing is paused and {} blocks are queued, drop block {}, it is synced at resume.",
+                paused_blocks.len(),
+                block.id()
+            );
+            self.dropped_paused_blocks = self.dropped_paused_blocks.saturating_add(1);
+        } else {
+            paused_blocks.push_back(block);
+        }
     }
 
-    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
-        ctx.unsubscribe::<SyncStatusChangeEvent>();
-        ctx.unsubscribe::<MinedBlock>();
-        Ok(())
+    pub fn is_synced(&self) -> bool {
+        match self.sync_status.as_ref() {
+            Some(sync_status) => sync_status.is_synced(),
+            None => false,
+        }
     }
-}
 
-impl EventHandler<Self, BlockConnectedEvent> for BlockConnectorService {
-    fn handle_event(
-        &mut self,
-        msg: BlockConnectedEvent,
-        _ctx: &mut ServiceContext<BlockConnectorService>,
-    ) {
+    fn broadcast_block_received(&self, block_id: HashValue, ctx: &mut ServiceContext<Self>) {
+        let received_at = self.chain_service.get_main().time_service().now_millis();
+        ctx.broadcast(BlockReceived {
+            block_id,
+            received_at,
+        });
+    }
+
+    fn connect_synced_block(&mut self, msg: BlockConnectedEvent) {
         //because this block has execute at sync task, so just try connect to select head chain.
         //TODO refactor connect and execute
         let block = msg.block;
@@ -84,13 +139,12 @@ impl EventHandler<Self, BlockConnectedEvent> for BlockConnectorService {
             error!("Process connected block error: {:?}", e);
         }
     }
-}
 
-impl EventHandler<Self, MinedBlock> for BlockConnectorService {
-    fn handle_event(&mut self, msg: MinedBlock, _ctx: &mut ServiceContext<Self>) {
+    fn connect_mined_block(&mut self, msg: MinedBlock, ctx: &mut ServiceContext<Self>) {
         let MinedBlock(new_block) = msg;
         let id = new_block.header().id();
         debug!("try connect mined block: {}", id);
+        self.broadcast_block_received(id, ctx);
 
         match self.chain_service.try_connect(new_block.as_ref().clone()) {
             Ok(_) => debug!("Process mined block {} success.", id),
@@ -99,21 +153,10 @@ impl EventHandler<Self, MinedBlock> for BlockConnectorService {
             }
         }
     }
-}
-
-impl EventHandler<Self, SyncStatusChangeEvent> for BlockConnectorService {
-    fn handle_event(&mut self, msg: SyncStatusChangeEvent, _ctx: &mut ServiceContext<Self>) {
-        self.sync_status = Some(msg.0);
-    }
-}
 
-impl EventHandler<Self, PeerNewBlock> for BlockConnectorService {
-    fn handle_event(&mut self, msg: PeerNewBlock, ctx: &mut ServiceContext<Self>) {
-        if !self.is_synced() {
-            debug!("[connector] Ignore PeerNewBlock event because the node has not been synchronized yet.");
-            return;
-        }
+    fn connect_peer_block(&mut self, msg: PeerNewBlock, ctx: &mut ServiceContext<Self>) {
         let peer_id = msg.get_peer_id();
+        self.broadcast_block_received(msg.get_block().id(), ctx);
         if let Err(e) = self.chain_service.try_connect(msg.get_block().clone()) {
             match e.downcast::<ConnectBlockError>() {
                 Ok(connect_error) => {
@@ -164,3 +207,107 @@ impl EventHandler<Self, PeerNewBlock> for BlockConnectorService {
         }
     }
 }
+
+impl ServiceFactory<Self> for BlockConnectorService {
+    fn create(ctx: &mut ServiceContext<BlockConnectorService>) -> Result<BlockConnectorService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let bus = ctx.bus_ref().clone();
+        let txpool = ctx.get_shared::<TxPoolService>()?;
+        let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let startup_info = storage
+            .get_startup_info()?
+            .ok_or_else(|| format_err!("Startup info should exist."))?;
+        let chain_service =
+            WriteBlockChainService::new(config, startup_info, storage, txpool, bus)?;
+
+        Ok(Self::new(chain_service))
+    }
+}
+
+impl ActorService for BlockConnectorService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        //TODO figure out a more suitable value.
+        ctx.set_mailbox_capacity(1024);
+        ctx.subscribe::<SyncStatusChangeEvent>();
+        ctx.subscribe::<MinedBlock>();
+        Ok(())
+    }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<SyncStatusChangeEvent>();
+        ctx.unsubscribe::<MinedBlock>();
+        Ok(())
+    }
+}
+
+impl EventHandler<Self, BlockConnectedEvent> for BlockConnectorService {
+    fn handle_event(
+        &mut self,
+        msg: BlockConnectedEvent,
+        _ctx: &mut ServiceContext<BlockConnectorService>,
+    ) {
+        if self.is_paused() {
+            self.queue_paused_block(PausedBlock::Synced(msg));
+        } else {
+            self.connect_synced_block(msg);
+        }
+    }
+}
+
+impl EventHandler<Self, MinedBlock> for BlockConnectorService {
+    fn handle_event(&mut self, msg: MinedBlock, ctx: &mut ServiceContext<Self>) {
+        if self.is_paused() {
+            self.queue_paused_block(PausedBlock::Mined(msg));
+        } else {
+            self.connect_mined_block(msg, ctx);
+        }
+    }
+}
+
+impl ServiceHandler<Self, RewindToRequest> for BlockConnectorService {
+    fn handle(&mut self, msg: RewindToRequest, _ctx: &mut ServiceContext<Self>) -> Result<()> {
+        self.chain_service.rewind_to(msg.number)
+    }
+}
+
+impl ServiceHandler<Self, PauseProcessingRequest> for BlockConnectorService {
+    fn handle(
+        &mut self,
+        _msg: PauseProcessingRequest,
+        _ctx: &mut ServiceContext<Self>,
+    ) -> Result<()> {
+        self.pause_processing();
+        Ok(())
+    }
+}
+
+impl ServiceHandler<Self, ResumeProcessingRequest> for BlockConnectorService {
+    fn handle(
+        &mut self,
+        _msg: ResumeProcessingRequest,
+        ctx: &mut ServiceContext<Self>,
+    ) -> Result<()> {
+        self.resume_processing(ctx);
+        Ok(())
+    }
+}
+
+impl EventHandler<Self, SyncStatusChangeEvent> for BlockConnectorService {
+    fn handle_event(&mut self, msg: SyncStatusChangeEvent, _ctx: &mut ServiceContext<Self>) {
+        self.sync_status = Some(msg.0);
+    }
+}
+
+impl EventHandler<Self, PeerNewBlock> for BlockConnectorService {
+    fn handle_event(&mut self, msg: PeerNewBlock, ctx: &mut ServiceContext<Self>) {
+        if !self.is_synced() {
+            debug!("[connector] Ignore PeerNewBlock event because the node has not been synchronized yet.");
+            return;
+        }
+        if self.is_paused() {
+            self.queue_paused_block(PausedBlock::Peer(msg));
+        } else {
+            self.connect_peer_block(msg, ctx);
+        }
+    }
+}
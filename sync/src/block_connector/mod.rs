@@ -3,6 +3,7 @@
 
 mod block_connector_service;
 mod metrics;
+mod orphan_block_pool;
 #[cfg(test)]
 mod test_illegal_block;
 #[cfg(test)]
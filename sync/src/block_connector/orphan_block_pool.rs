@@ -0,0 +1,75 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use starcoin_crypto::HashValue;
+use starcoin_types::block::Block;
+use std::collections::{HashMap, VecDeque};
+
+pub const DEFAULT_MAX_ORPHAN_BLOCKS: usize = 256;
+
+/// Blocks whose parent is not connected yet, keyed by the parent id.
+/// The oldest block is evicted when the pool is full.
+pub struct OrphanBlockPool {
+    blocks: HashMap<HashValue, Vec<Block>>,
+    /// The (parent id, block id) of the buffered blocks, in the order they arrive.
+    arrival: VecDeque<(HashValue, HashValue)>,
+    capacity: usize,
+}
+
+impl OrphanBlockPool {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            blocks: HashMap::new(),
+            arrival: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.arrival.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.arrival.is_empty()
+    }
+
+    pub fn contains(&self, block_id: HashValue) -> bool {
+        self.arrival.iter().any(|(_, id)| *id == block_id)
+    }
+
+    /// Buffers the block until its parent is connected,
+    /// return false if the block is already in the pool.
+    pub fn insert(&mut self, block: Block) -> bool {
+        let block_id = block.id();
+        if self.capacity == 0 || self.contains(block_id) {
+            return false;
+        }
+        while self.arrival.len() >= self.capacity {
+            if let Some((parent_id, evicted_id)) = self.arrival.pop_front() {
+                self.remove(parent_id, evicted_id);
+            }
+        }
+        let parent_id = block.header().parent_hash();
+        self.arrival.push_back((parent_id, block_id));
+        self.blocks.entry(parent_id).or_default().push(block);
+        true
+    }
+
+    /// Takes all the buffered children of the given parent out of the pool.
+    pub fn take_children(&mut self, parent_id: HashValue) -> Vec<Block> {
+        let children = self.blocks.remove(&parent_id).unwrap_or_default();
+        if !children.is_empty() {
+            self.arrival.retain(|(parent, _)| *parent != parent_id);
+        }
+        children
+    }
+
+    fn remove(&mut self, parent_id: HashValue, block_id: HashValue) {
+        if let Some(children) = self.blocks.get_mut(&parent_id) {
+            children.retain(|block| block.id() != block_id);
+            if children.is_empty() {
+                self.blocks.remove(&parent_id);
+            }
+        }
+    }
+}
@@ -1,21 +1,31 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 #![allow(clippy::integer_arithmetic)]
-use crate::block_connector::WriteBlockChainService;
+use crate::block_connector::orphan_block_pool::OrphanBlockPool;
+use crate::block_connector::{BlockConnectorService, WriteBlockChainService};
 use config::NodeConfig;
 use consensus::Consensus;
+use futures::StreamExt;
 use starcoin_account_api::AccountInfo;
 use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_chain_api::ConnectBlockError;
+use starcoin_chain_mock::MockChain;
 use starcoin_chain_service::WriteableChainService;
+use starcoin_crypto::HashValue;
 use starcoin_genesis::Genesis as StarcoinGenesis;
-use starcoin_service_registry::bus::BusService;
+use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::{RegistryAsyncService, RegistryService};
-use starcoin_storage::Store;
+use starcoin_storage::block_info::BlockInfoStore;
+use starcoin_storage::{BlockStore, Store};
+use starcoin_sync_api::{PauseProcessingRequest, ResumeProcessingRequest};
 use starcoin_txpool_mock_service::MockTxPoolService;
 use starcoin_types::block::Block;
 use starcoin_types::startup_info::StartupInfo;
+use starcoin_types::system_events::{ChainReorg, MinedBlock, NewHeadBlock};
 use starcoin_vm_types::time::TimeService;
 use std::sync::Arc;
+use std::time::Duration;
+use txpool::TxPoolService;
 
 pub async fn create_writeable_block_chain() -> (
     WriteBlockChainService<MockTxPoolService>,
@@ -170,6 +180,116 @@ async fn test_block_chain_forks() {
     );
 }
 
+#[stest::test]
+async fn test_connect_orphan_block() {
+    let (mut writeable_block_chain_service, _, storage) = create_writeable_block_chain().await;
+    let (mut other_block_chain_service, other_node_config, _) =
+        create_writeable_block_chain().await;
+    gen_blocks(
+        2,
+        &mut other_block_chain_service,
+        other_node_config.net().time_service().as_ref(),
+    );
+    let orphan_block = other_block_chain_service.get_main().head_block();
+    let parent_id = orphan_block.header().parent_hash();
+    let genesis_id = writeable_block_chain_service
+        .get_main()
+        .current_header()
+        .id();
+
+    // the parent of the orphan block is unknown, it is rejected as a future block.
+    let err = writeable_block_chain_service
+        .try_connect(orphan_block.clone())
+        .unwrap_err();
+    match err.downcast::<ConnectBlockError>() {
+        Ok(ConnectBlockError::FutureBlock(block)) => {
+            assert_eq!(block.id(), orphan_block.id());
+            assert_eq!(block.header().parent_hash(), parent_id);
+        }
+        other => panic!("expect future block error, but got: {:?}", other),
+    }
+
+    // no state is applied for the orphan block.
+    assert!(storage
+        .get_block_by_hash(orphan_block.id())
+        .unwrap()
+        .is_none());
+    assert!(storage.get_block_info(orphan_block.id()).unwrap().is_none());
+    assert_eq!(
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .id(),
+        genesis_id
+    );
+}
+
+#[stest::test]
+async fn test_connect_orphan_block_after_parent() {
+    let (mut writeable_block_chain_service, node_config, _) = create_writeable_block_chain().await;
+    let mut mock_chain = MockChain::new(node_config.net().clone()).unwrap();
+    let mut blocks = vec![];
+    for _i in 0..3 {
+        let block = mock_chain.produce().unwrap();
+        mock_chain.apply(block.clone()).unwrap();
+        blocks.push(block);
+    }
+
+    // the children arrive before their parent, and are buffered.
+    for block in blocks.iter().skip(1).rev() {
+        assert!(writeable_block_chain_service
+            .try_connect(block.clone())
+            .is_err());
+    }
+    assert_eq!(writeable_block_chain_service.orphan_blocks().len(), 2);
+    assert_eq!(
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .number(),
+        0
+    );
+
+    writeable_block_chain_service
+        .try_connect(blocks[0].clone())
+        .unwrap();
+    assert!(writeable_block_chain_service.orphan_blocks().is_empty());
+    assert_eq!(
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .id(),
+        blocks[2].id()
+    );
+}
+
+#[stest::test]
+async fn test_orphan_block_pool_evict_oldest() {
+    let (_, node_config, _) = create_writeable_block_chain().await;
+    let mut mock_chain = MockChain::new(node_config.net().clone()).unwrap();
+    let mut blocks = vec![];
+    for _i in 0..3 {
+        let block = mock_chain.produce().unwrap();
+        mock_chain.apply(block.clone()).unwrap();
+        blocks.push(block);
+    }
+
+    let mut pool = OrphanBlockPool::new(2);
+    for block in &blocks {
+        assert!(pool.insert(block.clone()));
+    }
+    assert!(!pool.insert(blocks[2].clone()));
+    assert_eq!(pool.len(), 2);
+    assert!(!pool.contains(blocks[0].id()));
+    assert!(pool
+        .take_children(blocks[0].header().parent_hash())
+        .is_empty());
+    let children = pool.take_children(blocks[1].header().parent_hash());
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].id(), blocks[1].id());
+    assert_eq!(pool.len(), 1);
+}
+
 #[stest::test]
 async fn test_block_chain_switch_main() {
     let times = 10;
@@ -203,3 +323,223 @@ async fn test_block_chain_switch_main() {
         2 * times
     );
 }
+
+#[stest::test]
+async fn test_new_head_block_event() {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let (storage, chain_info, _) = StarcoinGenesis::init_storage_for_test(node_config.net())
+        .expect("init storage by genesis fail.");
+    let registry = RegistryService::launch();
+    let bus = registry.service_ref::<BusService>().await.unwrap();
+    let mut new_heads = bus.channel::<NewHeadBlock>().await.unwrap();
+    let mut writeable_block_chain_service = WriteBlockChainService::new(
+        node_config.clone(),
+        StartupInfo::new(chain_info.head().id()),
+        storage,
+        MockTxPoolService::new(),
+        bus,
+    )
+    .unwrap();
+    let net = node_config.net();
+    gen_blocks(
+        3,
+        &mut writeable_block_chain_service,
+        net.time_service().as_ref(),
+    );
+    for _ in 0..3 {
+        new_heads
+            .next()
+            .await
+            .expect("new head event should exist.");
+    }
+
+    // a linear extension changes the head.
+    gen_blocks(
+        1,
+        &mut writeable_block_chain_service,
+        net.time_service().as_ref(),
+    );
+    let event = new_heads
+        .next()
+        .await
+        .expect("new head event should exist.");
+    assert_eq!(
+        event.0.block().id(),
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .id()
+    );
+
+    // a stale side block does not change the head.
+    gen_fork_block_chain(0, node_config, 1, &mut writeable_block_chain_service);
+    tokio::time::delay_for(Duration::from_millis(200)).await;
+    assert!(new_heads.try_next().is_err());
+}
+
+#[stest::test]
+async fn test_chain_reorg_event() {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let (storage, chain_info, _) = StarcoinGenesis::init_storage_for_test(node_config.net())
+        .expect("init storage by genesis fail.");
+    let registry = RegistryService::launch();
+    let bus = registry.service_ref::<BusService>().await.unwrap();
+    let mut reorgs = bus.channel::<ChainReorg>().await.unwrap();
+    let mut writeable_block_chain_service = WriteBlockChainService::new(
+        node_config.clone(),
+        StartupInfo::new(chain_info.head().id()),
+        storage,
+        MockTxPoolService::new(),
+        bus,
+    )
+    .unwrap();
+    let net = node_config.net();
+    gen_blocks(
+        3,
+        &mut writeable_block_chain_service,
+        net.time_service().as_ref(),
+    );
+    let main_ids = |service: &WriteBlockChainService<MockTxPoolService>| -> Vec<HashValue> {
+        (1..=service.get_main().current_header().number())
+            .map(|number| {
+                service
+                    .get_main()
+                    .get_header_by_number(number)
+                    .unwrap()
+                    .unwrap()
+                    .id()
+            })
+            .collect()
+    };
+    let old_main = main_ids(&writeable_block_chain_service);
+
+    gen_fork_block_chain(1, node_config, 4, &mut writeable_block_chain_service);
+    let new_main = main_ids(&writeable_block_chain_service);
+    // the heavier branch wins.
+    assert_eq!(new_main.len(), 5);
+    assert_eq!(new_main[0], old_main[0]);
+
+    let reorg = reorgs.next().await.expect("reorg event should exist.");
+    assert_eq!(reorg.from, old_main[2]);
+    assert_eq!(reorg.retracted, old_main[1..].to_vec());
+    assert!(!reorg.applied.is_empty());
+    assert_eq!(reorg.applied, new_main[1..=reorg.applied.len()].to_vec());
+    assert_eq!(reorg.applied.last(), Some(&reorg.to));
+    // the later fork blocks extend the new main head.
+    tokio::time::delay_for(Duration::from_millis(200)).await;
+    assert!(reorgs.try_next().is_err());
+}
+
+#[stest::test]
+async fn test_rewind_to() {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let (storage, chain_info, _) = StarcoinGenesis::init_storage_for_test(node_config.net())
+        .expect("init storage by genesis fail.");
+    let registry = RegistryService::launch();
+    let bus = registry.service_ref::<BusService>().await.unwrap();
+    let mut writeable_block_chain_service = WriteBlockChainService::new(
+        node_config.clone(),
+        StartupInfo::new(chain_info.head().id()),
+        storage.clone(),
+        MockTxPoolService::new(),
+        bus.clone(),
+    )
+    .unwrap();
+    let net = node_config.net();
+    gen_blocks(
+        10,
+        &mut writeable_block_chain_service,
+        net.time_service().as_ref(),
+    );
+    let old_head = writeable_block_chain_service.get_main().current_header();
+    let block_5 = writeable_block_chain_service
+        .get_main()
+        .get_header_by_number(5)
+        .unwrap()
+        .unwrap();
+    let block_6 = writeable_block_chain_service
+        .get_main()
+        .get_header_by_number(6)
+        .unwrap()
+        .unwrap();
+    let mut new_heads = bus.channel::<NewHeadBlock>().await.unwrap();
+    let mut reorgs = bus.channel::<ChainReorg>().await.unwrap();
+
+    writeable_block_chain_service.rewind_to(5).unwrap();
+    assert_eq!(
+        writeable_block_chain_service.get_main().current_header(),
+        block_5
+    );
+    assert_eq!(
+        storage.get_startup_info().unwrap().map(|info| info.main),
+        Some(block_5.id())
+    );
+    // the blocks above are kept as orphans.
+    assert!(storage.get_block_by_hash(block_6.id()).unwrap().is_some());
+
+    // the readers follow the rewind by the new head and reorg events.
+    let event = new_heads
+        .next()
+        .await
+        .expect("new head event should exist.");
+    assert_eq!(event.0.block().id(), block_5.id());
+    let reorg = reorgs.next().await.expect("reorg event should exist.");
+    assert_eq!(reorg.from, old_head.id());
+    assert_eq!(reorg.to, block_5.id());
+    assert_eq!(reorg.retracted.len(), 5);
+    assert!(reorg.applied.is_empty());
+
+    // can not rewind to a block not lower than the head.
+    assert!(writeable_block_chain_service.rewind_to(5).is_err());
+}
+
+#[stest::test]
+async fn test_pause_and_resume_processing() {
+    let node_config = Arc::new(NodeConfig::random_for_test());
+    let (storage, chain_info, _) = StarcoinGenesis::init_storage_for_test(node_config.net())
+        .expect("init storage by genesis fail.");
+    let registry = RegistryService::launch();
+    registry.put_shared(node_config.clone()).await.unwrap();
+    registry.put_shared(storage.clone()).await.unwrap();
+    let txpool = TxPoolService::new(
+        node_config.clone(),
+        storage.clone(),
+        chain_info.head().clone(),
+    );
+    registry.put_shared(txpool).await.unwrap();
+    let connector = registry.register::<BlockConnectorService>().await.unwrap();
+    let bus = registry.service_ref::<BusService>().await.unwrap();
+    let mut new_heads = bus.channel::<NewHeadBlock>().await.unwrap();
+
+    connector
+        .send(PauseProcessingRequest)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut mock_chain = MockChain::new(node_config.net().clone()).unwrap();
+    for _i in 0..2 {
+        let block = mock_chain.produce().unwrap();
+        mock_chain.apply(block.clone()).unwrap();
+        bus.broadcast(MinedBlock(Arc::new(block))).unwrap();
+    }
+    // the mined blocks are queued, the main head is not changed.
+    tokio::time::delay_for(Duration::from_millis(200)).await;
+    assert!(new_heads.try_next().is_err());
+    let startup_info = storage.get_startup_info().unwrap().unwrap();
+    assert_eq!(startup_info.main, chain_info.head().id());
+
+    // the queued blocks are connected at resume.
+    connector
+        .send(ResumeProcessingRequest)
+        .await
+        .unwrap()
+        .unwrap();
+    for _i in 0..2 {
+        new_heads
+            .next()
+            .await
+            .expect("new head event should exist.");
+    }
+    let startup_info = storage.get_startup_info().unwrap().unwrap();
+    assert_eq!(startup_info.main, mock_chain.head().current_header().id());
+}
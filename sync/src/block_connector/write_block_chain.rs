@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::metrics::WRITE_BLOCK_CHAIN_METRICS;
-use anyhow::{format_err, Result};
+use super::orphan_block_pool::{OrphanBlockPool, DEFAULT_MAX_ORPHAN_BLOCKS};
+use anyhow::{ensure, format_err, Result};
 use config::NodeConfig;
 use logger::prelude::*;
 use starcoin_chain::BlockChain;
@@ -13,9 +14,9 @@ use starcoin_service_registry::ServiceRef;
 use starcoin_storage::Store;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::{
-    block::{Block, BlockHeader, ExecutedBlock},
+    block::{Block, BlockHeader, BlockNumber, ExecutedBlock},
     startup_info::StartupInfo,
-    system_events::{NewBranch, NewHeadBlock},
+    system_events::{ChainReorg, NewBranch, NewHeadBlock},
 };
 use starcoin_vm_types::on_chain_config::GlobalTimeOnChain;
 use std::sync::Arc;
@@ -32,6 +33,7 @@ where
     storage: Arc<dyn Store>,
     txpool: P,
     bus: ServiceRef<BusService>,
+    orphan_blocks: OrphanBlockPool,
 }
 
 impl<P> WriteableChainService for WriteBlockChainService<P>
@@ -39,7 +41,21 @@ where
     P: TxPoolSyncService + 'static,
 {
     fn try_connect(&mut self, block: Block) -> Result<()> {
-        self.connect_inner(block)
+        let block_id = block.id();
+        match self.connect_inner(block) {
+            Ok(()) => {
+                self.connect_orphan_children(block_id);
+                Ok(())
+            }
+            Err(e) => match e.downcast::<ConnectBlockError>() {
+                Ok(ConnectBlockError::FutureBlock(block)) => {
+                    self.orphan_blocks.insert(block.as_ref().clone());
+                    Err(ConnectBlockError::FutureBlock(block).into())
+                }
+                Ok(e) => Err(e.into()),
+                Err(e) => Err(e),
+            },
+        }
     }
 }
 
@@ -63,6 +79,7 @@ where
             storage,
             txpool,
             bus,
+            orphan_blocks: OrphanBlockPool::new(DEFAULT_MAX_ORPHAN_BLOCKS),
         })
     }
 
@@ -124,6 +141,17 @@ where
                 } else {
                     (1, vec![block], 0, vec![])
                 };
+            // the retracted blocks are capped by MAX_ROLL_BACK_BLOCK.
+            let reorg = if retracted_blocks.is_empty() {
+                None
+            } else {
+                Some(ChainReorg {
+                    from: self.main.current_header().id(),
+                    to: block_header.id(),
+                    retracted: retracted_blocks.iter().map(|block| block.id()).collect(),
+                    applied: enacted_blocks.iter().map(|block| block.id()).collect(),
+                })
+            };
             self.main = new_branch;
 
             self.do_new_head(
@@ -133,6 +161,9 @@ where
                 retracted_count,
                 retracted_blocks,
             )?;
+            if let Some(reorg) = reorg {
+                self.broadcast_chain_reorg(reorg);
+            }
         } else {
             //send new branch event
             self.broadcast_new_branch(executed_block);
@@ -170,6 +201,46 @@ where
         Ok(())
     }
 
+    /// Rewind the main head to the block of the number, the blocks above are kept in the storage
+    /// as orphans, and their txns are returned to the txpool. Only supported on the test or dev
+    /// network.
+    pub fn rewind_to(&mut self, number: BlockNumber) -> Result<()> {
+        self.config.net().assert_test_or_dev()?;
+        let old_head = self.main.current_header();
+        ensure!(
+            number < old_head.number(),
+            "Can not rewind to block {}, it is not lower than the head {}",
+            number,
+            old_head.number()
+        );
+        let block_id = self.main.get_hash_by_number_ensure(number)?;
+        let retracted_blocks =
+            self.find_blocks_until(old_head.id(), block_id, MAX_ROLL_BACK_BLOCK)?;
+        self.main = BlockChain::new(
+            self.config.net().time_service(),
+            block_id,
+            self.storage.clone(),
+        )?;
+        let executed_block =
+            ExecutedBlock::new(self.main.head_block(), self.main.status().info().clone());
+        self.update_startup_info(executed_block.header())?;
+        let reorg = ChainReorg {
+            from: old_head.id(),
+            to: block_id,
+            retracted: retracted_blocks.iter().map(|block| block.id()).collect(),
+            applied: vec![],
+        };
+        self.commit_2_txpool(vec![], retracted_blocks);
+        info!(
+            "[chain] Rewind the main head from {} to {}",
+            old_head.number(),
+            number
+        );
+        self.broadcast_new_head(executed_block);
+        self.broadcast_chain_reorg(reorg);
+        Ok(())
+    }
+
     fn is_main_head(&self, parent_id: &HashValue) -> bool {
         parent_id == &self.startup_info.main
     }
@@ -263,12 +334,36 @@ where
         }
     }
 
+    fn broadcast_chain_reorg(&self, reorg: ChainReorg) {
+        if let Err(e) = self.bus.broadcast(reorg) {
+            error!("Broadcast ChainReorg error: {:?}", e);
+        }
+    }
+
     fn broadcast_new_branch(&self, block: ExecutedBlock) {
         if let Err(e) = self.bus.broadcast(NewBranch(Arc::new(block))) {
             error!("Broadcast NewBranch error: {:?}", e);
         }
     }
 
+    pub fn orphan_blocks(&self) -> &OrphanBlockPool {
+        &self.orphan_blocks
+    }
+
+    /// Connect the buffered orphan blocks whose ancestors are connected now.
+    fn connect_orphan_children(&mut self, parent_id: HashValue) {
+        let mut parents = vec![parent_id];
+        while let Some(parent_id) = parents.pop() {
+            for child in self.orphan_blocks.take_children(parent_id) {
+                let child_id = child.id();
+                match self.connect_inner(child) {
+                    Ok(()) => parents.push(child_id),
+                    Err(e) => warn!("Connect orphan block {} failed: {:?}", child_id, e),
+                }
+            }
+        }
+    }
+
     fn connect_inner(&mut self, block: Block) -> Result<()> {
         let block_id = block.id();
         if self.main.current_header().id() == block_id {
@@ -1,6 +1,7 @@
 use crate::pool::{AccountSeqNumberClient, UnverifiedUserTransaction};
 use anyhow::Result;
 use parking_lot::RwLock;
+use starcoin_executor::TransactionValidator;
 use starcoin_state_api::AccountStateReader;
 use starcoin_statedb::ChainStateDB;
 use std::{collections::HashMap, fmt::Debug, sync::Arc};
@@ -151,6 +152,21 @@ impl crate::pool::Client for PoolClient {
             .clone()
             .check_signature()
             .map_err(|e| TransactionError::InvalidSignature(e.to_string()))?;
+        let validator = TransactionValidator::new(
+            self.best_block_header.chain_id(),
+            self.best_block_header.timestamp() / 1000,
+        );
+        match validator.validate(self.nonce_client.statedb.as_ref(), &txn) {
+            Ok(None) => {}
+            Ok(Some(reason)) => {
+                debug!("reject txn {}: {}", txn.id(), reason);
+                return Err(reason.into());
+            }
+            Err(e) => {
+                error!("validate txn {} error: {:?}", txn.id(), e);
+                return Err(TransactionError::CallErr(CallError::StateCorrupt));
+            }
+        }
         match starcoin_executor::validate_transaction(self.nonce_client.statedb.as_ref(), txn) {
             None => Ok(checked_txn),
             Some(status) => Err(TransactionError::CallErr(CallError::ExecutionError(status))),
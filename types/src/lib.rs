@@ -74,6 +74,10 @@ pub mod identifier {
     pub use starcoin_vm_types::identifier::{IdentStr, Identifier};
 }
 
+pub mod move_resource {
+    pub use starcoin_vm_types::move_resource::MoveResource;
+}
+
 pub mod write_set {
     pub use starcoin_vm_types::write_set::{WriteOp, WriteSet, WriteSetMut};
 }
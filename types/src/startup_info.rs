@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::block::{BlockHeader, BlockInfo};
+use crate::block::{BlockHeader, BlockInfo, BlockNumber};
 use anyhow::Result;
 use bcs_ext::{BCSCodec, Sample};
 use serde::{Deserialize, Serialize};
@@ -141,6 +141,22 @@ impl Sample for ChainStatus {
     }
 }
 
+/// A consistent snapshot of the main chain statistics.
+#[derive(Eq, PartialEq, Hash, Deserialize, Serialize, Clone, Debug)]
+pub struct ChainStats {
+    /// Chain head block's number.
+    pub head_number: BlockNumber,
+    /// Total transactions on the chain, include the block metadata transactions.
+    pub total_txns: u64,
+    pub total_difficulty: U256,
+    /// Difficulty of the head block.
+    pub current_difficulty: U256,
+    /// Total supply of STC.
+    pub total_supply: u128,
+    /// Median of the block time (in milliseconds) of the latest blocks.
+    pub median_block_time: u64,
+}
+
 #[derive(Eq, PartialEq, Hash, Deserialize, Serialize, Clone, Debug)]
 pub struct StartupInfo {
     /// main chain head block hash
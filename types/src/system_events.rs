@@ -16,9 +16,28 @@ pub struct NewHeadBlock(pub Arc<ExecutedBlock>);
 #[derive(Clone, Debug)]
 pub struct NewBranch(pub Arc<ExecutedBlock>);
 
+/// Fire this event when the main chain switches to a heavier branch which does not extend the
+/// old main head, the block ids are in ascending number order from the common ancestor.
+#[derive(Clone, Debug)]
+pub struct ChainReorg {
+    pub from: HashValue,
+    pub to: HashValue,
+    pub retracted: Vec<HashValue>,
+    pub applied: Vec<HashValue>,
+}
+
 #[derive(Clone, Debug)]
 pub struct MinedBlock(pub Arc<Block>);
 
+/// Fire this event when a new block of a peer or the local miner reaches the block connector,
+/// before the block is connected.
+#[derive(Clone, Debug)]
+pub struct BlockReceived {
+    pub block_id: HashValue,
+    /// The local time in milliseconds the block is received at.
+    pub received_at: u64,
+}
+
 ///Fire this event on System start and all service is init.
 #[derive(Clone, Debug)]
 pub struct SystemStarted;
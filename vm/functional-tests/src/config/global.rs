@@ -66,6 +66,8 @@ pub struct AccountDefinition {
 pub enum Entry {
     /// Defines an account that can be used in tests.
     AccountDefinition(AccountDefinition),
+    /// The seed to shuffle the transactions within a block.
+    ShuffleSeed(u64),
 }
 
 impl FromStr for Entry {
@@ -77,6 +79,9 @@ impl FromStr for Entry {
             .ok_or_else(|| ErrorKind::Other("txn config entry must start with //!".to_string()))?
             .trim_start();
 
+        if let Some(s) = strip(s, "shuffle-seed:") {
+            return Ok(Entry::ShuffleSeed(s.parse::<u64>()?));
+        }
         if let Some(s) = strip(s, "account:") {
             let v: Vec<_> = s
                 .split(|c: char| c == ',' || c.is_whitespace())
@@ -150,6 +155,8 @@ pub struct Config {
     pub genesis_accounts: BTreeMap<String, Account>,
     /// The validator set after genesis
     pub validator_accounts: usize,
+    /// If set, the transactions within a block are evaluated in an order shuffled by this seed.
+    pub shuffle_seed: Option<u64>,
 }
 
 impl Config {
@@ -159,6 +166,7 @@ impl Config {
         // key generator with a fixed seed
         // this is important as it ensures the tests are deterministic
         let mut keygen = KeyGen::from_seed([0x1f; 32]);
+        let mut shuffle_seed = None;
 
         // initialize the keys of validator entries with the validator set
         // enhance type of config to contain a validator set, use it to initialize genesis
@@ -190,6 +198,12 @@ impl Config {
                         }
                     }
                 }
+                Entry::ShuffleSeed(seed) => {
+                    if shuffle_seed.is_some() {
+                        return Err(ErrorKind::Other("shuffle seed already set".to_string()).into());
+                    }
+                    shuffle_seed = Some(*seed);
+                }
             }
         }
 
@@ -209,6 +223,7 @@ impl Config {
             accounts,
             genesis_accounts: make_genesis_accounts(),
             validator_accounts: 0,
+            shuffle_seed,
         })
     }
 
@@ -4,11 +4,14 @@
 use crate::{common::strip, config::global::Config as GlobalConfig, errors::*, evaluator::Stage};
 use executor::account::Account;
 use starcoin_vm_types::{
+    access_path::{AccessPath, DataPath, DataType},
+    account_address::AccountAddress,
+    identifier::Identifier,
     language_storage::TypeTag,
-    parser::{parse_transaction_argument, parse_type_tags},
+    parser::{parse_struct_tag, parse_transaction_argument, parse_type_tags},
     transaction_argument::TransactionArgument,
 };
-use std::{collections::BTreeSet, str::FromStr};
+use std::{collections::BTreeSet, fmt, str::FromStr};
 
 /// A partially parsed transaction argument.
 #[derive(Debug)]
@@ -31,6 +34,121 @@ impl FromStr for Argument {
     }
 }
 
+/// A partially parsed access path, in the form of `address/data_type/path`.
+/// The data type is `code`, `resource` or its storage index, and the address may refer to an
+/// account by name, e.g. `{{alice}}/resource/0x1::Account::Balance<0x1::STC::STC>`.
+#[derive(Debug)]
+pub enum RawAccessPath {
+    AddressOf(String, DataPath),
+    SelfContained(AccessPath),
+}
+
+impl FromStr for RawAccessPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.splitn(3, '/');
+        let (address, data_type, path) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(address), Some(data_type), Some(path)) => (address, data_type, path),
+            _ => {
+                return Err(ErrorKind::Other(format!(
+                    "failed to parse '{}' as access path, expect 'address/data_type/path'",
+                    s
+                ))
+                .into())
+            }
+        };
+        let data_type = match data_type {
+            "code" => DataType::CODE,
+            "resource" => DataType::RESOURCE,
+            index => DataType::from_index(index.parse::<u8>()?)?,
+        };
+        let data_path = match data_type {
+            DataType::CODE => AccessPath::code_data_path(Identifier::new(path)?),
+            DataType::RESOURCE => AccessPath::resource_data_path(parse_struct_tag(path)?),
+        };
+        if address.starts_with("{{") && address.ends_with("}}") {
+            return Ok(RawAccessPath::AddressOf(
+                address[2..address.len() - 2].to_string(),
+                data_path,
+            ));
+        }
+        Ok(RawAccessPath::SelfContained(AccessPath::new(
+            AccountAddress::from_str(address)?,
+            data_path,
+        )))
+    }
+}
+
+/// A bound of the gas used by a transaction, in the form of `<= n`, `>= n` or `== n`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GasBound {
+    AtMost(u64),
+    AtLeast(u64),
+    Exactly(u64),
+}
+
+impl GasBound {
+    pub fn check(&self, gas_used: u64) -> bool {
+        match self {
+            GasBound::AtMost(n) => gas_used <= *n,
+            GasBound::AtLeast(n) => gas_used >= *n,
+            GasBound::Exactly(n) => gas_used == *n,
+        }
+    }
+}
+
+impl FromStr for GasBound {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some(n) = strip(s, "<=") {
+            return Ok(GasBound::AtMost(n.parse::<u64>()?));
+        }
+        if let Some(n) = strip(s, ">=") {
+            return Ok(GasBound::AtLeast(n.parse::<u64>()?));
+        }
+        if let Some(n) = strip(s, "==") {
+            return Ok(GasBound::Exactly(n.parse::<u64>()?));
+        }
+        Err(ErrorKind::Other(format!(
+            "failed to parse '{}' as gas bound, expect '<= n', '>= n' or '== n'",
+            s
+        ))
+        .into())
+    }
+}
+
+impl fmt::Display for GasBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GasBound::AtMost(n) => write!(f, "<= {}", n),
+            GasBound::AtLeast(n) => write!(f, ">= {}", n),
+            GasBound::Exactly(n) => write!(f, "== {}", n),
+        }
+    }
+}
+
+/// Splits the comma separated list, skipping the commas within the type arguments of a struct.
+fn split_list(s: &str) -> Vec<&str> {
+    let mut items = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&s[start..]);
+    items
+}
+
 /// A raw entry extracted from the input. Used to build a transaction config table.
 #[derive(Debug)]
 pub enum Entry {
@@ -41,7 +159,16 @@ pub enum Entry {
     MaxGas(u64),
     GasPrice(u64),
     SequenceNumber(u64),
-    ExpirationTime(u64),
+    ExpirationTime(i64),
+    ExpectSequenceNumber(String, u64),
+    ExpectSequenceNumberAt(u64, String, u64),
+    ExpectLedgerVersion(u64),
+    ShowFootprint(bool),
+    ShowStateDiff(bool),
+    ExpectNoStateChange(bool),
+    ExpectWrites(Vec<RawAccessPath>),
+    ExpectStatus(String),
+    ExpectGasUsed(GasBound),
 }
 
 impl FromStr for Entry {
@@ -90,7 +217,84 @@ impl FromStr for Entry {
             return Ok(Entry::SequenceNumber(s.parse::<u64>()?));
         }
         if let Some(s) = strip(s, "expiration-time:") {
-            return Ok(Entry::ExpirationTime(s.parse::<u64>()?));
+            return Ok(Entry::ExpirationTime(s.parse::<i64>()?));
+        }
+        if let Some(s) = strip(s, "footprint:") {
+            return Ok(Entry::ShowFootprint(s.parse::<bool>()?));
+        }
+        if let Some(s) = strip(s, "state-diff:") {
+            return Ok(Entry::ShowStateDiff(s.parse::<bool>()?));
+        }
+        if let Some(s) = strip(s, "no-state-change:") {
+            return Ok(Entry::ExpectNoStateChange(s.parse::<bool>()?));
+        }
+        if let Some(s) = strip(s, "writes:") {
+            let s = strip(s, "[")
+                .and_then(|s| s.strip_suffix(']'))
+                .ok_or_else(|| {
+                    ErrorKind::Other(format!(
+                        "failed to parse '{}' as expected writes, expect '[path, ...]'",
+                        s
+                    ))
+                })?;
+            let res: Result<Vec<_>> = split_list(s)
+                .into_iter()
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse::<RawAccessPath>())
+                .collect();
+            return Ok(Entry::ExpectWrites(res?));
+        }
+        if let Some(s) = strip(s, "status:") {
+            if s.is_empty() {
+                return Err(ErrorKind::Other("status cannot be empty".to_string()).into());
+            }
+            return Ok(Entry::ExpectStatus(s.to_ascii_uppercase()));
+        }
+        if let Some(s) = strip(s, "gas-used:") {
+            return Ok(Entry::ExpectGasUsed(s.parse::<GasBound>()?));
+        }
+        if let Some(s) = strip(s, "ledger-version:") {
+            return Ok(Entry::ExpectLedgerVersion(s.parse::<u64>()?));
+        }
+        if let Some(s) = strip(s, "seq-at:") {
+            let mut parts = s.splitn(2, ',');
+            let (version, s) = match (parts.next(), parts.next()) {
+                (Some(version), Some(s)) => (version.parse::<u64>()?, s),
+                _ => {
+                    return Err(ErrorKind::Other(format!(
+                        "failed to parse '{}' as historical sequence number assertion, expect 'version, name = number'",
+                        s
+                    ))
+                    .into())
+                }
+            };
+            let mut parts = s.splitn(2, '=');
+            return match (parts.next(), parts.next()) {
+                (Some(name), Some(sn)) if !name.is_empty() => Ok(Entry::ExpectSequenceNumberAt(
+                    version,
+                    name.to_ascii_lowercase(),
+                    sn.parse::<u64>()?,
+                )),
+                _ => Err(ErrorKind::Other(format!(
+                    "failed to parse '{}' as historical sequence number assertion, expect 'version, name = number'",
+                    s
+                ))
+                .into()),
+            };
+        }
+        if let Some(s) = strip(s, "seq:") {
+            let mut parts = s.splitn(2, '=');
+            return match (parts.next(), parts.next()) {
+                (Some(name), Some(sn)) if !name.is_empty() => Ok(Entry::ExpectSequenceNumber(
+                    name.to_ascii_lowercase(),
+                    sn.parse::<u64>()?,
+                )),
+                _ => Err(ErrorKind::Other(format!(
+                    "failed to parse '{}' as sequence number assertion, expect 'name = number'",
+                    s
+                ))
+                .into()),
+            };
         }
 
         Err(ErrorKind::Other(format!(
@@ -131,7 +335,28 @@ pub struct Config<'a> {
     pub max_gas: Option<u64>,
     pub gas_price: Option<u64>,
     pub sequence_number: Option<u64>,
-    pub expiration_time: Option<u64>,
+    /// The seconds the transaction expires after the current block time,
+    /// a negative value makes an already expired transaction.
+    pub expiration_time: Option<i64>,
+    /// Sequence numbers the accounts are expected to have after the transaction is evaluated.
+    pub expected_sequence_numbers: Vec<(&'a Account, u64)>,
+    /// Sequence numbers the accounts are expected to have had as of a prior ledger version.
+    pub expected_sequence_numbers_at: Vec<(u64, &'a Account, u64)>,
+    /// The ledger version expected after the transaction is evaluated.
+    pub expected_ledger_version: Option<u64>,
+    /// Whether to output the state footprint of the transaction execution.
+    pub show_footprint: bool,
+    /// Whether to output the state changed by the transaction execution.
+    pub show_state_diff: bool,
+    /// Whether the transaction is expected to write nothing but the gas and sequence number
+    /// bookkeeping.
+    pub expect_no_state_change: bool,
+    /// The access paths the transaction is expected to write, exactly.
+    pub expected_writes: Option<BTreeSet<AccessPath>>,
+    /// The top-level status code the transaction is expected to finish with, e.g. `EXECUTED`.
+    pub expected_status: Option<String>,
+    /// The bounds the gas used by the transaction is expected to be within.
+    pub expected_gas_used: Vec<GasBound>,
 }
 
 impl<'a> Config<'a> {
@@ -145,6 +370,15 @@ impl<'a> Config<'a> {
         let mut gas_price = None;
         let mut sequence_number = None;
         let mut expiration_time = None;
+        let mut expected_sequence_numbers: Vec<(&'a Account, u64)> = vec![];
+        let mut expected_sequence_numbers_at: Vec<(u64, &'a Account, u64)> = vec![];
+        let mut expected_ledger_version = None;
+        let mut show_footprint = None;
+        let mut show_state_diff = None;
+        let mut expect_no_state_change = None;
+        let mut expected_writes = None;
+        let mut expected_status = None;
+        let mut expected_gas_used = vec![];
 
         for entry in entries {
             match entry {
@@ -218,6 +452,89 @@ impl<'a> Config<'a> {
                         )
                     }
                 },
+                Entry::ExpectSequenceNumber(name, sn) => {
+                    let account = config.get_account_for_name(name)?;
+                    if expected_sequence_numbers
+                        .iter()
+                        .any(|(expected, _)| expected.address() == account.address())
+                    {
+                        return Err(ErrorKind::Other(format!(
+                            "expected sequence number of '{}' already set",
+                            name
+                        ))
+                        .into());
+                    }
+                    expected_sequence_numbers.push((account, *sn));
+                }
+                Entry::ExpectSequenceNumberAt(version, name, sn) => {
+                    let account = config.get_account_for_name(name)?;
+                    if expected_sequence_numbers_at.iter().any(|(v, expected, _)| {
+                        v == version && expected.address() == account.address()
+                    }) {
+                        return Err(ErrorKind::Other(format!(
+                            "expected sequence number of '{}' at version {} already set",
+                            name, version
+                        ))
+                        .into());
+                    }
+                    expected_sequence_numbers_at.push((*version, account, *sn));
+                }
+                Entry::ExpectLedgerVersion(version) => match expected_ledger_version {
+                    None => expected_ledger_version = Some(*version),
+                    Some(_) => {
+                        return Err(
+                            ErrorKind::Other("ledger version already set".to_string()).into()
+                        )
+                    }
+                },
+                Entry::ShowFootprint(show) => match show_footprint {
+                    None => show_footprint = Some(*show),
+                    Some(_) => {
+                        return Err(ErrorKind::Other("footprint already set".to_string()).into())
+                    }
+                },
+                Entry::ShowStateDiff(show) => match show_state_diff {
+                    None => show_state_diff = Some(*show),
+                    Some(_) => {
+                        return Err(ErrorKind::Other("state diff already set".to_string()).into())
+                    }
+                },
+                Entry::ExpectNoStateChange(expect) => match expect_no_state_change {
+                    None => expect_no_state_change = Some(*expect),
+                    Some(_) => {
+                        return Err(
+                            ErrorKind::Other("no state change already set".to_string()).into()
+                        )
+                    }
+                },
+                Entry::ExpectWrites(raw_paths) => match expected_writes {
+                    None => {
+                        expected_writes = Some(
+                            raw_paths
+                                .iter()
+                                .map(|path| match path {
+                                    RawAccessPath::AddressOf(name, data_path) => {
+                                        Ok(AccessPath::new(
+                                            *config.get_account_for_name(name)?.address(),
+                                            data_path.clone(),
+                                        ))
+                                    }
+                                    RawAccessPath::SelfContained(path) => Ok(path.clone()),
+                                })
+                                .collect::<Result<BTreeSet<_>>>()?,
+                        );
+                    }
+                    Some(_) => {
+                        return Err(ErrorKind::Other("writes already set".to_string()).into())
+                    }
+                },
+                Entry::ExpectStatus(status) => match expected_status {
+                    None => expected_status = Some(status.clone()),
+                    Some(_) => {
+                        return Err(ErrorKind::Other("status already set".to_string()).into())
+                    }
+                },
+                Entry::ExpectGasUsed(bound) => expected_gas_used.push(*bound),
             }
         }
 
@@ -230,6 +547,15 @@ impl<'a> Config<'a> {
             gas_price,
             sequence_number,
             expiration_time,
+            expected_sequence_numbers,
+            expected_sequence_numbers_at,
+            expected_ledger_version,
+            show_footprint: show_footprint.unwrap_or(false),
+            show_state_diff: show_state_diff.unwrap_or(false),
+            expect_no_state_change: expect_no_state_change.unwrap_or(false),
+            expected_writes,
+            expected_status,
+            expected_gas_used,
         })
     }
 
@@ -5,37 +5,44 @@ use crate::{
     compiler::{Compiler, ScriptOrModule},
     config::{global::Config as GlobalConfig, transaction::Config as TransactionConfig},
     errors::*,
-    executor::FakeExecutor,
+    executor::{ExecutionFootprint, FakeExecutor},
 };
-use executor::account::AccountData;
+use executor::account::{Account, AccountData};
 use mirai_annotations::checked_verify;
 use once_cell::sync::Lazy;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use starcoin_account_api::AccountPrivateKey;
 use starcoin_config::DEFAULT_GAS_CONSTANTS;
 use starcoin_types::{
-    access_path::AccessPath,
+    access_path::{AccessPath, DataPath},
     account_address::AccountAddress,
     block_metadata::BlockMetadata,
+    contract_event::ContractEvent,
     transaction::{
         Module as TransactionModule, RawUserTransaction, Script as TransactionScript,
         SignedUserTransaction, Transaction as StarcoinTransaction, TransactionOutput,
         TransactionStatus,
     },
+    write_set::WriteSet,
+};
+use starcoin_vm_types::account_config::{
+    core_code_address, events::accept_token_payment::AcceptTokenEvent, genesis_address,
+    BlockRewardEvent, BurnEvent, DepositEvent, MintEvent, WithdrawEvent,
 };
 use starcoin_vm_types::genesis_config::ChainId;
-use starcoin_vm_types::token::stc::STC_TOKEN_CODE_STR;
+use starcoin_vm_types::token::{stc::STC_TOKEN_CODE_STR, token_code::TokenCode};
 use starcoin_vm_types::transaction_argument::convert_txn_args;
-use starcoin_vm_types::vm_status::{KeptVMStatus, VMStatus};
+use starcoin_vm_types::vm_status::{KeptVMStatus, StatusCode, VMStatus};
 use starcoin_vm_types::{
     bytecode_verifier::{self, dependencies},
-    errors::{Location, VMError},
+    errors::{Location, VMError, VMResult},
     file_format::{CompiledModule, CompiledScript},
     gas_schedule::GasAlgebra,
-    language_storage::ModuleId,
+    language_storage::{ModuleId, TypeTag},
     state_view::StateView,
     views::ModuleView,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
 use std::str::FromStr;
 
@@ -55,14 +62,15 @@ pub struct Transaction<'a> {
 /// Commands that drives the operation of DiemVM. Such as:
 /// 1. Execute user transaction
 /// 2. Publish a new block metadata
+/// 3. Clean the cache of the executor
 ///
 /// In the future we will add more commands to mimic the full public API of DiemVM,
-/// including reloading the on-chain configuration that will affect the code path for DiemVM,
-/// cleaning the cache in the DiemVM, etc.
+/// including reloading the on-chain configuration that will affect the code path for DiemVM, etc.
 #[derive(Debug)]
 pub enum Command<'a> {
     Transaction(Transaction<'a>),
     BlockMetadata(BlockMetadata),
+    ClearCache,
 }
 
 /// Indicates one step in the pipeline the given move module/program goes through.
@@ -102,6 +110,10 @@ pub enum OutputType {
     CompiledScript(Box<CompiledScript>),
     CompilerLog(String),
     TransactionOutput(Box<TransactionOutput>),
+    ExecutionFootprint { reads: u64, writes: u64, bytes: u64 },
+    StateDiff(Box<WriteSet>),
+    Events(Vec<EventOutput>),
+    Discard(DiscardOutput),
 }
 
 impl OutputType {
@@ -110,6 +122,97 @@ impl OutputType {
     }
 }
 
+/// An event emitted by a transaction, rendered as `event <name> <field>: <value> ...`
+/// so that it can be checked with a directive like `// check: event WithdrawEvent amount: 100`.
+#[derive(Clone)]
+pub struct EventOutput {
+    pub type_tag: TypeTag,
+    /// The decoded fields of the common STC events, empty for the other events.
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl EventOutput {
+    pub fn decode(event: &ContractEvent) -> Self {
+        let fields = if event.is::<WithdrawEvent>() {
+            event
+                .decode_event::<WithdrawEvent>()
+                .map(|e| token_fields(e.amount(), e.token_code()))
+        } else if event.is::<DepositEvent>() {
+            event
+                .decode_event::<DepositEvent>()
+                .map(|e| token_fields(e.amount(), e.token_code()))
+        } else if event.is::<MintEvent>() {
+            event
+                .decode_event::<MintEvent>()
+                .map(|e| token_fields(e.amount(), e.token_code()))
+        } else if event.is::<BurnEvent>() {
+            event
+                .decode_event::<BurnEvent>()
+                .map(|e| token_fields(e.amount(), e.token_code()))
+        } else if event.is::<AcceptTokenEvent>() {
+            event
+                .decode_event::<AcceptTokenEvent>()
+                .map(|e| vec![("token_code", e.token_code().to_string())])
+        } else if event.is::<BlockRewardEvent>() {
+            event.decode_event::<BlockRewardEvent>().map(|e| {
+                vec![
+                    ("block_number", e.block_number.to_string()),
+                    ("block_reward", e.block_reward.to_string()),
+                    ("gas_fees", e.gas_fees.to_string()),
+                    ("miner", e.miner.to_string()),
+                ]
+            })
+        } else {
+            Ok(vec![])
+        };
+        Self {
+            type_tag: event.type_tag().clone(),
+            fields: fields.unwrap_or_default(),
+        }
+    }
+}
+
+fn token_fields(amount: u128, token_code: &TokenCode) -> Vec<(&'static str, String)> {
+    vec![
+        ("amount", amount.to_string()),
+        ("token_code", token_code.to_string()),
+    ]
+}
+
+impl fmt::Debug for EventOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.type_tag {
+            TypeTag::Struct(struct_tag) => write!(f, "event {}", struct_tag.name)?,
+            type_tag => write!(f, "event {}", type_tag)?,
+        }
+        for (name, value) in &self.fields {
+            write!(f, " {}: {}", name, value)?;
+        }
+        write!(f, " type: {}", self.type_tag)
+    }
+}
+
+/// The status a transaction is discarded with, rendered as `DISCARD <status>`
+/// so that it can be checked with a directive like `// check: DISCARD`.
+#[derive(Clone)]
+pub struct DiscardOutput(pub StatusCode);
+
+impl fmt::Debug for DiscardOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DISCARD {:?}", self.0)
+    }
+}
+
+impl From<ExecutionFootprint> for OutputType {
+    fn from(footprint: ExecutionFootprint) -> Self {
+        OutputType::ExecutionFootprint {
+            reads: footprint.reads,
+            writes: footprint.writes,
+            bytes: footprint.bytes,
+        }
+    }
+}
+
 /// An entry in the `EvaluationLog`.
 #[derive(Debug)]
 pub enum EvaluationOutput {
@@ -171,6 +274,30 @@ impl fmt::Display for OutputType {
             CompiledScript(cs) => write!(f, "{:#?}", cs),
             CompilerLog(s) => write!(f, "{}", s),
             TransactionOutput(output) => write!(f, "{:#?}", output),
+            ExecutionFootprint {
+                reads,
+                writes,
+                bytes,
+            } => write!(
+                f,
+                "ExecutionFootprint {{ reads: {}, writes: {}, bytes: {} }}",
+                reads, writes, bytes
+            ),
+            StateDiff(write_set) => {
+                writeln!(f, "StateDiff {{")?;
+                for (access_path, write_op) in write_set.iter() {
+                    writeln!(f, "    {}: {:?}", access_path, write_op)?;
+                }
+                write!(f, "}}")
+            }
+            Events(events) => {
+                writeln!(f, "Events {{")?;
+                for event in events {
+                    writeln!(f, "    {:?}", event)?;
+                }
+                write!(f, "}}")
+            }
+            Discard(discard) => write!(f, "{:?}", discard),
         }
     }
 }
@@ -270,14 +397,18 @@ struct TransactionParameters<'a> {
     pub expiration_timestamp_seconds: u64,
 }
 
+/// Reads the current sequence number of the account from the executor state.
+fn read_sequence_number(exec: &FakeExecutor, account: &Account) -> u64 {
+    exec.read_account_resource(account)
+        .expect("read_account_resource fail")
+        .sequence_number()
+}
+
 /// Gets the transaction parameters from the current execution environment and the config.
 fn get_transaction_parameters<'a>(
     exec: &'a FakeExecutor,
     config: &'a TransactionConfig,
 ) -> TransactionParameters<'a> {
-    let account_resource = exec
-        .read_account_resource(config.sender)
-        .expect("read_account_resource fail");
     let account_balance = exec
         .read_balance_resource(config.sender)
         .expect("read_balance_resource fail");
@@ -299,11 +430,15 @@ fn get_transaction_parameters<'a>(
         privkey: &config.sender.private_key(),
         sequence_number: config
             .sequence_number
-            .unwrap_or_else(|| account_resource.sequence_number()),
+            .unwrap_or_else(|| read_sequence_number(exec, config.sender)),
         max_gas_amount,
         gas_unit_price,
-        expiration_timestamp_seconds: exec.read_timestamp()
-            + config.expiration_time.unwrap_or(3600),
+        expiration_timestamp_seconds: match config.expiration_time.unwrap_or(3600) {
+            expiration_time if expiration_time < 0 => exec
+                .read_timestamp()
+                .saturating_sub(expiration_time.unsigned_abs()),
+            expiration_time => exec.read_timestamp() + expiration_time as u64,
+        },
     }
 }
 
@@ -356,17 +491,26 @@ fn make_module_transaction(
     Ok(SignedUserTransaction::new(raw_txn, signature))
 }
 
-/// Runs a single transaction using the fake executor.
+/// Runs a single transaction using the fake executor, returns the result with the footprint.
 fn run_transaction(
     exec: &mut FakeExecutor,
     transaction: SignedUserTransaction,
+) -> (Result<TransactionOutput>, ExecutionFootprint) {
+    let (outputs, footprint) = exec
+        .execute_block_with_footprint(vec![transaction])
+        .unwrap();
+    (apply_transaction_output(exec, outputs), footprint)
+}
+
+fn apply_transaction_output(
+    exec: &mut FakeExecutor,
+    mut outputs: Vec<(VMStatus, TransactionOutput)>,
 ) -> Result<TransactionOutput> {
-    let mut outputs = exec.execute_block(vec![transaction]).unwrap();
     if outputs.len() == 1 {
         let (vm_status, output) = outputs.pop().unwrap();
         match output.status() {
             TransactionStatus::Keep(status) => {
-                exec.apply_write_set(output.write_set());
+                exec.commit_transaction(output.write_set());
                 if status == &KeptVMStatus::Executed {
                     Ok(output)
                 } else {
@@ -383,6 +527,154 @@ fn run_transaction(
     }
 }
 
+/// Logs the status of a discarded transaction, so that the discard reason can be checked
+/// regardless of the `status` directive.
+fn log_discard(txn_output: &Result<TransactionOutput>, log: &mut EvaluationLog) {
+    if let Err(err) = txn_output {
+        if let Some(ErrorKind::DiscardedTransaction(output)) = err.downcast_ref::<ErrorKind>() {
+            if let Err(status_code) = output.status().status() {
+                log.append(EvaluationOutput::Output(OutputType::Discard(
+                    DiscardOutput(status_code),
+                )));
+            }
+        }
+    }
+}
+
+/// Checks the top-level status code of the transaction against the `status` directive.
+/// A transaction finished with the expected status is treated as a success, even if it is
+/// kept with a failure or discarded.
+fn check_transaction_status(
+    config: &TransactionConfig,
+    txn_output: Result<TransactionOutput>,
+) -> Result<TransactionOutput> {
+    let expected = match &config.expected_status {
+        Some(expected) => expected,
+        None => return txn_output,
+    };
+    let (status_code, output) = match txn_output {
+        Ok(output) => (StatusCode::EXECUTED, output),
+        Err(err) => match err.downcast::<ErrorKind>() {
+            Ok(ErrorKind::VMExecutionFailure(vm_status, output)) => {
+                (vm_status.status_code(), output)
+            }
+            Ok(ErrorKind::DiscardedTransaction(output)) => {
+                let status_code = output
+                    .status()
+                    .status()
+                    .err()
+                    .expect("discarded transaction must have a discard status");
+                (status_code, output)
+            }
+            Ok(kind) => return Err(kind.into()),
+            Err(err) => return Err(err),
+        },
+    };
+    let actual = format!("{:?}", status_code);
+    if &actual == expected {
+        Ok(output)
+    } else {
+        Err(ErrorKind::Other(format!(
+            "transaction status mismatch, expected: {}, actual: {}",
+            expected, actual
+        ))
+        .into())
+    }
+}
+
+/// Checks the transaction writes nothing but the gas and sequence number bookkeeping,
+/// when the `no-state-change` directive is set.
+fn check_no_state_change(config: &TransactionConfig, output: &TransactionOutput) -> Result<()> {
+    if !config.expect_no_state_change {
+        return Ok(());
+    }
+    let sender = *config.sender.address();
+    let unexpected_writes: Vec<String> = output
+        .write_set()
+        .iter()
+        .filter(|(access_path, _)| !is_bookkeeping_write(access_path, sender))
+        .map(|(access_path, _)| access_path.to_string())
+        .collect();
+    if unexpected_writes.is_empty() {
+        Ok(())
+    } else {
+        Err(ErrorKind::Other(format!(
+            "unexpected state change: {}",
+            unexpected_writes.join(", ")
+        ))
+        .into())
+    }
+}
+
+/// Checks the access paths written by the transaction are exactly the expected ones,
+/// when the `writes` directive is set.
+fn check_expected_writes(config: &TransactionConfig, output: &TransactionOutput) -> Result<()> {
+    let expected = match &config.expected_writes {
+        Some(expected) => expected,
+        None => return Ok(()),
+    };
+    let actual: BTreeSet<&AccessPath> = output
+        .write_set()
+        .iter()
+        .map(|(access_path, _)| access_path)
+        .collect();
+    let missing: Vec<String> = expected
+        .iter()
+        .filter(|access_path| !actual.contains(access_path))
+        .map(|access_path| access_path.to_string())
+        .collect();
+    let extra: Vec<String> = actual
+        .iter()
+        .filter(|access_path| !expected.contains(**access_path))
+        .map(|access_path| access_path.to_string())
+        .collect();
+    if missing.is_empty() && extra.is_empty() {
+        Ok(())
+    } else {
+        Err(ErrorKind::Other(format!(
+            "write set mismatch, missing: [{}], extra: [{}]",
+            missing.join(", "),
+            extra.join(", ")
+        ))
+        .into())
+    }
+}
+
+/// Checks the gas used by the transaction is within the bounds of the `gas-used` directives.
+fn check_gas_used(config: &TransactionConfig, output: &TransactionOutput) -> Result<()> {
+    let gas_used = output.gas_used();
+    let violated: Vec<String> = config
+        .expected_gas_used
+        .iter()
+        .filter(|bound| !bound.check(gas_used))
+        .map(|bound| bound.to_string())
+        .collect();
+    if violated.is_empty() {
+        Ok(())
+    } else {
+        Err(ErrorKind::Other(format!(
+            "gas used out of bound, expected: {}, actual: {}",
+            violated.join(", "),
+            gas_used
+        ))
+        .into())
+    }
+}
+
+/// Every transaction bumps the sequence number in the sender's account, charges the gas from
+/// the sender's balance and deposits the gas fee to the `TransactionFee` of the genesis account.
+fn is_bookkeeping_write(access_path: &AccessPath, sender: AccountAddress) -> bool {
+    let struct_tag = match &access_path.path {
+        DataPath::Resource(struct_tag) if struct_tag.address == core_code_address() => struct_tag,
+        _ => return false,
+    };
+    match (struct_tag.module.as_str(), struct_tag.name.as_str()) {
+        ("Account", "Account") | ("Account", "Balance") => access_path.address == sender,
+        ("TransactionFee", "TransactionFee") => access_path.address == genesis_address(),
+        _ => false,
+    }
+}
+
 /// Serializes the script then deserializes it.
 fn serialize_and_deserialize_script(script: &CompiledScript) -> Result<()> {
     let mut script_blob = vec![];
@@ -424,6 +716,42 @@ fn is_precompiled_script(input_str: &str) -> Option<CompiledScript> {
     None
 }
 
+/// Parses the raw bytecode input in the form of `bytecode::module::<hex>` or `bytecode::script::<hex>`.
+/// Returns None if the input is not a bytecode input.
+fn parse_bytecode_input(input_str: &str) -> Option<Result<(bool, Vec<u8>)>> {
+    let input_str = input_str.trim().strip_prefix("bytecode::")?;
+    let (is_module, bytecode) = if let Some(bytecode) = input_str.strip_prefix("module::") {
+        (true, bytecode)
+    } else if let Some(bytecode) = input_str.strip_prefix("script::") {
+        (false, bytecode)
+    } else {
+        return Some(Err(ErrorKind::Other(format!(
+            "bytecode input must be 'bytecode::module::<hex>' or 'bytecode::script::<hex>', got '{}'",
+            input_str
+        ))
+        .into()));
+    };
+    let bytecode = bytecode.strip_prefix("0x").unwrap_or(bytecode);
+    Some(
+        hex::decode(bytecode)
+            .map(|bytes| (is_module, bytes))
+            .map_err(|e| ErrorKind::Other(format!("invalid bytecode hex: {}", e)).into()),
+    )
+}
+
+/// Deserializes the raw bytecode without any verification.
+fn deserialize_bytecode(is_module: bool, bytecode: &[u8]) -> VMResult<ScriptOrModule> {
+    if is_module {
+        CompiledModule::deserialize(bytecode)
+            .map(ScriptOrModule::Module)
+            .map_err(|e| e.finish(Location::Undefined))
+    } else {
+        CompiledScript::deserialize(bytecode)
+            .map(ScriptOrModule::Script)
+            .map_err(|e| e.finish(Location::Undefined))
+    }
+}
+
 fn eval_transaction<TComp: Compiler>(
     compiler: &mut TComp,
     exec: &mut FakeExecutor,
@@ -449,20 +777,33 @@ fn eval_transaction<TComp: Compiler>(
     // Start processing a new transaction.
     log.append(EvaluationOutput::Transaction(idx));
 
-    // stage 1: Compile the script/module
-    if transaction.config.is_stage_disabled(Stage::Compiler) {
-        return Ok(Status::Success);
-    }
-    log.append(EvaluationOutput::Stage(Stage::Compiler));
-    let compiler_log = |s| log.append(EvaluationOutput::Output(OutputType::CompilerLog(s)));
-
     //TODO support Call ScriptFunction
-    let parsed_script_or_module =
+    let parsed_script_or_module = if let Some(bytecode) = parse_bytecode_input(&transaction.input) {
+        // raw bytecode skips the compiler, the deserialization is the first step of verification.
+        let (is_module, bytecode) = unwrap_or_abort!(bytecode);
+        match deserialize_bytecode(is_module, &bytecode) {
+            Ok(script_or_module) => script_or_module,
+            Err(err) => {
+                log.append(EvaluationOutput::Stage(Stage::Verifier));
+                let err: Error = ErrorKind::VerificationError(err.into_vm_status()).into();
+                log.append(EvaluationOutput::Error(Box::new(err)));
+                return Ok(Status::Failure);
+            }
+        }
+    } else {
+        // stage 1: Compile the script/module
+        if transaction.config.is_stage_disabled(Stage::Compiler) {
+            return Ok(Status::Success);
+        }
+        log.append(EvaluationOutput::Stage(Stage::Compiler));
+        let compiler_log = |s| log.append(EvaluationOutput::Output(OutputType::CompilerLog(s)));
+
         if let Some(compiled_script) = is_precompiled_script(&transaction.input) {
             ScriptOrModule::Script(compiled_script)
         } else {
             unwrap_or_abort!(compiler.compile(compiler_log, sender_addr, &transaction.input))
-        };
+        }
+    };
 
     match parsed_script_or_module {
         ScriptOrModule::Script(compiled_script) => {
@@ -498,10 +839,37 @@ fn eval_transaction<TComp: Compiler>(
             log.append(EvaluationOutput::Stage(Stage::Runtime));
             let script_transaction =
                 make_script_transaction(&exec, &transaction.config, compiled_script)?;
-            let txn_output = unwrap_or_abort!(run_transaction(exec, script_transaction));
+            let state_before = if transaction.config.show_state_diff {
+                Some(exec.capture_state())
+            } else {
+                None
+            };
+            let (txn_output, footprint) = run_transaction(exec, script_transaction);
+            if transaction.config.show_footprint {
+                log.append(EvaluationOutput::Output(footprint.into()));
+            }
+            if let Some(state_before) = state_before {
+                log.append(EvaluationOutput::Output(OutputType::StateDiff(Box::new(
+                    exec.state_diff(&state_before),
+                ))));
+            }
+            log_discard(&txn_output, log);
+            let txn_output =
+                unwrap_or_abort!(check_transaction_status(&transaction.config, txn_output));
+            unwrap_or_abort!(check_no_state_change(&transaction.config, &txn_output));
+            unwrap_or_abort!(check_expected_writes(&transaction.config, &txn_output));
+            unwrap_or_abort!(check_gas_used(&transaction.config, &txn_output));
+            let events: Vec<_> = txn_output
+                .events()
+                .iter()
+                .map(EventOutput::decode)
+                .collect();
             log.append(EvaluationOutput::Output(OutputType::TransactionOutput(
                 Box::new(txn_output),
             )));
+            if !events.is_empty() {
+                log.append(EvaluationOutput::Output(OutputType::Events(events)));
+            }
         }
         ScriptOrModule::Module(compiled_module) => {
             log.append(EvaluationOutput::Output(OutputType::CompiledModule(
@@ -536,10 +904,37 @@ fn eval_transaction<TComp: Compiler>(
             log.append(EvaluationOutput::Stage(Stage::Runtime));
             let module_transaction =
                 make_module_transaction(&exec, &transaction.config, compiled_module)?;
-            let txn_output = unwrap_or_abort!(run_transaction(exec, module_transaction));
+            let state_before = if transaction.config.show_state_diff {
+                Some(exec.capture_state())
+            } else {
+                None
+            };
+            let (txn_output, footprint) = run_transaction(exec, module_transaction);
+            if transaction.config.show_footprint {
+                log.append(EvaluationOutput::Output(footprint.into()));
+            }
+            if let Some(state_before) = state_before {
+                log.append(EvaluationOutput::Output(OutputType::StateDiff(Box::new(
+                    exec.state_diff(&state_before),
+                ))));
+            }
+            log_discard(&txn_output, log);
+            let txn_output =
+                unwrap_or_abort!(check_transaction_status(&transaction.config, txn_output));
+            unwrap_or_abort!(check_no_state_change(&transaction.config, &txn_output));
+            unwrap_or_abort!(check_expected_writes(&transaction.config, &txn_output));
+            unwrap_or_abort!(check_gas_used(&transaction.config, &txn_output));
+            let events: Vec<_> = txn_output
+                .events()
+                .iter()
+                .map(EventOutput::decode)
+                .collect();
             log.append(EvaluationOutput::Output(OutputType::TransactionOutput(
                 Box::new(txn_output),
             )));
+            if !events.is_empty() {
+                log.append(EvaluationOutput::Output(OutputType::Events(events)));
+            }
         }
     }
     Ok(Status::Success)
@@ -560,10 +955,14 @@ pub fn eval_block_metadata(
                 .expect("There should be one output in the result");
             match output.status() {
                 TransactionStatus::Keep(_status) => {
-                    executor.apply_write_set(output.write_set());
+                    executor.commit_transaction(output.write_set());
+                    let events: Vec<_> = output.events().iter().map(EventOutput::decode).collect();
                     log.append(EvaluationOutput::Output(OutputType::TransactionOutput(
                         Box::new(output),
                     )));
+                    if !events.is_empty() {
+                        log.append(EvaluationOutput::Output(OutputType::Events(events)));
+                    }
                     Ok(Status::Success)
                 }
                 TransactionStatus::Discard(status) => {
@@ -592,6 +991,106 @@ pub fn eval<TComp: Compiler>(
     eval_with_executor(config, compiler, &mut exec, commands)
 }
 
+/// Checks the sequence numbers asserted by the `seq` and `seq-at` directives against the
+/// executor state. Logs an error for every mismatch and returns false if any assertion fails.
+fn check_sequence_numbers(
+    exec: &FakeExecutor,
+    config: &TransactionConfig,
+    log: &mut EvaluationLog,
+) -> bool {
+    let mut passed = true;
+    for (account, expected) in &config.expected_sequence_numbers {
+        let actual = read_sequence_number(exec, account);
+        if actual != *expected {
+            log.append(EvaluationOutput::Error(Box::new(
+                ErrorKind::Other(format!(
+                    "sequence number of account {} mismatch, expected: {}, actual: {}",
+                    account.address(),
+                    expected,
+                    actual
+                ))
+                .into(),
+            )));
+            passed = false;
+        }
+    }
+    for (version, account, expected) in &config.expected_sequence_numbers_at {
+        let actual = match exec.read_account_resource_at(account, *version) {
+            Some(resource) => resource.sequence_number(),
+            None => {
+                log.append(EvaluationOutput::Error(Box::new(
+                    ErrorKind::Other(format!(
+                        "account {} not found at ledger version {}",
+                        account.address(),
+                        version
+                    ))
+                    .into(),
+                )));
+                passed = false;
+                continue;
+            }
+        };
+        if actual != *expected {
+            log.append(EvaluationOutput::Error(Box::new(
+                ErrorKind::Other(format!(
+                    "sequence number of account {} at ledger version {} mismatch, expected: {}, actual: {}",
+                    account.address(),
+                    version,
+                    expected,
+                    actual
+                ))
+                .into(),
+            )));
+            passed = false;
+        }
+    }
+    passed
+}
+
+/// Checks the ledger version asserted by the `ledger-version` directive against the executor.
+fn check_ledger_version(
+    exec: &FakeExecutor,
+    config: &TransactionConfig,
+    log: &mut EvaluationLog,
+) -> bool {
+    match config.expected_ledger_version {
+        Some(expected) if expected != exec.ledger_version() => {
+            log.append(EvaluationOutput::Error(Box::new(
+                ErrorKind::Other(format!(
+                    "ledger version mismatch, expected: {}, actual: {}",
+                    expected,
+                    exec.ledger_version()
+                ))
+                .into(),
+            )));
+            false
+        }
+        _ => true,
+    }
+}
+
+/// Returns the order to evaluate the commands in. The transactions between two block metadata
+/// or clear cache commands are shuffled by a rng seeded with `seed`, the other commands stay
+/// in place.
+pub fn shuffle_commands(commands: &[Command], seed: u64) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut order = Vec::with_capacity(commands.len());
+    let mut batch = vec![];
+    for (idx, command) in commands.iter().enumerate() {
+        match command {
+            Command::Transaction(_) => batch.push(idx),
+            Command::BlockMetadata(_) | Command::ClearCache => {
+                batch.shuffle(&mut rng);
+                order.append(&mut batch);
+                order.push(idx);
+            }
+        }
+    }
+    batch.shuffle(&mut rng);
+    order.append(&mut batch);
+    order
+}
+
 /// Feeds all given transactions through the pipeline and produces an EvaluationLog.
 pub fn eval_with_executor<TComp: Compiler>(
     config: &GlobalConfig,
@@ -628,16 +1127,30 @@ pub fn eval_with_executor<TComp: Compiler>(
 
     let mut log = EvaluationLog { outputs: vec![] };
 
-    for (idx, command) in commands.iter().enumerate() {
-        match command {
+    let order = match config.shuffle_seed {
+        Some(seed) => shuffle_commands(commands, seed),
+        None => (0..commands.len()).collect(),
+    };
+    for idx in order {
+        match &commands[idx] {
             Command::Transaction(transaction) => {
-                let status = eval_transaction(&mut compiler, exec, idx, transaction, &mut log)?;
+                let mut status = eval_transaction(&mut compiler, exec, idx, transaction, &mut log)?;
+                if !check_sequence_numbers(exec, &transaction.config, &mut log) {
+                    status = Status::Failure;
+                }
+                if !check_ledger_version(exec, &transaction.config, &mut log) {
+                    status = Status::Failure;
+                }
                 log.append(EvaluationOutput::Status(status));
             }
             Command::BlockMetadata(block_metadata) => {
                 let status = eval_block_metadata(exec, block_metadata.clone(), &mut log)?;
                 log.append(EvaluationOutput::Status(status));
             }
+            Command::ClearCache => {
+                exec.clear_cache();
+                log.append(EvaluationOutput::Status(Status::Success));
+            }
         }
     }
 
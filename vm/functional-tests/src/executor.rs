@@ -7,7 +7,7 @@ use executor::account::{Account, AccountData};
 use starcoin_config::ChainNetwork;
 use starcoin_crypto::HashValue;
 use starcoin_genesis::Genesis;
-use starcoin_statedb::{ChainStateDB, ChainStateWriter};
+use starcoin_statedb::{ChainStateDB, ChainStateReader, ChainStateWriter};
 use starcoin_types::write_set::{WriteOp, WriteSetMut};
 use starcoin_types::{
     access_path::AccessPath,
@@ -22,16 +22,92 @@ use starcoin_vm_types::on_chain_resource::GlobalTimeOnChain;
 use starcoin_vm_types::{
     account_config::{association_address, AccountResource, BalanceResource},
     file_format::CompiledModule,
-    language_storage::ModuleId,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag},
     state_view::StateView,
     vm_status::VMStatus,
 };
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The state touched by executing transactions.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ExecutionFootprint {
+    /// The number of distinct access paths read.
+    pub reads: u64,
+    /// The number of distinct access paths written.
+    pub writes: u64,
+    /// The total bytes of the read and written values.
+    pub bytes: u64,
+}
+
+/// A state view records every access path read through it, and the size of the read value.
+struct FootprintStateView<'a> {
+    inner: &'a dyn StateView,
+    reads: RefCell<BTreeMap<AccessPath, usize>>,
+}
+
+impl<'a> FootprintStateView<'a> {
+    fn new(inner: &'a dyn StateView) -> Self {
+        Self {
+            inner,
+            reads: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    fn footprint<'b>(&self, write_sets: impl Iterator<Item = &'b WriteSet>) -> ExecutionFootprint {
+        let reads = self.reads.borrow();
+        let mut writes = BTreeSet::new();
+        let mut bytes = reads.values().sum::<usize>();
+        for (access_path, write_op) in write_sets.flat_map(|write_set| write_set.iter()) {
+            writes.insert(access_path);
+            if let WriteOp::Value(value) = write_op {
+                bytes += value.len();
+            }
+        }
+        ExecutionFootprint {
+            reads: reads.len() as u64,
+            writes: writes.len() as u64,
+            bytes: bytes as u64,
+        }
+    }
+}
+
+impl<'a> StateView for FootprintStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        let value = self.inner.get(access_path)?;
+        self.reads.borrow_mut().insert(
+            access_path.clone(),
+            value.as_ref().map(|value| value.len()).unwrap_or_default(),
+        );
+        Ok(value)
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.inner.is_genesis()
+    }
+}
+
+/// The values of every access path in the state of a `FakeExecutor`, captured at some point.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StateSnapshot(BTreeMap<AccessPath, Vec<u8>>);
 
 /// Provides an environment to run a VM instance.
 pub struct FakeExecutor {
     data_store: ChainStateDB,
     block_time: u64,
     net: ChainNetwork,
+    /// The number of transactions committed after genesis.
+    ledger_version: u64,
+    /// The state root of every ledger version, recorded as the transactions are committed.
+    version_roots: BTreeMap<u64, HashValue>,
 }
 
 impl Default for FakeExecutor {
@@ -50,6 +126,8 @@ impl FakeExecutor {
             data_store,
             block_time: 0,
             net,
+            ledger_version: 0,
+            version_roots: BTreeMap::new(),
         }
     }
 
@@ -60,6 +138,8 @@ impl FakeExecutor {
             data_store: ChainStateDB::mock(),
             block_time: 0,
             net,
+            ledger_version: 0,
+            version_roots: BTreeMap::new(),
         }
     }
 
@@ -82,6 +162,60 @@ impl FakeExecutor {
             .expect("statedb apply write set should work.");
     }
 
+    /// Applies the [`WriteSet`] of a kept transaction and bumps the ledger version.
+    /// The state roots before and after the transaction are recorded, to read the state
+    /// as of a prior version.
+    pub fn commit_transaction(&mut self, write_set: &WriteSet) {
+        let root = self.flush_state();
+        self.version_roots.insert(self.ledger_version, root);
+        self.apply_write_set(write_set);
+        self.ledger_version += 1;
+        let root = self.flush_state();
+        self.version_roots.insert(self.ledger_version, root);
+    }
+
+    /// The number of transactions committed after genesis.
+    pub fn ledger_version(&self) -> u64 {
+        self.ledger_version
+    }
+
+    /// Returns a view of the state as of the given ledger version,
+    /// or None if the version is not committed yet.
+    pub fn state_view_at(&self, version: u64) -> Option<ChainStateDB> {
+        if version == self.ledger_version {
+            return Some(self.data_store.change_root(self.flush_state()));
+        }
+        self.version_roots
+            .get(&version)
+            .map(|root| self.data_store.change_root(*root))
+    }
+
+    /// Reads the resource [`Value`] for an account as of the given ledger version.
+    pub fn read_account_resource_at(
+        &self,
+        account: &Account,
+        version: u64,
+    ) -> Option<AccountResource> {
+        let data_blob = self
+            .state_view_at(version)?
+            .get(&account.make_account_access_path())
+            .expect("read data store should work")?;
+        bcs_ext::from_bytes(data_blob.as_slice()).ok()
+    }
+
+    /// Drops the cached state of the data store, the committed state is kept and
+    /// is read from the storage again.
+    pub fn clear_cache(&mut self) {
+        let root = self.flush_state();
+        self.data_store = self.data_store.change_root(root);
+    }
+
+    fn flush_state(&self) -> HashValue {
+        let root = self.data_store.commit().expect("commit state should work.");
+        self.data_store.flush().expect("flush state should work.");
+        root
+    }
+
     /// Adds an account to this executor's data store.
     pub fn add_account_data(&mut self, account_data: &AccountData) {
         let write_set = account_data.to_writeset();
@@ -185,6 +319,26 @@ impl FakeExecutor {
         vm.execute_block_transactions(&self.data_store, txn_block, None)
     }
 
+    /// Executes the given block of transactions like `execute_block`,
+    /// and records the state footprint of the execution.
+    pub fn execute_block_with_footprint(
+        &self,
+        txn_block: Vec<SignedUserTransaction>,
+    ) -> Result<(Vec<(VMStatus, TransactionOutput)>, ExecutionFootprint)> {
+        let state_view = FootprintStateView::new(&self.data_store);
+        let mut vm = StarcoinVM::new();
+        let outputs = vm.execute_block_transactions(
+            &state_view,
+            txn_block
+                .into_iter()
+                .map(Transaction::UserTransaction)
+                .collect(),
+            None,
+        )?;
+        let footprint = state_view.footprint(outputs.iter().map(|(_, output)| output.write_set()));
+        Ok((outputs, footprint))
+    }
+
     pub fn execute_transaction(&self, txn: SignedUserTransaction) -> (VMStatus, TransactionOutput) {
         let txn_block = vec![txn];
         let mut outputs = self
@@ -210,6 +364,54 @@ impl FakeExecutor {
         &self.data_store
     }
 
+    /// Captures the current state, later passed to `state_diff` to get the state changes.
+    /// The applied write sets are committed first, as only committed state can be dumped.
+    pub fn capture_state(&self) -> StateSnapshot {
+        self.data_store.commit().expect("commit state should work.");
+        let chain_state_set = self.data_store.dump().expect("dump state should work.");
+        let mut values = BTreeMap::new();
+        for (address, account_state_set) in chain_state_set.state_sets() {
+            if let Some(code_set) = account_state_set.code_set() {
+                for (key, value) in code_set.iter() {
+                    let module_name = bcs_ext::from_bytes::<Identifier>(key)
+                        .expect("decode module name should work.");
+                    values.insert(
+                        AccessPath::code_access_path(*address, module_name),
+                        value.clone(),
+                    );
+                }
+            }
+            if let Some(resource_set) = account_state_set.resource_set() {
+                for (key, value) in resource_set.iter() {
+                    let struct_tag = bcs_ext::from_bytes::<StructTag>(key)
+                        .expect("decode struct tag should work.");
+                    values.insert(
+                        AccessPath::resource_access_path(*address, struct_tag),
+                        value.clone(),
+                    );
+                }
+            }
+        }
+        StateSnapshot(values)
+    }
+
+    /// Returns the writes which turn the `before` state into the current state.
+    pub fn state_diff(&self, before: &StateSnapshot) -> WriteSet {
+        let after = self.capture_state();
+        let mut write_set = WriteSetMut::default();
+        for (access_path, value) in &after.0 {
+            if before.0.get(access_path) != Some(value) {
+                write_set.push((access_path.clone(), WriteOp::Value(value.clone())));
+            }
+        }
+        for access_path in before.0.keys() {
+            if !after.0.contains_key(access_path) {
+                write_set.push((access_path.clone(), WriteOp::Deletion));
+            }
+        }
+        write_set.freeze().expect("freeze write set should work.")
+    }
+
     pub fn new_block(&mut self) {
         //TODO refactor block time.
         self.block_time += 1;
@@ -233,14 +435,24 @@ impl FakeExecutor {
         //TODO block event.
         //assert!(event.key() == &new_block_event_key());
         //assert!(bcs_ext::from_bytes::<NewBlockEvent>(event.event_data()).is_ok());
-        self.apply_write_set(output.write_set());
+        self.commit_transaction(output.write_set());
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::executor::FakeExecutor;
-    use executor::account::AccountData;
+    use executor::account::{Account, AccountData};
+    use executor::{
+        create_signed_txn_with_association_account, encode_transfer_script_function,
+        DEFAULT_MAX_GAS_AMOUNT,
+    };
+    use starcoin_config::ChainNetwork;
+    use starcoin_types::access_path::{AccessPath, DataPath};
+    use starcoin_types::transaction::{ScriptFunction, SignedUserTransaction, TransactionPayload};
+    use starcoin_vm_types::account_config::{core_code_address, STC_TOKEN_CODE_STR};
+    use starcoin_vm_types::identifier::Identifier;
+    use starcoin_vm_types::language_storage::ModuleId;
 
     #[test]
     fn test_executor() {
@@ -250,4 +462,141 @@ mod tests {
         let resource = executor.read_account_resource(account_data.account());
         assert!(resource.is_some());
     }
+
+    fn association_txn(
+        executor: &FakeExecutor,
+        payload: TransactionPayload,
+    ) -> SignedUserTransaction {
+        let sequence_number = executor
+            .read_account_resource(&Account::new_association())
+            .expect("association account should exist.")
+            .sequence_number();
+        create_signed_txn_with_association_account(
+            payload,
+            sequence_number,
+            DEFAULT_MAX_GAS_AMOUNT,
+            1,
+            executor.read_timestamp() + 3600,
+            &ChainNetwork::new_test(),
+        )
+    }
+
+    #[test]
+    fn test_execution_footprint() {
+        let executor = FakeExecutor::new();
+        let empty_script = ScriptFunction::new(
+            ModuleId::new(
+                core_code_address(),
+                Identifier::new("EmptyScripts").unwrap(),
+            ),
+            Identifier::new("empty_script").unwrap(),
+            vec![],
+            vec![],
+        );
+        let (_, trivial_footprint) = executor
+            .execute_block_with_footprint(vec![association_txn(
+                &executor,
+                TransactionPayload::ScriptFunction(empty_script),
+            )])
+            .unwrap();
+        // transfer to a new account reads the token info, balances and event handles of both accounts.
+        let transfer_script = encode_transfer_script_function(*Account::new().address(), 1000);
+        let (_, transfer_footprint) = executor
+            .execute_block_with_footprint(vec![association_txn(
+                &executor,
+                TransactionPayload::ScriptFunction(transfer_script),
+            )])
+            .unwrap();
+        assert!(trivial_footprint.reads > 0);
+        assert!(transfer_footprint.reads > trivial_footprint.reads);
+        assert!(transfer_footprint.writes > trivial_footprint.writes);
+        assert!(transfer_footprint.bytes > trivial_footprint.bytes);
+    }
+
+    #[test]
+    fn test_state_diff() {
+        let mut executor = FakeExecutor::new();
+        let before = executor.capture_state();
+        assert!(executor.state_diff(&before).is_empty());
+
+        let receiver = Account::new();
+        let transfer_script = encode_transfer_script_function(*receiver.address(), 1000);
+        let (_, output) = executor
+            .execute_block(vec![association_txn(
+                &executor,
+                TransactionPayload::ScriptFunction(transfer_script),
+            )])
+            .unwrap()
+            .pop()
+            .unwrap();
+        executor.apply_write_set(output.write_set());
+
+        let diff = executor.state_diff(&before);
+        let balance_paths = diff
+            .iter()
+            .map(|(access_path, _)| access_path)
+            .filter(|access_path| match &access_path.path {
+                DataPath::Resource(struct_tag) => struct_tag.name.as_str() == "Balance",
+                DataPath::Code(_) => false,
+            })
+            .cloned()
+            .collect::<Vec<AccessPath>>();
+        let mut expected = vec![
+            Account::new_association().make_balance_access_path(STC_TOKEN_CODE_STR),
+            receiver.make_balance_access_path(STC_TOKEN_CODE_STR),
+        ];
+        expected.sort();
+        assert_eq!(balance_paths, expected);
+    }
+
+    #[test]
+    fn test_clear_cache() {
+        let mut executor = FakeExecutor::new();
+        let association = Account::new_association();
+        let account = executor.read_account_resource(&association).unwrap();
+        assert!(executor.data_store.cache_len() > 0);
+
+        executor.clear_cache();
+        assert_eq!(executor.data_store.cache_len(), 0);
+        assert_eq!(
+            executor
+                .read_account_resource(&association)
+                .map(|resource| resource.sequence_number()),
+            Some(account.sequence_number())
+        );
+    }
+
+    #[test]
+    fn test_ledger_version() {
+        let mut executor = FakeExecutor::new();
+        let association = Account::new_association();
+        assert_eq!(executor.ledger_version(), 0);
+        let sequence_number = |executor: &FakeExecutor, version| {
+            executor
+                .read_account_resource_at(&association, version)
+                .map(|resource| resource.sequence_number())
+        };
+        let genesis_sequence_number = sequence_number(&executor, 0).unwrap();
+
+        for _ in 0..3 {
+            let transfer_script = encode_transfer_script_function(*Account::new().address(), 1000);
+            let (_, output) = executor
+                .execute_block(vec![association_txn(
+                    &executor,
+                    TransactionPayload::ScriptFunction(transfer_script),
+                )])
+                .unwrap()
+                .pop()
+                .unwrap();
+            executor.commit_transaction(output.write_set());
+        }
+        assert_eq!(executor.ledger_version(), 3);
+        for version in 0..=3 {
+            assert_eq!(
+                sequence_number(&executor, version),
+                Some(genesis_sequence_number + version)
+            );
+        }
+        assert!(executor.state_view_at(4).is_none());
+    }
 }
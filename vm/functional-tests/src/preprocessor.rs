@@ -40,12 +40,14 @@ pub struct RawTransactionInput {
 pub enum RawCommand {
     Transaction(RawTransactionInput),
     BlockMetadata(Vec<BlockEntry>),
+    ClearCache,
 }
 
 fn is_empty_command(cmd: &RawCommand) -> bool {
     match cmd {
         RawCommand::Transaction(txn) => txn.text.is_empty() && txn.config_entries.is_empty(),
         RawCommand::BlockMetadata(entries) => entries.is_empty(),
+        RawCommand::ClearCache => false,
     }
 }
 
@@ -75,9 +77,19 @@ fn check_raw_command(cmd: &RawCommand) -> Result<()> {
                 Ok(())
             }
         }
+        RawCommand::ClearCache => Ok(()),
     }
 }
 
+/// Checks whether a line denotes a command clearing the cache of the executor.
+fn is_clear_cache(s: &str) -> bool {
+    let s = s.trim();
+    if !s.starts_with("//!") {
+        return false;
+    }
+    s[3..].trim_start() == "clear-cache"
+}
+
 fn new_command(input: &str) -> Option<RawCommand> {
     if is_new_transaction(input) {
         return Some(RawCommand::Transaction(RawTransactionInput {
@@ -88,6 +100,9 @@ fn new_command(input: &str) -> Option<RawCommand> {
     if is_new_block(input) {
         return Some(RawCommand::BlockMetadata(vec![]));
     }
+    if is_clear_cache(input) {
+        return Some(RawCommand::ClearCache);
+    }
     None
 }
 
@@ -150,6 +165,15 @@ pub fn split_input(
                     continue;
                 }
             }
+            RawCommand::ClearCache => {
+                if !line.trim().is_empty() {
+                    return Err(ErrorKind::Other(format!(
+                        "clear-cache doesn't take any input, got '{}'",
+                        line.trim()
+                    ))
+                    .into());
+                }
+            }
         }
     }
 
@@ -173,6 +197,7 @@ pub fn build_transactions<'a>(
             RawCommand::BlockMetadata(entries) => Ok(Command::BlockMetadata(build_block_metadata(
                 config, &entries,
             )?)),
+            RawCommand::ClearCache => Ok(Command::ClearCache),
         })
         .collect()
 }
@@ -0,0 +1,77 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::global::Config as GlobalConfig,
+    evaluator::{shuffle_commands, Command, EventOutput},
+    preprocessor::{build_transactions, split_input},
+};
+use starcoin_types::{account_address::AccountAddress, contract_event::ContractEvent};
+use starcoin_vm_types::{
+    account_config::WithdrawEvent, event::EventKey, language_storage::TypeTag,
+    move_resource::MoveResource, token::stc::STC_TOKEN_CODE,
+};
+
+#[rustfmt::skip]
+#[test]
+fn shuffle_commands_is_deterministic() {
+    let mut input = String::new();
+    for i in 0..10 {
+        if i == 5 {
+            input.push_str(r"
+                //! block-prologue
+                //! author: genesis
+                //! block-number: 1
+                //! block-time: 1000
+            ");
+        }
+        input.push_str(r"
+            //! new-transaction
+            script { fun main() {} }
+        ");
+    }
+    let (config, _, transactions) = split_input(input.lines()).unwrap();
+    let config = GlobalConfig::build(&config).unwrap();
+    let commands = build_transactions(&config, &transactions).unwrap();
+    assert_eq!(commands.len(), 11);
+    assert!(matches!(commands[5], Command::BlockMetadata(_)));
+
+    let order = shuffle_commands(&commands, 42);
+    assert_eq!(order, shuffle_commands(&commands, 42));
+    assert_ne!(order, (0..commands.len()).collect::<Vec<_>>());
+    // the transactions are only shuffled within their block.
+    assert_eq!(order[5], 5);
+    let mut first_block = order[..5].to_vec();
+    first_block.sort_unstable();
+    assert_eq!(first_block, vec![0, 1, 2, 3, 4]);
+    let mut second_block = order[6..].to_vec();
+    second_block.sort_unstable();
+    assert_eq!(second_block, vec![6, 7, 8, 9, 10]);
+}
+
+#[test]
+fn event_output_decodes_common_events() {
+    let key = EventKey::new_from_address(&AccountAddress::random(), 0);
+    let withdraw = WithdrawEvent::new(100, STC_TOKEN_CODE.clone(), vec![]);
+    let event = ContractEvent::new(
+        key,
+        0,
+        TypeTag::Struct(WithdrawEvent::struct_tag()),
+        bcs_ext::to_bytes(&withdraw).unwrap(),
+    );
+    assert_eq!(
+        format!("{:?}", EventOutput::decode(&event)),
+        format!(
+            "event WithdrawEvent amount: 100 token_code: {} type: {}",
+            *STC_TOKEN_CODE,
+            event.type_tag()
+        )
+    );
+
+    // the fields of the other events are not decoded.
+    let event = ContractEvent::new(key, 1, TypeTag::U64, bcs_ext::to_bytes(&1u64).unwrap());
+    assert_eq!(
+        format!("{:?}", EventOutput::decode(&event)),
+        "event u64 type: u64"
+    );
+}
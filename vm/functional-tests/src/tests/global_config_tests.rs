@@ -38,6 +38,20 @@ fn parse_account_negative() {
     }
 }
 
+#[test]
+fn parse_shuffle_seed() {
+    for s in &["//! shuffle-seed: 42", "//!shuffle-seed:0"] {
+        s.parse::<Entry>().unwrap();
+    }
+    for s in &[
+        "//! shuffle-seed:",
+        "//! shuffle-seed: -1",
+        "//! shuffle-seed: abc",
+    ] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
+
 /// Parses each line in the given input as an entry and build global config.
 pub fn parse_and_build_config(s: &str) -> Result<Config> {
     Config::build(&parse_each_line_as::<Entry>(s)?)
@@ -109,3 +123,22 @@ fn build_global_config_6() {
     ");
     assert!(config.is_err());
 }
+
+#[rustfmt::skip]
+#[test]
+fn build_global_config_shuffle_seed() {
+    let config = parse_and_build_config(r"
+        //! account: alice
+    ").unwrap();
+    assert_eq!(config.shuffle_seed, None);
+
+    let config = parse_and_build_config(r"
+        //! shuffle-seed: 42
+    ").unwrap();
+    assert_eq!(config.shuffle_seed, Some(42));
+
+    parse_and_build_config(r"
+        //! shuffle-seed: 42
+        //! shuffle-seed: 43
+    ").unwrap_err();
+}
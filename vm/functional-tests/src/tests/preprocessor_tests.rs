@@ -69,3 +69,27 @@ fn parse_input_empty_transaction_with_config() {
         main() {}
     ").unwrap_err();
 }
+
+#[rustfmt::skip]
+#[test]
+fn parse_input_clear_cache() {
+    parse_input(r"
+        main() {}
+
+        //! clear-cache
+
+        //! new-transaction
+        main() {}
+    ").unwrap();
+}
+
+#[rustfmt::skip]
+#[test]
+fn parse_input_clear_cache_with_input() {
+    parse_input(r"
+        main() {}
+
+        //! clear-cache
+        main() {}
+    ").unwrap_err();
+}
@@ -4,7 +4,7 @@
 use crate::{
     config::{
         global::Config as GlobalConfig,
-        transaction::{is_new_transaction, Config, Entry},
+        transaction::{is_new_transaction, Config, Entry, GasBound},
     },
     errors::*,
     tests::{
@@ -95,6 +95,91 @@ fn parse_sequence_number() {
     // Rewrite the parser to handle this case properly.
 }
 
+#[test]
+fn parse_expected_sequence_number() {
+    for s in &[
+        "//! seq: alice = 5",
+        "//!seq:bob=0",
+        "//! seq:  alice =  123",
+    ] {
+        s.parse::<Entry>().unwrap();
+    }
+
+    for s in &[
+        "//! seq:",
+        "//! seq: alice",
+        "//! seq: = 5",
+        "//! seq: alice = abc",
+        "//! seq: alice = 1, 2",
+    ] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
+
+#[test]
+fn parse_footprint() {
+    for s in &["//! footprint: true", "//!footprint:false"] {
+        s.parse::<Entry>().unwrap();
+    }
+
+    for s in &["//! footprint:", "//! footprint: yes"] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
+
+#[test]
+fn parse_state_diff() {
+    for s in &["//! state-diff: true", "//!state-diff:false"] {
+        s.parse::<Entry>().unwrap();
+    }
+
+    for s in &["//! state-diff:", "//! state-diff: yes"] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
+
+#[test]
+fn parse_no_state_change() {
+    for s in &["//! no-state-change: true", "//!no-state-change:false"] {
+        s.parse::<Entry>().unwrap();
+    }
+
+    for s in &["//! no-state-change:", "//! no-state-change: yes"] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
+
+#[test]
+fn parse_expected_writes() {
+    for s in &[
+        "//! writes: []",
+        "//! writes: [0x1/1/0x1::TransactionFee::TransactionFee<0x1::STC::STC>]",
+        "//! writes: [{{alice}}/resource/0x1::Account::Account, 0x1/code/Account]",
+        "//!writes:[{{bob}}/resource/0x1::M::Pair<0x1::STC::STC,u64>]",
+    ] {
+        s.parse::<Entry>().unwrap();
+    }
+
+    for s in &[
+        "//! writes:",
+        "//! writes: 0x1/resource/0x1::Account::Account",
+        "//! writes: [0x1/resource]",
+        "//! writes: [0x1/data/0x1::Account::Account]",
+        "//! writes: [0x1/code/0x1::Account::Account]",
+    ] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
+
+#[test]
+fn parse_expected_status() {
+    for s in &["//! status: EXECUTED", "//!status:out_of_gas"] {
+        s.parse::<Entry>().unwrap();
+    }
+
+    "//! status:".parse::<Entry>().unwrap_err();
+}
+
 #[test]
 fn parse_new_transaction() {
     assert!(is_new_transaction("//! new-transaction"));
@@ -145,3 +230,133 @@ fn build_transaction_config_3() {
         //! args: {{bob}}
     ").unwrap_err();
 }
+
+#[rustfmt::skip]
+#[test]
+fn build_transaction_config_expected_sequence_number() {
+    let global = parse_and_build_global_config(r"
+        //! account: bob
+        //! account: alice
+    ").unwrap();
+
+    let config = parse_and_build_config(&global, r"
+        //! sender: alice
+        //! seq: alice = 1
+        //! seq: bob = 0
+    ").unwrap();
+    assert_eq!(config.expected_sequence_numbers.len(), 2);
+    assert_eq!(config.expected_sequence_numbers[0].1, 1);
+
+    parse_and_build_config(&global, r"
+        //! seq: alice = 1
+        //! seq: alice = 2
+    ").unwrap_err();
+
+    parse_and_build_config(&global, r"
+        //! seq: carol = 1
+    ").unwrap_err();
+}
+
+#[test]
+fn parse_expected_ledger_version() {
+    for s in &[
+        "//! ledger-version: 3",
+        "//!ledger-version:0",
+        "//! seq-at: 1, alice = 0",
+        "//!seq-at:0,bob=2",
+    ] {
+        s.parse::<Entry>().unwrap();
+    }
+
+    for s in &[
+        "//! ledger-version:",
+        "//! ledger-version: abc",
+        "//! seq-at: alice = 0",
+        "//! seq-at: 1, alice",
+        "//! seq-at: abc, alice = 0",
+    ] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
+
+#[rustfmt::skip]
+#[test]
+fn build_transaction_config_expected_ledger_version() {
+    let global = parse_and_build_global_config(r"
+        //! account: alice
+    ").unwrap();
+
+    let config = parse_and_build_config(&global, r"
+        //! ledger-version: 3
+        //! seq-at: 1, alice = 1
+        //! seq-at: 2, alice = 2
+    ").unwrap();
+    assert_eq!(config.expected_ledger_version, Some(3));
+    assert_eq!(config.expected_sequence_numbers_at.len(), 2);
+    assert_eq!(config.expected_sequence_numbers_at[0].0, 1);
+
+    parse_and_build_config(&global, r"
+        //! ledger-version: 1
+        //! ledger-version: 2
+    ").unwrap_err();
+
+    parse_and_build_config(&global, r"
+        //! seq-at: 1, alice = 1
+        //! seq-at: 1, alice = 2
+    ").unwrap_err();
+}
+
+#[test]
+fn parse_expected_gas_used() {
+    for s in &[
+        "//! gas-used: <= 5000",
+        "//!gas-used:>=0",
+        "//! gas-used: == 700",
+    ] {
+        s.parse::<Entry>().unwrap();
+    }
+
+    for s in &[
+        "//! gas-used:",
+        "//! gas-used: 5000",
+        "//! gas-used: < 5000",
+        "//! gas-used: <= abc",
+        "//! gas-used: <= -1",
+    ] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
+
+#[rustfmt::skip]
+#[test]
+fn build_transaction_config_expected_gas_used() {
+    let global = parse_and_build_global_config(r"
+        //! account: alice
+    ").unwrap();
+
+    let config = parse_and_build_config(&global, r"
+        //! gas-used: >= 100
+        //! gas-used: <= 5000
+    ").unwrap();
+    assert_eq!(config.expected_gas_used, vec![GasBound::AtLeast(100), GasBound::AtMost(5000)]);
+    assert!(config.expected_gas_used.iter().all(|bound| bound.check(100)));
+    assert!(!config.expected_gas_used.iter().all(|bound| bound.check(5001)));
+}
+
+#[test]
+fn parse_expiration_time() {
+    for (s, expiration_time) in &[
+        ("//! expiration-time: 3600", 3600),
+        ("//!expiration-time:0", 0),
+        ("//! expiration-time: -10", -10),
+    ] {
+        match s.parse::<Entry>().unwrap() {
+            Entry::ExpirationTime(t) => assert_eq!(t, *expiration_time),
+            _ => panic!("expected expiration time entry."),
+        }
+    }
+
+    for s in &["//! expiration-time:", "//! expiration-time: abc"] {
+        s.parse::<Entry>().unwrap_err();
+    }
+}
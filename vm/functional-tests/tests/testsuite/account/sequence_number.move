@@ -0,0 +1,45 @@
+//! account: alice, 10000 0x1::STC::STC
+//! account: bob, 10000 0x1::STC::STC
+
+//! sender: alice
+//! seq: alice = 1
+//! seq: bob = 0
+script {
+    fun main() {
+    }
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+//! seq: alice = 2
+address bob = {{bob}};
+script {
+    use 0x1::STC::STC;
+    use 0x1::Account;
+    fun main(account: signer) {
+        Account::pay_from<STC>(&account, @bob, 10);
+    }
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: bob
+//! seq: alice = 2
+//! seq: bob = 1
+script {
+    fun main() {
+    }
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: bob
+//! seq: bob = 2
+script {
+    fun main() {
+        abort 77
+    }
+}
+// check: ABORTED
+// check: 77
@@ -0,0 +1,16 @@
+//! account: alice, 10000 0x1::STC::STC
+//! account: bob
+
+//! sender: alice
+address bob = {{bob}};
+script {
+    use 0x1::Account;
+    use 0x1::STC::STC;
+
+    fun main(account: signer) {
+        Account::pay_from<STC>(&account, @bob, 100);
+    }
+}
+// check: EXECUTED
+// check: "event WithdrawEvent amount: 100"
+// check: "event DepositEvent amount: 100"
@@ -0,0 +1,21 @@
+//! account: alice, 10000 0x1::STC::STC
+
+//! sender: alice
+address alice = {{alice}};
+module alice::M {
+    public fun value(): u64 { 42 }
+}
+// check: EXECUTED
+
+//! clear-cache
+
+//! new-transaction
+//! sender: alice
+address alice = {{alice}};
+script {
+    use alice::M;
+    fun main() {
+        assert(M::value() == 42, 1);
+    }
+}
+// check: EXECUTED
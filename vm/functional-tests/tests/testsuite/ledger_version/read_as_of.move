@@ -0,0 +1,31 @@
+//! account: alice, 10000 0x1::STC::STC
+
+//! sender: alice
+//! ledger-version: 1
+script {
+    fun main() {
+    }
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+//! ledger-version: 2
+script {
+    fun main() {
+    }
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+//! ledger-version: 3
+//! seq: alice = 3
+//! seq-at: 0, alice = 0
+//! seq-at: 1, alice = 1
+//! seq-at: 2, alice = 2
+script {
+    fun main() {
+    }
+}
+// check: EXECUTED
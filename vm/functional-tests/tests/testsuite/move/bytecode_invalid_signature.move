@@ -0,0 +1,6 @@
+// A module supplied as raw bytes skips the compiler, the bytes contain a signature table
+// with an invalid signature token 0xFF, which must be rejected by the verifier.
+
+//! new-transaction
+bytecode::module::0xa11ceb0b010000000105000201ff00
+// check: VerificationError
@@ -0,0 +1,40 @@
+//! account: alice, 90000 0x1::STC::STC
+//! account: bob, 90000 0x1::STC::STC
+
+//! new-transaction
+//! sender: alice
+//! footprint: true
+script {
+    fun main() {
+    }
+}
+// check: ExecutionFootprint
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+//! footprint: true
+address alice = {{alice}};
+address bob = {{bob}};
+
+script {
+    use 0x1::Account;
+    use 0x1::STC::STC;
+
+    fun main(account: signer) {
+        Account::pay_from<STC>(&account, @bob, 10);
+        assert(Account::balance<STC>(@alice) > 0, 1000);
+        assert(Account::balance<STC>(@bob) > 0, 1001);
+    }
+}
+// check: ExecutionFootprint
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+script {
+    fun main() {
+    }
+}
+// not: ExecutionFootprint
+// check: EXECUTED
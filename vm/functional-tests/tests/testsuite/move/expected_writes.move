@@ -0,0 +1,36 @@
+// the writes directive asserts the exact access paths written by the transaction.
+
+//! account: alice, 90000 0x1::STC::STC
+//! account: bob, 90000 0x1::STC::STC
+
+//! new-transaction
+//! sender: alice
+//! gas-price: 1
+//! writes: [{{alice}}/resource/0x1::Account::Account, {{alice}}/resource/0x1::Account::Balance<0x1::STC::STC>, {{bob}}/resource/0x1::Account::Account, {{bob}}/resource/0x1::Account::Balance<0x1::STC::STC>, 0x1/resource/0x1::TransactionFee::TransactionFee<0x1::STC::STC>]
+address bob = {{bob}};
+
+script {
+    use 0x1::Account;
+    use 0x1::STC::STC;
+
+    fun main(account: signer) {
+        Account::pay_from<STC>(&account, @bob, 10);
+    }
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+//! gas-price: 1
+//! writes: [{{alice}}/resource/0x1::Account::Account, {{alice}}/resource/0x1::Account::Balance<0x1::STC::STC>, 0x1/resource/0x1::TransactionFee::TransactionFee<0x1::STC::STC>]
+address bob = {{bob}};
+
+script {
+    use 0x1::Account;
+    use 0x1::STC::STC;
+
+    fun main(account: signer) {
+        Account::pay_from<STC>(&account, @bob, 10);
+    }
+}
+// check: write set mismatch
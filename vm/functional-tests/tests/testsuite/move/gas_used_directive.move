@@ -0,0 +1,25 @@
+// the gas-used directive asserts the gas used by the transaction is within the bounds.
+
+//! gas-used: >= 1
+//! gas-used: <= 100000
+script {
+    fun main() {
+        let i = 0;
+        while (i < 10) {
+            i = i + 1;
+        };
+    }
+}
+// check: EXECUTED
+
+//! new-transaction
+//! gas-used: <= 1
+script {
+    fun main() {
+        let i = 0;
+        while (i < 10) {
+            i = i + 1;
+        };
+    }
+}
+// check: gas used out of bound
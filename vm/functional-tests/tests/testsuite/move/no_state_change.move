@@ -0,0 +1,35 @@
+// the no-state-change directive asserts the transaction writes nothing
+// but the gas and sequence number bookkeeping of the sender.
+
+//! account: alice, 90000 0x1::STC::STC
+//! account: bob, 90000 0x1::STC::STC
+
+//! new-transaction
+//! sender: alice
+//! no-state-change: true
+address alice = {{alice}};
+
+script {
+    use 0x1::Account;
+    use 0x1::STC::STC;
+
+    fun main() {
+        assert(Account::balance<STC>(@alice) > 0, 1000);
+    }
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+//! no-state-change: true
+address bob = {{bob}};
+
+script {
+    use 0x1::Account;
+    use 0x1::STC::STC;
+
+    fun main(account: signer) {
+        Account::pay_from<STC>(&account, @bob, 10);
+    }
+}
+// check: unexpected state change
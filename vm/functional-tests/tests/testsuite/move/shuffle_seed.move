@@ -0,0 +1,25 @@
+// the transactions within a block are evaluated in an order shuffled by the seed.
+
+//! shuffle-seed: 7
+//! account: alice
+//! account: bob
+
+//! sender: alice
+script {
+    fun main() {}
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: bob
+script {
+    fun main() {}
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+script {
+    fun main() {}
+}
+// check: EXECUTED
@@ -0,0 +1,28 @@
+//! account: alice, 90000 0x1::STC::STC
+//! account: bob, 90000 0x1::STC::STC
+
+//! new-transaction
+//! sender: alice
+//! state-diff: true
+address bob = {{bob}};
+
+script {
+    use 0x1::Account;
+    use 0x1::STC::STC;
+
+    fun main(account: signer) {
+        Account::pay_from<STC>(&account, @bob, 10);
+    }
+}
+// check: StateDiff
+// check: Balance
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+script {
+    fun main() {
+    }
+}
+// not: StateDiff
+// check: EXECUTED
@@ -0,0 +1,20 @@
+// the status directive asserts the top-level status code of the transaction.
+
+//! status: EXECUTED
+script {
+    fun main() {
+        let i = 0;
+        while (i < 10) {
+            i = i + 1;
+        };
+    }
+}
+
+//! new-transaction
+//! max-gas: 700
+//! status: OUT_OF_GAS
+script {
+    fun main() {
+        loop {}
+    }
+}
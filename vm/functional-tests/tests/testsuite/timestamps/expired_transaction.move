@@ -0,0 +1,30 @@
+//! account: alice
+
+//! block-prologue
+//! author: genesis
+//! block-number: 1
+//! block-time: 86400000
+
+//! new-transaction
+//! sender: alice
+//! expiration-time: -1
+script {
+    fun main() {}
+}
+// check: DISCARD
+// check: "DISCARD TRANSACTION_EXPIRED"
+
+//! new-transaction
+//! sender: alice
+//! expiration-time: 0
+script {
+    fun main() {}
+}
+// check: "DISCARD TRANSACTION_EXPIRED"
+
+//! new-transaction
+//! sender: alice
+script {
+    fun main() {}
+}
+// check: EXECUTED
@@ -19,6 +19,7 @@ use std::{collections::HashMap, sync::Arc};
 mod consensus_config;
 mod dao_config;
 mod genesis_gas_schedule;
+mod reward_config;
 mod version;
 mod vm_config;
 
@@ -28,6 +29,7 @@ pub use self::{
     genesis_gas_schedule::{
         init_cost_table, initial_instruction_table, initial_native_table, v1_native_table,
     },
+    reward_config::RewardConfig,
     version::{version_config_type_tag, Version, VERSION_CONFIG_IDENTIFIER},
     vm_config::{vm_config_type_tag, TransactionPublishOption, VMConfig, SCRIPT_HASH_LENGTH},
 };
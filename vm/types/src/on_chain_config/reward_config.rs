@@ -0,0 +1,16 @@
+use crate::on_chain_config::OnChainConfig;
+use serde::{Deserialize, Serialize};
+
+const REWARD_CONFIG_MODULE_NAME: &str = "RewardConfig";
+
+/// The block reward config on chain.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RewardConfig {
+    /// how many blocks delay reward distribution.
+    pub reward_delay: u64,
+}
+
+impl OnChainConfig for RewardConfig {
+    const MODULE_IDENTIFIER: &'static str = REWARD_CONFIG_MODULE_NAME;
+    const CONF_IDENTIFIER: &'static str = REWARD_CONFIG_MODULE_NAME;
+}
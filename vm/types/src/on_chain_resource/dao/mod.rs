@@ -7,6 +7,7 @@ use crate::account_config::CORE_CODE_ADDRESS;
 use crate::event::EventHandle;
 use crate::language_storage::{StructTag, TypeTag};
 use crate::move_resource::MoveResource;
+use anyhow::{ensure, Result};
 use serde::{Deserialize, Serialize};
 
 mod actions;
@@ -101,6 +102,77 @@ where
     }
 }
 
+/// The state of a proposal, same as the state constants of the `Dao` module.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ProposalState {
+    Pending = 1,
+    Active = 2,
+    Defeated = 3,
+    Agreed = 4,
+    Queued = 5,
+    Executable = 6,
+    Extracted = 7,
+}
+
+/// The fields of a `Proposal` except its action, which is read without knowing the action type.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProposalSummary {
+    pub id: u64,
+    pub proposer: AccountAddress,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub for_votes: u128,
+    pub against_votes: u128,
+    pub eta: u64,
+    pub action_delay: u64,
+    pub quorum_votes: u128,
+    /// the action is taken out of the proposal once it is executed.
+    pub has_action: bool,
+}
+
+impl ProposalSummary {
+    /// The bcs size of the fields before the action, and the option tag of the action, which
+    /// is encoded as the same byte of a bool.
+    const ENCODED_SIZE: usize = 8 * 5 + AccountAddress::LENGTH + 16 * 3 + 1;
+
+    /// Check the struct tag is a `Proposal` of the token, with any action type.
+    pub fn is_proposal_of(struct_tag: &StructTag, token_type_tag: &StructTag) -> bool {
+        struct_tag.address == CORE_CODE_ADDRESS
+            && struct_tag.module.as_str() == DaoGlobalInfo::MODULE_NAME
+            && struct_tag.name.as_str() == "Proposal"
+            && struct_tag.type_params.first() == Some(&TypeTag::Struct(token_type_tag.clone()))
+    }
+
+    /// Decode the summary from the bcs bytes of a `Proposal` resource.
+    pub fn decode(proposal_bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            proposal_bytes.len() >= Self::ENCODED_SIZE,
+            "Invalid proposal bytes length: {}",
+            proposal_bytes.len()
+        );
+        bcs_ext::from_bytes(&proposal_bytes[..Self::ENCODED_SIZE])
+    }
+
+    /// The state of the proposal at `now_millis`, same as `Dao::proposal_state`.
+    pub fn state(&self, now_millis: u64) -> ProposalState {
+        if now_millis < self.start_time {
+            ProposalState::Pending
+        } else if now_millis <= self.end_time {
+            ProposalState::Active
+        } else if self.for_votes <= self.against_votes || self.for_votes < self.quorum_votes {
+            ProposalState::Defeated
+        } else if self.eta == 0 {
+            ProposalState::Agreed
+        } else if now_millis < self.eta {
+            ProposalState::Queued
+        } else if self.has_action {
+            ProposalState::Executable
+        } else {
+            ProposalState::Extracted
+        }
+    }
+}
+
 /// User vote info.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Vote {
@@ -118,3 +190,18 @@ impl MoveResource for Vote {
     const MODULE_NAME: &'static str = "Dao";
     const STRUCT_NAME: &'static str = "Vote";
 }
+
+impl Vote {
+    pub fn struct_tag_for(token_type_tag: StructTag) -> StructTag {
+        StructTag {
+            address: CORE_CODE_ADDRESS,
+            module: Vote::module_identifier(),
+            name: Vote::struct_identifier(),
+            type_params: vec![TypeTag::Struct(token_type_tag)],
+        }
+    }
+
+    pub fn resource_path_for(voter: AccountAddress, token_type_tag: StructTag) -> AccessPath {
+        AccessPath::resource_access_path(voter, Vote::struct_tag_for(token_type_tag))
+    }
+}
@@ -21,13 +21,17 @@ pub static STC_TOKEN_CODE: Lazy<TokenCode> = Lazy::new(|| {
 
 static STC_IDENTIFIER: Lazy<Identifier> = Lazy::new(|| Identifier::new(STC_NAME).unwrap());
 
-pub fn stc_type_tag() -> TypeTag {
-    TypeTag::Struct(StructTag {
+pub fn stc_struct_tag() -> StructTag {
+    StructTag {
         address: CORE_CODE_ADDRESS,
         module: STC_IDENTIFIER.clone(),
         name: STC_IDENTIFIER.clone(),
         type_params: vec![],
-    })
+    }
+}
+
+pub fn stc_type_tag() -> TypeTag {
+    TypeTag::Struct(stc_struct_tag())
 }
 
 pub const SYMBOL_NANOSTC: &str = "nanoSTC";
@@ -4,3 +4,4 @@
 mod access_path_test;
 mod block_metadata_test;
 mod transaction_test;
+mod write_set_test;
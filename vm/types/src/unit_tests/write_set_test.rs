@@ -0,0 +1,144 @@
+use crate::access_path::AccessPath;
+use crate::write_set::{WriteOp, WriteSet, WriteSetMut};
+
+#[test]
+fn test_write_set_get() {
+    let value_path = AccessPath::random_resource();
+    let deletion_path = AccessPath::random_code();
+    let missing_path = AccessPath::random_resource();
+    let write_set = WriteSetMut::new(vec![
+        (value_path.clone(), WriteOp::Value(vec![1, 2, 3])),
+        (deletion_path.clone(), WriteOp::Deletion),
+    ])
+    .freeze()
+    .unwrap();
+    assert_eq!(write_set.len(), 2);
+    assert!(!write_set.is_empty());
+    assert_eq!(
+        write_set.get(&value_path),
+        Some(&WriteOp::Value(vec![1, 2, 3]))
+    );
+    assert_eq!(write_set.get(&deletion_path), Some(&WriteOp::Deletion));
+    assert_eq!(write_set.get(&missing_path), None);
+
+    let empty = WriteSet::default();
+    assert_eq!(empty.len(), 0);
+    assert!(empty.is_empty());
+    assert_eq!(empty.get(&value_path), None);
+}
+
+#[test]
+fn test_write_set_sorted_by_access_path() {
+    let mut access_paths: Vec<AccessPath> =
+        (0..20).map(|_| AccessPath::random_resource()).collect();
+    let write_set = access_paths
+        .iter()
+        .enumerate()
+        .map(|(i, access_path)| (access_path.clone(), WriteOp::Value(vec![i as u8])))
+        .collect::<WriteSetMut>()
+        .freeze()
+        .unwrap();
+    access_paths.sort();
+    assert_eq!(
+        write_set
+            .iter()
+            .map(|(access_path, _)| access_path.clone())
+            .collect::<Vec<_>>(),
+        access_paths
+    );
+    for access_path in access_paths.iter() {
+        assert!(write_set.get(access_path).is_some());
+    }
+}
+
+#[test]
+fn test_write_set_duplicated_access_path() {
+    let access_path = AccessPath::random_resource();
+    let write_set = WriteSetMut::new(vec![
+        (access_path.clone(), WriteOp::Value(vec![1])),
+        (access_path.clone(), WriteOp::Deletion),
+    ])
+    .freeze()
+    .unwrap();
+    assert_eq!(write_set.len(), 1);
+    assert_eq!(write_set.get(&access_path), Some(&WriteOp::Deletion));
+}
+
+#[test]
+fn test_write_set_deserialize_sorted() {
+    let access_paths: Vec<AccessPath> = (0..5).map(|_| AccessPath::random_resource()).collect();
+    // an unsorted write set from the wire is sorted when deserialized.
+    let write_set_mut = WriteSetMut::new(
+        access_paths
+            .iter()
+            .map(|access_path| (access_path.clone(), WriteOp::Deletion))
+            .collect(),
+    );
+    let bytes = bcs_ext::to_bytes(&write_set_mut).unwrap();
+    let write_set: WriteSet = bcs_ext::from_bytes(&bytes).unwrap();
+    assert_eq!(write_set, write_set_mut.freeze().unwrap());
+    for access_path in access_paths.iter() {
+        assert_eq!(write_set.get(access_path), Some(&WriteOp::Deletion));
+    }
+}
+
+#[test]
+fn test_write_set_merge_disjoint() {
+    let mut access_paths: Vec<AccessPath> = (0..4).map(|_| AccessPath::random_resource()).collect();
+    let left = WriteSetMut::new(vec![
+        (access_paths[0].clone(), WriteOp::Value(vec![0])),
+        (access_paths[1].clone(), WriteOp::Deletion),
+    ])
+    .freeze()
+    .unwrap();
+    let right = WriteSetMut::new(vec![
+        (access_paths[2].clone(), WriteOp::Value(vec![2])),
+        (access_paths[3].clone(), WriteOp::Deletion),
+    ])
+    .freeze()
+    .unwrap();
+    let merged = left.merge(&right);
+    assert_eq!(merged.len(), 4);
+    assert_eq!(merged, right.merge(&left));
+    assert_eq!(merged.get(&access_paths[0]), Some(&WriteOp::Value(vec![0])));
+    assert_eq!(merged.get(&access_paths[3]), Some(&WriteOp::Deletion));
+    access_paths.sort();
+    assert_eq!(
+        merged
+            .iter()
+            .map(|(access_path, _)| access_path.clone())
+            .collect::<Vec<_>>(),
+        access_paths
+    );
+}
+
+#[test]
+fn test_write_set_merge_overlapping() {
+    let overridden = AccessPath::random_resource();
+    let deleted = AccessPath::random_resource();
+    let kept = AccessPath::random_resource();
+    let left = WriteSetMut::new(vec![
+        (overridden.clone(), WriteOp::Value(vec![1])),
+        (deleted.clone(), WriteOp::Value(vec![1])),
+        (kept.clone(), WriteOp::Value(vec![1])),
+    ])
+    .freeze()
+    .unwrap();
+    let right = WriteSetMut::new(vec![
+        (overridden.clone(), WriteOp::Value(vec![2])),
+        (deleted.clone(), WriteOp::Deletion),
+    ])
+    .freeze()
+    .unwrap();
+    let merged = left.merge(&right);
+    assert_eq!(merged.len(), 3);
+    assert_eq!(merged.get(&overridden), Some(&WriteOp::Value(vec![2])));
+    assert_eq!(merged.get(&deleted), Some(&WriteOp::Deletion));
+    assert_eq!(merged.get(&kept), Some(&WriteOp::Value(vec![1])));
+    // the later write wins.
+    assert_eq!(
+        right.merge(&left).get(&deleted),
+        Some(&WriteOp::Value(vec![1]))
+    );
+    assert_eq!(left.merge(&WriteSet::default()), left);
+}
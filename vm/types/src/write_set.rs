@@ -5,8 +5,10 @@
 //! path it updates. For each access path, the VM can either give its new value or delete it.
 
 use crate::access_path::AccessPath;
-use anyhow::Result;
+use anyhow::{Error, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
 
 #[derive(Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum WriteOp {
@@ -43,7 +45,10 @@ impl std::fmt::Debug for WriteOp {
 /// `WriteSet` contains all access paths that one transaction modifies. Each of them is a `WriteOp`
 /// where `Value(val)` means that serialized representation should be updated to `val`, and
 /// `Deletion` means that we are going to delete this access path.
+///
+/// The entries are sorted by the access path, each access path appears at most once.
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "WriteSetMut")]
 pub struct WriteSet(WriteSetMut);
 
 impl WriteSet {
@@ -53,8 +58,38 @@ impl WriteSet {
     }
 
     #[inline]
-    pub fn iter(&self) -> ::std::slice::Iter<'_, (AccessPath, WriteOp)> {
-        self.into_iter()
+    pub fn len(&self) -> usize {
+        self.0.write_set.len()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&AccessPath, &WriteOp)> {
+        self.0
+            .write_set
+            .iter()
+            .map(|(access_path, write_op)| (access_path, write_op))
+    }
+
+    /// Get the write of the access path, by a binary search on the sorted entries.
+    pub fn get(&self, access_path: &AccessPath) -> Option<&WriteOp> {
+        self.0
+            .write_set
+            .binary_search_by(|(probe, _)| probe.cmp(access_path))
+            .ok()
+            .map(|idx| &self.0.write_set[idx].1)
+    }
+
+    /// Merge the writes of `other` after the writes of `self`, the write in `other` wins on the
+    /// same access path, a deletion included.
+    pub fn merge(&self, other: &WriteSet) -> WriteSet {
+        let write_set: BTreeMap<AccessPath, WriteOp> = self
+            .0
+            .write_set
+            .iter()
+            .chain(other.0.write_set.iter())
+            .cloned()
+            .collect();
+        WriteSet(WriteSetMut::new(write_set.into_iter().collect()))
     }
 
     #[inline]
@@ -85,9 +120,20 @@ impl WriteSetMut {
         self.write_set.is_empty()
     }
 
+    /// Sort the writes by the access path, the later write of a duplicated access path wins,
+    /// as it does when the writes are applied in order.
     pub fn freeze(self) -> Result<WriteSet> {
         // TODO: add structural validation
-        Ok(WriteSet(self))
+        let write_set: BTreeMap<AccessPath, WriteOp> = self.write_set.into_iter().collect();
+        Ok(WriteSet(WriteSetMut::new(write_set.into_iter().collect())))
+    }
+}
+
+impl TryFrom<WriteSetMut> for WriteSet {
+    type Error = Error;
+
+    fn try_from(write_set: WriteSetMut) -> Result<Self> {
+        write_set.freeze()
     }
 }
 